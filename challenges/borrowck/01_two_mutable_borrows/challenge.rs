@@ -0,0 +1,16 @@
+// Two mutable borrows
+//
+// Both `first` and `second` borrow `list` mutably at the same time.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0499
+// OUTPUT: [1, 2, 3, 4, 5]
+
+fn main() {
+    let mut list = vec![1, 2, 3];
+    let first = &mut list;
+    let second = &mut list;
+    first.push(4);
+    second.push(5);
+    println!("{:?}", list);
+}
@@ -0,0 +1,15 @@
+// Reading while writing
+//
+// `total` is read through a shared borrow while a mutable borrow is still in use.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: reader sees 15
+
+fn main() {
+    let mut total = 10;
+    let writer = &mut total;
+    let reader = &total;
+    *writer += 5;
+    println!("reader sees {}", reader);
+}
@@ -0,0 +1,7 @@
+fn main() {
+    let mut total = 10;
+    let writer = &mut total;
+    *writer += 5;
+    let reader = &total;
+    println!("reader sees {}", reader);
+}
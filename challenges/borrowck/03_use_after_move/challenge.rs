@@ -0,0 +1,13 @@
+// Use after move
+//
+// `s1` is moved into `s2` and then used again.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: hello and hello
+
+fn main() {
+    let s1 = String::from("hello");
+    let s2 = s1;
+    println!("{} and {}", s1, s2);
+}
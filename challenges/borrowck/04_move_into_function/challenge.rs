@@ -0,0 +1,19 @@
+// Moved into a function
+//
+// `shout` takes ownership of `name`, so `main` can't print it afterwards.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: FERRIS!
+// OUTPUT: ferris has 6 letters
+
+fn shout(text: String) -> usize {
+    println!("{}!", text.to_uppercase());
+    text.len()
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let length = shout(name);
+    println!("{} has {} letters", name, length);
+}
@@ -0,0 +1,10 @@
+fn shout(text: &str) -> usize {
+    println!("{}!", text.to_uppercase());
+    text.len()
+}
+
+fn main() {
+    let name = String::from("ferris");
+    let length = shout(&name);
+    println!("{} has {} letters", name, length);
+}
@@ -0,0 +1,16 @@
+// Returning a reference to a local
+//
+// `greeting` returns a reference to a `String` that is dropped when the function returns.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0515
+// OUTPUT: Hello, Ferris!
+
+fn greeting(name: &str) -> &str {
+    let message = format!("Hello, {}!", name);
+    &message
+}
+
+fn main() {
+    println!("{}", greeting("Ferris"));
+}
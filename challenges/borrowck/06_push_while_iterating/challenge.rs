@@ -0,0 +1,17 @@
+// Pushing while iterating
+//
+// The loop pushes into `v3` while it is being iterated over through `&v3`.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: [1, 2, 3, 10, 30]
+
+fn main() {
+    let mut v3 = vec![1, 2, 3];
+    for x in &v3 {
+        if x % 2 == 1 {
+            v3.push(x * 10);
+        }
+    }
+    println!("{:?}", v3);
+}
@@ -0,0 +1,11 @@
+fn main() {
+    let mut v3 = vec![1, 2, 3];
+    let mut extra = Vec::new();
+    for x in &v3 {
+        if x % 2 == 1 {
+            extra.push(x * 10);
+        }
+    }
+    v3.extend(extra);
+    println!("{:?}", v3);
+}
@@ -0,0 +1,23 @@
+// An excerpt outliving its novel
+//
+// `ImportantExcerpt` borrows from `novel`, but `novel` is dropped before the excerpt is used.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0597
+// OUTPUT: Call me Ishmael
+
+struct ImportantExcerpt<'a> {
+    part: &'a str,
+}
+
+fn main() {
+    let excerpt;
+    {
+        let novel = String::from("Call me Ishmael. Some years ago...");
+        let first_sentence = novel.split('.').next().unwrap();
+        excerpt = ImportantExcerpt {
+            part: first_sentence,
+        };
+    }
+    println!("{}", excerpt.part);
+}
@@ -0,0 +1,15 @@
+struct ImportantExcerpt<'a> {
+    part: &'a str,
+}
+
+fn main() {
+    let excerpt;
+    let novel = String::from("Call me Ishmael. Some years ago...");
+    {
+        let first_sentence = novel.split('.').next().unwrap();
+        excerpt = ImportantExcerpt {
+            part: first_sentence,
+        };
+    }
+    println!("{}", excerpt.part);
+}
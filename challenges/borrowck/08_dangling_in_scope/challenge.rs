@@ -0,0 +1,16 @@
+// A dangling reference
+//
+// `r` refers to `x`, which goes out of scope before `r` is printed.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0597
+// OUTPUT: r: 5
+
+fn main() {
+    let r;
+    {
+        let x = 5;
+        r = &x;
+    }
+    println!("r: {}", r);
+}
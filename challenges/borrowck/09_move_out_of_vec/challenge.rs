@@ -0,0 +1,13 @@
+// Moving out of a vector
+//
+// `let first = names[0];` tries to move a `String` out of the vector.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0507
+// OUTPUT: Ada of 2
+
+fn main() {
+    let names = vec![String::from("Ada"), String::from("Grace")];
+    let first = names[0];
+    println!("{} of {}", first, names.len());
+}
@@ -0,0 +1,5 @@
+fn main() {
+    let names = vec![String::from("Ada"), String::from("Grace")];
+    let first = &names[0];
+    println!("{} of {}", first, names.len());
+}
@@ -0,0 +1,18 @@
+// Moving out of a reference
+//
+// `*borrowed` tries to take the `String` out from behind a shared reference.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0507
+// OUTPUT: The Rust Programming Language / The Rust Programming Language
+
+fn take_title(borrowed: &String) -> String {
+    let title = *borrowed;
+    title
+}
+
+fn main() {
+    let book = String::from("The Rust Programming Language");
+    let title = take_title(&book);
+    println!("{} / {}", title, book);
+}
@@ -0,0 +1,10 @@
+fn take_title(borrowed: &String) -> String {
+    let title = borrowed.clone();
+    title
+}
+
+fn main() {
+    let book = String::from("The Rust Programming Language");
+    let title = take_title(&book);
+    println!("{} / {}", title, book);
+}
@@ -0,0 +1,15 @@
+// Assigning twice to an immutable variable
+//
+// `count` is assigned a second time, but it isn't declared `mut`.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0384
+// OUTPUT: count = 1
+// OUTPUT: count = 2
+
+fn main() {
+    let count = 1;
+    println!("count = {}", count);
+    count = 2;
+    println!("count = {}", count);
+}
@@ -0,0 +1,6 @@
+fn main() {
+    let mut count = 1;
+    println!("count = {}", count);
+    count = 2;
+    println!("count = {}", count);
+}
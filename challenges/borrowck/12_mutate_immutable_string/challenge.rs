@@ -0,0 +1,13 @@
+// Mutating an immutable binding
+//
+// `push_str` needs `&mut self`, but `s` isn't mutable.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0596
+// OUTPUT: hello, world
+
+fn main() {
+    let s = String::from("hello");
+    s.push_str(", world");
+    println!("{}", s);
+}
@@ -0,0 +1,17 @@
+// Mutating through `&`
+//
+// `change` tries to modify a string it only borrowed immutably.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0596
+// OUTPUT: hello, world
+
+fn change(some_string: &String) {
+    some_string.push_str(", world");
+}
+
+fn main() {
+    let mut s = String::from("hello");
+    change(&s);
+    println!("{}", s);
+}
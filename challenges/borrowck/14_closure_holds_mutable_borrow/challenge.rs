@@ -0,0 +1,16 @@
+// A closure holding a mutable borrow
+//
+// The closure `bump` borrows `count` mutably for as long as the closure is used.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: after two bumps: 2
+
+fn main() {
+    let mut count = 0;
+    let mut bump = || count += 1;
+    bump();
+    println!("after one bump: {}", count);
+    bump();
+    println!("after two bumps: {}", count);
+}
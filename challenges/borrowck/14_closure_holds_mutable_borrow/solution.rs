@@ -0,0 +1,7 @@
+fn main() {
+    let mut count = 0;
+    let mut bump = || count += 1;
+    bump();
+    bump();
+    println!("after two bumps: {}", count);
+}
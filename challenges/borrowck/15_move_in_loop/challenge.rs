@@ -0,0 +1,19 @@
+// Moving inside a loop
+//
+// `consume` takes `message` by value, and the loop tries to do that twice.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: consumed: ping
+// OUTPUT: consumed: ping
+
+fn consume(message: String) {
+    println!("consumed: {}", message);
+}
+
+fn main() {
+    let message = String::from("ping");
+    for _ in 0..2 {
+        consume(message);
+    }
+}
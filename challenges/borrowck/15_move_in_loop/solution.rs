@@ -0,0 +1,10 @@
+fn consume(message: String) {
+    println!("consumed: {}", message);
+}
+
+fn main() {
+    let message = String::from("ping");
+    for _ in 0..2 {
+        consume(message.clone());
+    }
+}
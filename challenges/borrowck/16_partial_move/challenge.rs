@@ -0,0 +1,24 @@
+// A partial move
+//
+// Moving `user.name` out of the struct leaves `user` partially moved.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: Ferris is 8
+// OUTPUT: User { name: "Ferris", age: 8 }
+
+#[derive(Debug)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+fn main() {
+    let user = User {
+        name: String::from("Ferris"),
+        age: 8,
+    };
+    let name = user.name;
+    println!("{} is {}", name, user.age);
+    println!("{:?}", user);
+}
@@ -0,0 +1,15 @@
+#[derive(Debug)]
+struct User {
+    name: String,
+    age: u32,
+}
+
+fn main() {
+    let user = User {
+        name: String::from("Ferris"),
+        age: 8,
+    };
+    let name = &user.name;
+    println!("{} is {}", name, user.age);
+    println!("{:?}", user);
+}
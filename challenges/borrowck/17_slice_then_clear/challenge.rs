@@ -0,0 +1,18 @@
+// Clearing a borrowed string
+//
+// `word` borrows from `s`, so `s` can't be cleared while `word` is still used.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: the first word is: hello
+
+fn first_word(s: &str) -> &str {
+    s.split(' ').next().unwrap_or("")
+}
+
+fn main() {
+    let mut s = String::from("hello world");
+    let word = first_word(&s);
+    s.clear();
+    println!("the first word is: {}", word);
+}
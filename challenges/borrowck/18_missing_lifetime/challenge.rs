@@ -0,0 +1,19 @@
+// A missing lifetime
+//
+// The compiler can't tell which input the returned reference borrows from.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0106
+// OUTPUT: abcd
+
+fn longest(x: &str, y: &str) -> &str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    println!("{}", longest("abcd", "xyz"));
+}
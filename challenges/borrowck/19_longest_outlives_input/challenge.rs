@@ -0,0 +1,25 @@
+// A result outliving an input
+//
+// The result of `longest` may point into `string2`, which is dropped too early.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0597
+// OUTPUT: The longest string is long string is long
+
+fn longest<'a>(x: &'a str, y: &'a str) -> &'a str {
+    if x.len() > y.len() {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    let string1 = String::from("long string is long");
+    let result;
+    {
+        let string2 = String::from("xyz");
+        result = longest(string1.as_str(), string2.as_str());
+    }
+    println!("The longest string is {}", result);
+}
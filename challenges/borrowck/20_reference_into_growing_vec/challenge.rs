@@ -0,0 +1,14 @@
+// A reference into a growing vector
+//
+// `first` points into `v`, and `push` may move the vector's buffer.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: The first element is: 1
+
+fn main() {
+    let mut v = vec![1, 2, 3, 4, 5];
+    let first = &v[0];
+    v.push(6);
+    println!("The first element is: {}", first);
+}
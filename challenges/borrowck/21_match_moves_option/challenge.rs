@@ -0,0 +1,17 @@
+// A match that moves
+//
+// Matching on `maybe_name` by value moves the `String` out of the `Option`.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: Hello, Ferris!
+// OUTPUT: still have Some("Ferris")
+
+fn main() {
+    let maybe_name = Some(String::from("Ferris"));
+    match maybe_name {
+        Some(name) => println!("Hello, {}!", name),
+        None => println!("Hello, stranger!"),
+    }
+    println!("still have {:?}", maybe_name);
+}
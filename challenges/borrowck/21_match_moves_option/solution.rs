@@ -0,0 +1,8 @@
+fn main() {
+    let maybe_name = Some(String::from("Ferris"));
+    match &maybe_name {
+        Some(name) => println!("Hello, {}!", name),
+        None => println!("Hello, stranger!"),
+    }
+    println!("still have {:?}", maybe_name);
+}
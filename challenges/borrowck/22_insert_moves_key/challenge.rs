@@ -0,0 +1,17 @@
+// Keys moved into a map
+//
+// `HashMap::insert` takes ownership of both the key and the value.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: Favorite color = Blue
+
+use std::collections::HashMap;
+
+fn main() {
+    let field_name = String::from("Favorite color");
+    let field_value = String::from("Blue");
+    let mut map = HashMap::new();
+    map.insert(field_name, field_value);
+    println!("{} = {}", field_name, map["Favorite color"]);
+}
@@ -0,0 +1,17 @@
+// A thread borrowing a local
+//
+// The spawned thread might outlive `v`, which it only borrows.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0373
+// OUTPUT: Here's a vector: [1, 2, 3]
+
+use std::thread;
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let handle = thread::spawn(|| {
+        println!("Here's a vector: {:?}", v);
+    });
+    handle.join().unwrap();
+}
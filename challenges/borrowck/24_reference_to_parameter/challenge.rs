@@ -0,0 +1,16 @@
+// A reference to a parameter
+//
+// `first` returns a reference into `words`, which `first` owns and drops.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0515
+// OUTPUT: alpha
+
+fn first(words: Vec<String>) -> &'static String {
+    &words[0]
+}
+
+fn main() {
+    let words = vec![String::from("alpha"), String::from("beta")];
+    println!("{}", first(words));
+}
@@ -0,0 +1,8 @@
+fn first(words: &[String]) -> &String {
+    &words[0]
+}
+
+fn main() {
+    let words = vec![String::from("alpha"), String::from("beta")];
+    println!("{}", first(&words));
+}
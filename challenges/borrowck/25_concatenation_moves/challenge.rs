@@ -0,0 +1,14 @@
+// Concatenation moves
+//
+// `s1 + &s2` moves `s1` into the result.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: Hello,  + world! = Hello, world!
+
+fn main() {
+    let s1 = String::from("Hello, ");
+    let s2 = String::from("world!");
+    let s3 = s1 + &s2;
+    println!("{} + {} = {}", s1, s2, s3);
+}
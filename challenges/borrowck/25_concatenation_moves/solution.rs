@@ -0,0 +1,6 @@
+fn main() {
+    let s1 = String::from("Hello, ");
+    let s2 = String::from("world!");
+    let s3 = format!("{}{}", s1, s2);
+    println!("{} + {} = {}", s1, s2, s3);
+}
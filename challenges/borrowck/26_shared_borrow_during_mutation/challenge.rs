@@ -0,0 +1,17 @@
+// A shared borrow during mutation
+//
+// `peek` reads `s` while the mutable borrow `editor` is still used afterwards.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: peeking at draft
+// OUTPUT: draft v2
+
+fn main() {
+    let mut s = String::from("draft");
+    let editor = &mut s;
+    let peek = &s;
+    println!("peeking at {}", peek);
+    editor.push_str(" v2");
+    println!("{}", s);
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let mut s = String::from("draft");
+    let peek = &s;
+    println!("peeking at {}", peek);
+    let editor = &mut s;
+    editor.push_str(" v2");
+    println!("{}", s);
+}
@@ -0,0 +1,15 @@
+// Two mutable borrows into one vector
+//
+// `a` and `b` both borrow `v` mutably, even though they point at different elements.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0499
+// OUTPUT: ["right", "left"]
+
+fn main() {
+    let mut v = vec![String::from("left"), String::from("right")];
+    let a = &mut v[0];
+    let b = &mut v[1];
+    std::mem::swap(a, b);
+    println!("{:?}", v);
+}
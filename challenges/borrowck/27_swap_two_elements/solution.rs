@@ -0,0 +1,6 @@
+fn main() {
+    let mut v = vec![String::from("left"), String::from("right")];
+    let (front, back) = v.split_at_mut(1);
+    std::mem::swap(&mut front[0], &mut back[0]);
+    println!("{:?}", v);
+}
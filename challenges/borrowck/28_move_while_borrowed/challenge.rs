@@ -0,0 +1,20 @@
+// Moving a borrowed value
+//
+// `user` is moved into `archived` while `name` still borrows from it.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0505
+// OUTPUT: Ferris archived Ferris
+
+struct User {
+    name: String,
+}
+
+fn main() {
+    let user = User {
+        name: String::from("Ferris"),
+    };
+    let name = &user.name;
+    let archived = user;
+    println!("{} archived {}", name, archived.name);
+}
@@ -0,0 +1,12 @@
+struct User {
+    name: String,
+}
+
+fn main() {
+    let user = User {
+        name: String::from("Ferris"),
+    };
+    let archived = user;
+    let name = &archived.name;
+    println!("{} archived {}", name, archived.name);
+}
@@ -0,0 +1,14 @@
+// Dropping a borrowed value
+//
+// `drop(s)` moves `s` while `r` still refers to it.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0505
+// OUTPUT: temporary
+
+fn main() {
+    let s = String::from("temporary");
+    let r = &s;
+    drop(s);
+    println!("{}", r);
+}
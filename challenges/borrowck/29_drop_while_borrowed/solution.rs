@@ -0,0 +1,6 @@
+fn main() {
+    let s = String::from("temporary");
+    let r = &s;
+    println!("{}", r);
+    drop(s);
+}
@@ -0,0 +1,16 @@
+// Storing short-lived references
+//
+// `names` keeps references to strings that only live inside the loop.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0597
+// OUTPUT: ["guest 1", "guest 2", "guest 3"]
+
+fn main() {
+    let mut names: Vec<&String> = Vec::new();
+    for i in 1..=3 {
+        let name = format!("guest {}", i);
+        names.push(&name);
+    }
+    println!("{:?}", names);
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let mut names: Vec<String> = Vec::new();
+    for i in 1..=3 {
+        let name = format!("guest {}", i);
+        names.push(name);
+    }
+    println!("{:?}", names);
+}
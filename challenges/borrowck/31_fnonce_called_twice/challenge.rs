@@ -0,0 +1,19 @@
+// Calling a `FnOnce` twice
+//
+// The closure moves `report` into `send`, so it can only be called once.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: sent weekly report
+// OUTPUT: sent weekly report
+
+fn send(report: String) {
+    println!("sent {}", report);
+}
+
+fn main() {
+    let report = String::from("weekly report");
+    let deliver = move || send(report);
+    deliver();
+    deliver();
+}
@@ -0,0 +1,10 @@
+fn send(report: &str) {
+    println!("sent {}", report);
+}
+
+fn main() {
+    let report = String::from("weekly report");
+    let deliver = move || send(&report);
+    deliver();
+    deliver();
+}
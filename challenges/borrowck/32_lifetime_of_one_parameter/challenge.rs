@@ -0,0 +1,19 @@
+// The wrong parameter's lifetime
+//
+// `pick` promises to return something that lives as long as `x`, but may return `y`.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0621
+// OUTPUT: banana
+
+fn pick<'a>(x: &'a str, y: &str, first: bool) -> &'a str {
+    if first {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    println!("{}", pick("apple", "banana", false));
+}
@@ -0,0 +1,11 @@
+fn pick<'a>(x: &'a str, y: &'a str, first: bool) -> &'a str {
+    if first {
+        x
+    } else {
+        y
+    }
+}
+
+fn main() {
+    println!("{}", pick("apple", "banana", false));
+}
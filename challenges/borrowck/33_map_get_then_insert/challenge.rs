@@ -0,0 +1,17 @@
+// Reading from a map while inserting
+//
+// `score` borrows from `scores`, which is then modified.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0502
+// OUTPUT: Blue has 10, 2 teams
+
+use std::collections::HashMap;
+
+fn main() {
+    let mut scores = HashMap::new();
+    scores.insert("Blue", 10);
+    let score = scores.get("Blue").unwrap();
+    scores.insert("Yellow", 50);
+    println!("Blue has {}, {} teams", score, scores.len());
+}
@@ -0,0 +1,9 @@
+use std::collections::HashMap;
+
+fn main() {
+    let mut scores = HashMap::new();
+    scores.insert("Blue", 10);
+    let score = *scores.get("Blue").unwrap();
+    scores.insert("Yellow", 50);
+    println!("Blue has {}, {} teams", score, scores.len());
+}
@@ -0,0 +1,19 @@
+// A mutable borrow of an immutable vector
+//
+// `&mut v` needs `v` to be declared mutable.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0596
+// OUTPUT: [2, 3, 4]
+
+fn add_one(values: &mut Vec<i32>) {
+    for value in values.iter_mut() {
+        *value += 1;
+    }
+}
+
+fn main() {
+    let v = vec![1, 2, 3];
+    add_one(&mut v);
+    println!("{:?}", v);
+}
@@ -0,0 +1,11 @@
+fn add_one(values: &mut Vec<i32>) {
+    for value in values.iter_mut() {
+        *value += 1;
+    }
+}
+
+fn main() {
+    let mut v = vec![1, 2, 3];
+    add_one(&mut v);
+    println!("{:?}", v);
+}
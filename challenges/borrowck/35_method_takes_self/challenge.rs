@@ -0,0 +1,27 @@
+// A method that takes `self`
+//
+// `into_summary` takes the article by value, so it can't be used afterwards.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0382
+// OUTPUT: Read more: Ferris learns Rust
+// OUTPUT: Ferris learns Rust
+
+struct Article {
+    headline: String,
+}
+
+impl Article {
+    fn into_summary(self) -> String {
+        format!("Read more: {}", self.headline)
+    }
+}
+
+fn main() {
+    let article = Article {
+        headline: String::from("Ferris learns Rust"),
+    };
+    let summary = article.into_summary();
+    println!("{}", summary);
+    println!("{}", article.headline);
+}
@@ -0,0 +1,18 @@
+struct Article {
+    headline: String,
+}
+
+impl Article {
+    fn summary(&self) -> String {
+        format!("Read more: {}", self.headline)
+    }
+}
+
+fn main() {
+    let article = Article {
+        headline: String::from("Ferris learns Rust"),
+    };
+    let summary = article.summary();
+    println!("{}", summary);
+    println!("{}", article.headline);
+}
@@ -0,0 +1,17 @@
+// An iterator outliving its vector
+//
+// `evens` borrows from `numbers`, which is dropped at the end of the block.
+// Fix it so that it compiles and prints exactly the output below.
+//
+// EXPECT_ERROR: E0597
+// OUTPUT: [2, 4, 6]
+
+fn main() {
+    let evens;
+    {
+        let numbers = vec![1, 2, 3, 4, 5, 6];
+        evens = numbers.iter().filter(|n| *n % 2 == 0);
+    }
+    let evens: Vec<&i32> = evens.collect();
+    println!("{:?}", evens);
+}
@@ -0,0 +1,8 @@
+fn main() {
+    let evens: Vec<i32>;
+    {
+        let numbers = vec![1, 2, 3, 4, 5, 6];
+        evens = numbers.into_iter().filter(|n| n % 2 == 0).collect();
+    }
+    println!("{:?}", evens);
+}
@@ -0,0 +1,27 @@
+// First word
+//
+// Write `first_word`, which returns the first word of a string as a slice of it.
+// Words are separated by spaces, and leading spaces are not part of any word:
+// the first word of "  hi there" is "hi". A string with no words gives "".
+//
+// `rust_tutor exercise check first_word` runs hidden tests against your code,
+// so keep the signature of `first_word` as it is.
+
+fn first_word(s: &str) -> &str {
+    todo!("return the first word of {:?}", s)
+}
+
+fn main() {
+    let s = String::from("hello world");
+    println!("the first word is: {}", first_word(&s));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_words() {
+        assert_eq!(first_word("hello world"), "hello");
+    }
+}
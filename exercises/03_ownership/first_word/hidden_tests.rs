@@ -0,0 +1,38 @@
+use super::*;
+
+#[test]
+fn two_words() {
+    assert_eq!(first_word("hello world"), "hello");
+}
+
+#[test]
+fn no_spaces() {
+    assert_eq!(first_word("hello"), "hello");
+}
+
+#[test]
+fn empty_string() {
+    assert_eq!(first_word(""), "");
+}
+
+#[test]
+fn only_spaces() {
+    assert_eq!(first_word("   "), "");
+}
+
+#[test]
+fn leading_whitespace() {
+    assert_eq!(first_word("  hi there"), "hi");
+}
+
+#[test]
+fn trailing_space() {
+    assert_eq!(first_word("end "), "end");
+}
+
+#[test]
+fn result_borrows_from_the_input() {
+    let s = String::from("slice of life");
+    let word = first_word(&s);
+    assert_eq!(word.as_ptr(), s.as_ptr());
+}
@@ -0,0 +1,24 @@
+// First word
+
+fn first_word(s: &str) -> &str {
+    let s = s.trim_start_matches(' ');
+    match s.find(' ') {
+        Some(end) => &s[..end],
+        None => s,
+    }
+}
+
+fn main() {
+    let s = String::from("hello world");
+    println!("the first word is: {}", first_word(&s));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn two_words() {
+        assert_eq!(first_word("hello world"), "hello");
+    }
+}
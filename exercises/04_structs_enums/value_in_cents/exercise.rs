@@ -0,0 +1,32 @@
+// Value in cents
+//
+// Write `value_in_cents`, which returns how many cents a `Coin` is worth:
+// a penny is 1, a nickel 5, a dime 10 and a quarter 25, whatever its state.
+//
+// `rust_tutor exercise check value_in_cents` runs hidden tests against your code,
+// so keep the `Coin` and `UsState` types and the signature of `value_in_cents` as they are.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UsState {
+    Alabama,
+    Alaska,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+fn value_in_cents(coin: Coin) -> u8 {
+    todo!("match on {:?}", coin)
+}
+
+fn main() {
+    let coins = [Coin::Penny, Coin::Dime, Coin::Quarter(UsState::Alaska)];
+    for coin in coins {
+        println!("{:?} is worth {} cents", coin, value_in_cents(coin));
+    }
+}
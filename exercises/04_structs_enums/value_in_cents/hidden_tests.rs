@@ -0,0 +1,26 @@
+use super::*;
+
+#[test]
+fn penny() {
+    assert_eq!(value_in_cents(Coin::Penny), 1);
+}
+
+#[test]
+fn nickel() {
+    assert_eq!(value_in_cents(Coin::Nickel), 5);
+}
+
+#[test]
+fn dime() {
+    assert_eq!(value_in_cents(Coin::Dime), 10);
+}
+
+#[test]
+fn quarter_from_alabama() {
+    assert_eq!(value_in_cents(Coin::Quarter(UsState::Alabama)), 25);
+}
+
+#[test]
+fn quarter_from_alaska() {
+    assert_eq!(value_in_cents(Coin::Quarter(UsState::Alaska)), 25);
+}
@@ -0,0 +1,31 @@
+// Value in cents
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum UsState {
+    Alabama,
+    Alaska,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+fn value_in_cents(coin: Coin) -> u8 {
+    match coin {
+        Coin::Penny => 1,
+        Coin::Nickel => 5,
+        Coin::Dime => 10,
+        Coin::Quarter(_) => 25,
+    }
+}
+
+fn main() {
+    let coins = [Coin::Penny, Coin::Dime, Coin::Quarter(UsState::Alaska)];
+    for coin in coins {
+        println!("{:?} is worth {} cents", coin, value_in_cents(coin));
+    }
+}
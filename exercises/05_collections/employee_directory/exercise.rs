@@ -0,0 +1,66 @@
+// Employee directory
+//
+// A company directory takes text commands like "Add Sally to Engineering" and lists who works
+// where. Write the methods of `Directory`:
+//
+// - `run` carries out one command. "Add <name> to <department>" adds the person to the
+//   department; names and departments can have several words ("Add Mary Ann to Human
+//   Resources"). Adding someone who is already there changes nothing. Anything else is an
+//   error: return `Err` with a message that includes the command.
+// - `department` lists the people in a department, sorted alphabetically, or nothing if
+//   there is no such department.
+// - `everyone` lists every department with its people, departments and people both sorted.
+//
+// You can change the fields of `Directory`: the `BTreeMap` is a suggestion.
+//
+// `rust_tutor exercise check employee_directory` runs hidden tests against your code,
+// so keep the signatures as they are.
+
+use std::collections::BTreeMap;
+
+struct Directory {
+    departments: BTreeMap<String, Vec<String>>,
+}
+
+impl Directory {
+    fn new() -> Directory {
+        Directory {
+            departments: BTreeMap::new(),
+        }
+    }
+
+    fn run(&mut self, command: &str) -> Result<(), String> {
+        todo!(
+            "carry out {:?} on {} department(s)",
+            command,
+            self.departments.len()
+        )
+    }
+
+    fn department(&self, name: &str) -> Vec<String> {
+        todo!("the people in {}", name)
+    }
+
+    fn everyone(&self) -> Vec<(String, Vec<String>)> {
+        todo!("every department, with its people")
+    }
+}
+
+fn main() {
+    let mut directory = Directory::new();
+    let commands = [
+        "Add Sally to Engineering",
+        "Add Amir to Sales",
+        "Add Bob to Engineering",
+        "Fire everyone",
+    ];
+    for command in commands {
+        if let Err(message) = directory.run(command) {
+            println!("error: {}", message);
+        }
+    }
+    println!("Engineering: {:?}", directory.department("Engineering"));
+    for (department, people) in directory.everyone() {
+        println!("{}: {}", department, people.join(", "));
+    }
+}
@@ -0,0 +1,79 @@
+use super::*;
+
+fn directory(commands: &[&str]) -> Directory {
+    let mut directory = Directory::new();
+    for command in commands {
+        directory.run(command).unwrap();
+    }
+    directory
+}
+
+#[test]
+fn people_in_a_department_are_sorted() {
+    let directory = directory(&[
+        "Add Sally to Engineering",
+        "Add Amir to Sales",
+        "Add Bob to Engineering",
+    ]);
+    assert_eq!(directory.department("Engineering"), ["Bob", "Sally"]);
+    assert_eq!(directory.department("Sales"), ["Amir"]);
+}
+
+#[test]
+fn unknown_department_is_empty() {
+    let directory = directory(&["Add Sally to Engineering"]);
+    assert!(directory.department("Marketing").is_empty());
+    assert!(Directory::new().department("Engineering").is_empty());
+}
+
+#[test]
+fn everyone_by_department() {
+    let directory = directory(&[
+        "Add Zoe to Sales",
+        "Add Sally to Engineering",
+        "Add Amir to Sales",
+        "Add Bob to Engineering",
+    ]);
+    assert_eq!(
+        directory.everyone(),
+        [
+            (
+                String::from("Engineering"),
+                vec![String::from("Bob"), String::from("Sally")]
+            ),
+            (
+                String::from("Sales"),
+                vec![String::from("Amir"), String::from("Zoe")]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn names_and_departments_with_spaces() {
+    let directory = directory(&["Add Mary Ann to Human Resources"]);
+    assert_eq!(directory.department("Human Resources"), ["Mary Ann"]);
+}
+
+#[test]
+fn adding_twice_changes_nothing() {
+    let directory = directory(&["Add Sally to Engineering", "Add Sally to Engineering"]);
+    assert_eq!(directory.department("Engineering"), ["Sally"]);
+}
+
+#[test]
+fn someone_can_be_in_two_departments() {
+    let directory = directory(&["Add Sally to Engineering", "Add Sally to Sales"]);
+    assert_eq!(directory.department("Engineering"), ["Sally"]);
+    assert_eq!(directory.department("Sales"), ["Sally"]);
+}
+
+#[test]
+fn bad_commands_are_errors() {
+    let mut directory = Directory::new();
+    for command in ["Fire everyone", "Add Sally", "Sally to Engineering", ""] {
+        let message = directory.run(command).unwrap_err();
+        assert!(message.contains(command), "{:?}", message);
+    }
+    assert!(directory.everyone().is_empty());
+}
@@ -0,0 +1,65 @@
+// Employee directory
+
+use std::collections::{BTreeMap, BTreeSet};
+
+struct Directory {
+    // A set keeps each department's people sorted and drops duplicates.
+    departments: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Directory {
+    fn new() -> Directory {
+        Directory {
+            departments: BTreeMap::new(),
+        }
+    }
+
+    fn run(&mut self, command: &str) -> Result<(), String> {
+        let (name, department) = command
+            .strip_prefix("Add ")
+            .and_then(|rest| rest.split_once(" to "))
+            .ok_or_else(|| format!("expected `Add <name> to <department>`, not {:?}", command))?;
+        let (name, department) = (name.trim(), department.trim());
+        if name.is_empty() || department.is_empty() {
+            return Err(format!("a name and a department are needed: {:?}", command));
+        }
+        self.departments
+            .entry(department.to_string())
+            .or_default()
+            .insert(name.to_string());
+        Ok(())
+    }
+
+    fn department(&self, name: &str) -> Vec<String> {
+        match self.departments.get(name) {
+            Some(people) => people.iter().cloned().collect(),
+            None => Vec::new(),
+        }
+    }
+
+    fn everyone(&self) -> Vec<(String, Vec<String>)> {
+        self.departments
+            .iter()
+            .map(|(department, people)| (department.clone(), people.iter().cloned().collect()))
+            .collect()
+    }
+}
+
+fn main() {
+    let mut directory = Directory::new();
+    let commands = [
+        "Add Sally to Engineering",
+        "Add Amir to Sales",
+        "Add Bob to Engineering",
+        "Fire everyone",
+    ];
+    for command in commands {
+        if let Err(message) = directory.run(command) {
+            println!("error: {}", message);
+        }
+    }
+    println!("Engineering: {:?}", directory.department("Engineering"));
+    for (department, people) in directory.everyone() {
+        println!("{}: {}", department, people.join(", "));
+    }
+}
@@ -0,0 +1,31 @@
+// Median and mode
+//
+// Given a list of integers, write:
+//
+// - `median`, the value in the middle once the list is sorted. With an even number of values,
+//   it's the average of the two in the middle: the median of [1, 4, 2, 3] is 2.5.
+// - `mode`, the value that occurs most often. When several values tie, return the smallest:
+//   the mode of [3, 1, 3, 1, 2] is 1.
+//
+// Both return `None` for an empty list. Neither may change the caller's list, so sort a copy.
+//
+// `rust_tutor exercise check median_mode` runs hidden tests against your code,
+// so keep the signatures as they are.
+
+use std::collections::HashMap;
+
+fn median(numbers: &[i32]) -> Option<f64> {
+    todo!("the middle of {:?}", numbers)
+}
+
+fn mode(numbers: &[i32]) -> Option<i32> {
+    let counts: HashMap<i32, usize> = HashMap::new();
+    todo!("count each of {:?} in {:?}", numbers, counts)
+}
+
+fn main() {
+    let numbers = vec![7, 1, 5, 3, 5, 9, 1, 5];
+    println!("numbers: {:?}", numbers);
+    println!("median: {:?}", median(&numbers));
+    println!("mode: {:?}", mode(&numbers));
+}
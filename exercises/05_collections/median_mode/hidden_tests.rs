@@ -0,0 +1,43 @@
+use super::*;
+
+#[test]
+fn median_of_an_odd_count() {
+    assert_eq!(median(&[5, 1, 3]), Some(3.0));
+    assert_eq!(median(&[42]), Some(42.0));
+}
+
+#[test]
+fn median_of_an_even_count() {
+    assert_eq!(median(&[1, 4, 2, 3]), Some(2.5));
+    assert_eq!(median(&[10, -10]), Some(0.0));
+}
+
+#[test]
+fn median_with_large_values() {
+    assert_eq!(median(&[i32::MAX, i32::MAX]), Some(i32::MAX as f64));
+}
+
+#[test]
+fn median_leaves_the_list_alone() {
+    let numbers = vec![3, 1, 2];
+    median(&numbers);
+    assert_eq!(numbers, [3, 1, 2]);
+}
+
+#[test]
+fn mode_is_the_most_common() {
+    assert_eq!(mode(&[7, 1, 5, 3, 5, 9, 1, 5]), Some(5));
+    assert_eq!(mode(&[-2]), Some(-2));
+}
+
+#[test]
+fn mode_ties_go_to_the_smallest() {
+    assert_eq!(mode(&[3, 1, 3, 1, 2]), Some(1));
+    assert_eq!(mode(&[9, 8, 7]), Some(7));
+}
+
+#[test]
+fn empty_lists() {
+    assert_eq!(median(&[]), None);
+    assert_eq!(mode(&[]), None);
+}
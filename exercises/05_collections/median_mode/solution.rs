@@ -0,0 +1,36 @@
+// Median and mode
+
+use std::collections::HashMap;
+
+fn median(numbers: &[i32]) -> Option<f64> {
+    if numbers.is_empty() {
+        return None;
+    }
+    let mut sorted = numbers.to_vec();
+    sorted.sort();
+    let middle = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        Some((f64::from(sorted[middle - 1]) + f64::from(sorted[middle])) / 2.0)
+    } else {
+        Some(f64::from(sorted[middle]))
+    }
+}
+
+fn mode(numbers: &[i32]) -> Option<i32> {
+    let mut counts: HashMap<i32, usize> = HashMap::new();
+    for &n in numbers {
+        *counts.entry(n).or_insert(0) += 1;
+    }
+    // The highest count wins; on a tie, `Reverse` makes the smaller value the larger key.
+    counts
+        .into_iter()
+        .max_by_key(|&(n, count)| (count, std::cmp::Reverse(n)))
+        .map(|(n, _)| n)
+}
+
+fn main() {
+    let numbers = vec![7, 1, 5, 3, 5, 9, 1, 5];
+    println!("numbers: {:?}", numbers);
+    println!("median: {:?}", median(&numbers));
+    println!("mode: {:?}", mode(&numbers));
+}
@@ -0,0 +1,31 @@
+// Pig latin
+//
+// Convert each word of a text to pig latin:
+//
+// - A word starting with a consonant has its first letter moved to the end, after a `-`,
+//   followed by "ay": "first" becomes "irst-fay".
+// - A word starting with a vowel (a, e, i, o or u, in either case) gets "-hay" added to the
+//   end instead: "apple" becomes "apple-hay".
+//
+// Every other letter counts as a consonant, including letters outside ASCII: "ñandú" becomes
+// "andú-ñay", and "über" becomes "ber-üay". Strings are UTF-8, so a letter can take more than
+// one byte; slicing at byte 1 would panic on those.
+//
+// Words are separated by whitespace, and the result puts a single space between them.
+//
+// `rust_tutor exercise check pig_latin` runs hidden tests against your code,
+// so keep the signatures as they are.
+
+fn pig_latin_word(word: &str) -> String {
+    todo!("convert {:?}", word)
+}
+
+fn pig_latin(text: &str) -> String {
+    todo!("convert every word of {:?} with pig_latin_word", text)
+}
+
+fn main() {
+    for text in ["first apple", "the quick brown fox", "ñandú über alles"] {
+        println!("{} -> {}", text, pig_latin(text));
+    }
+}
@@ -0,0 +1,50 @@
+use super::*;
+
+#[test]
+fn consonant_words() {
+    assert_eq!(pig_latin_word("first"), "irst-fay");
+    assert_eq!(pig_latin_word("rust"), "ust-ray");
+    assert_eq!(pig_latin_word("Hello"), "ello-Hay");
+}
+
+#[test]
+fn vowel_words() {
+    assert_eq!(pig_latin_word("apple"), "apple-hay");
+    assert_eq!(pig_latin_word("Ownership"), "Ownership-hay");
+    assert_eq!(pig_latin_word("u"), "u-hay");
+}
+
+#[test]
+fn one_letter_consonant() {
+    assert_eq!(pig_latin_word("x"), "-xay");
+}
+
+#[test]
+fn letters_outside_ascii() {
+    assert_eq!(pig_latin_word("ñandú"), "andú-ñay");
+    assert_eq!(pig_latin_word("über"), "ber-üay");
+    assert_eq!(pig_latin_word("日本"), "本-日ay");
+}
+
+#[test]
+fn whole_texts() {
+    assert_eq!(pig_latin("first apple"), "irst-fay apple-hay");
+    assert_eq!(
+        pig_latin("the quick brown fox"),
+        "he-tay uick-qay rown-bay ox-fay"
+    );
+}
+
+#[test]
+fn whitespace_between_words() {
+    assert_eq!(
+        pig_latin("  ñandú\tüber\nalles  "),
+        "andú-ñay ber-üay alles-hay"
+    );
+}
+
+#[test]
+fn empty_text() {
+    assert_eq!(pig_latin(""), "");
+    assert_eq!(pig_latin("   "), "");
+}
@@ -0,0 +1,23 @@
+// Pig latin
+
+fn pig_latin_word(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        None => String::new(),
+        Some(first) if "aeiouAEIOU".contains(first) => format!("{}-hay", word),
+        Some(first) => format!("{}-{}ay", chars.as_str(), first),
+    }
+}
+
+fn pig_latin(text: &str) -> String {
+    text.split_whitespace()
+        .map(pig_latin_word)
+        .collect::<Vec<String>>()
+        .join(" ")
+}
+
+fn main() {
+    for text in ["first apple", "the quick brown fox", "ñandú über alles"] {
+        println!("{} -> {}", text, pig_latin(text));
+    }
+}
@@ -0,0 +1,58 @@
+// Username errors
+//
+// `read_username_from_file` reads a username from a file. It fails with a `UsernameError`:
+//
+//     UsernameError::Io(error)        the file couldn't be read
+//     UsernameError::Empty            the file holds nothing but whitespace
+//     UsernameError::Invalid(name)    the name isn't 1 to 16 ASCII letters, digits or `_`
+//
+// Leading and trailing whitespace around the name doesn't count. Fill in:
+//
+// - `Display`, with these messages: "couldn't read the username file", "the username file
+//   is empty", and for `Invalid("a b")`: "\"a b\" is not a valid username"
+// - `Error::source`, which returns the `io::Error` for `Io`, and `None` otherwise
+// - `From<io::Error>`, so `?` can turn an `io::Error` into a `UsernameError`
+// - `read_username_from_file` itself
+//
+// `rust_tutor exercise check username` runs hidden tests against your code, in a directory
+// holding a few username files to read, so keep the types and signatures as they are.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Debug)]
+enum UsernameError {
+    Io(io::Error),
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for UsernameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        todo!("write the message for {:?} to f", self)
+    }
+}
+
+impl Error for UsernameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        todo!("the io::Error inside {:?}, if any", self)
+    }
+}
+
+impl From<io::Error> for UsernameError {
+    fn from(error: io::Error) -> UsernameError {
+        todo!("wrap {:?}", error)
+    }
+}
+
+fn read_username_from_file(path: &str) -> Result<String, UsernameError> {
+    todo!("read the username in {} with fs::read_to_string", path)
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let name = read_username_from_file("username.txt")?;
+    println!("hello, {}!", name);
+    Ok(())
+}
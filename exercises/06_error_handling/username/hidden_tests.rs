@@ -0,0 +1,94 @@
+use super::*;
+
+#[test]
+fn valid_name() {
+    assert_eq!(read_username_from_file("valid.txt").unwrap(), "ferris");
+}
+
+#[test]
+fn whitespace_is_trimmed() {
+    assert_eq!(read_username_from_file("padded.txt").unwrap(), "rust_2021");
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    match read_username_from_file("missing.txt") {
+        Err(UsernameError::Io(error)) => assert_eq!(error.kind(), io::ErrorKind::NotFound),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn empty_file() {
+    assert!(matches!(
+        read_username_from_file("empty.txt"),
+        Err(UsernameError::Empty)
+    ));
+}
+
+#[test]
+fn only_whitespace_is_empty() {
+    assert!(matches!(
+        read_username_from_file("blank.txt"),
+        Err(UsernameError::Empty)
+    ));
+}
+
+#[test]
+fn spaces_are_invalid() {
+    match read_username_from_file("spaces.txt") {
+        Err(UsernameError::Invalid(name)) => assert_eq!(name, "ferris the crab"),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn too_long_is_invalid() {
+    assert!(matches!(
+        read_username_from_file("long.txt"),
+        Err(UsernameError::Invalid(_))
+    ));
+}
+
+#[test]
+fn display_messages() {
+    let io_error = io::Error::new(io::ErrorKind::NotFound, "gone");
+    assert_eq!(
+        UsernameError::Io(io_error).to_string(),
+        "couldn't read the username file"
+    );
+    assert_eq!(
+        UsernameError::Empty.to_string(),
+        "the username file is empty"
+    );
+    assert_eq!(
+        UsernameError::Invalid(String::from("a b")).to_string(),
+        "\"a b\" is not a valid username"
+    );
+}
+
+#[test]
+fn source_is_the_io_error() {
+    let error = read_username_from_file("missing.txt").unwrap_err();
+    let source = error.source().expect("an I/O error has a source");
+    assert!(source.downcast_ref::<io::Error>().is_some());
+    assert!(UsernameError::Empty.source().is_none());
+    assert!(UsernameError::Invalid(String::from("x y"))
+        .source()
+        .is_none());
+}
+
+#[test]
+fn from_io_error() {
+    let error = UsernameError::from(io::Error::new(io::ErrorKind::PermissionDenied, "no"));
+    match error {
+        UsernameError::Io(error) => assert_eq!(error.kind(), io::ErrorKind::PermissionDenied),
+        other => panic!("{:?}", other),
+    }
+}
+
+#[test]
+fn converts_into_box_dyn_error() {
+    let boxed: Box<dyn Error> = read_username_from_file("empty.txt").unwrap_err().into();
+    assert_eq!(boxed.to_string(), "the username file is empty");
+}
@@ -0,0 +1,56 @@
+// Username errors
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+
+#[derive(Debug)]
+enum UsernameError {
+    Io(io::Error),
+    Empty,
+    Invalid(String),
+}
+
+impl fmt::Display for UsernameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsernameError::Io(_) => write!(f, "couldn't read the username file"),
+            UsernameError::Empty => write!(f, "the username file is empty"),
+            UsernameError::Invalid(name) => write!(f, "{:?} is not a valid username", name),
+        }
+    }
+}
+
+impl Error for UsernameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UsernameError::Io(error) => Some(error),
+            UsernameError::Empty | UsernameError::Invalid(_) => None,
+        }
+    }
+}
+
+impl From<io::Error> for UsernameError {
+    fn from(error: io::Error) -> UsernameError {
+        UsernameError::Io(error)
+    }
+}
+
+fn read_username_from_file(path: &str) -> Result<String, UsernameError> {
+    let text = fs::read_to_string(path)?;
+    let name = text.trim();
+    if name.is_empty() {
+        return Err(UsernameError::Empty);
+    }
+    if name.len() > 16 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(UsernameError::Invalid(name.to_string()));
+    }
+    Ok(name.to_string())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let name = read_username_from_file("username.txt")?;
+    println!("hello, {}!", name);
+    Ok(())
+}
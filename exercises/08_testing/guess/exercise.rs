@@ -0,0 +1,34 @@
+// Guess
+//
+// `Guess::new` should only accept values from 1 to 100. For anything else it must panic
+// with a message containing "between 1 and 100".
+//
+// Then write a test that checks this, using `#[should_panic(expected = "...")]`.
+// `rust_tutor exercise check guess` runs hidden tests against your code,
+// so keep the signature of `Guess::new` as it is.
+
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32) -> Guess {
+        Guess { value }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_fifty() {
+        assert_eq!(Guess::new(50).value(), 50);
+    }
+
+    // Your `#[should_panic]` test goes here.
+}
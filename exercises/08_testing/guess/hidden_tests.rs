@@ -0,0 +1,29 @@
+use super::*;
+
+#[test]
+fn accepts_1() {
+    assert_eq!(Guess::new(1).value(), 1);
+}
+
+#[test]
+fn accepts_100() {
+    assert_eq!(Guess::new(100).value(), 100);
+}
+
+#[test]
+#[should_panic(expected = "between 1 and 100")]
+fn rejects_0() {
+    Guess::new(0);
+}
+
+#[test]
+#[should_panic(expected = "between 1 and 100")]
+fn rejects_101() {
+    Guess::new(101);
+}
+
+#[test]
+#[should_panic(expected = "between 1 and 100")]
+fn rejects_negative() {
+    Guess::new(-5);
+}
@@ -0,0 +1,34 @@
+// Guess
+
+pub struct Guess {
+    value: i32,
+}
+
+impl Guess {
+    pub fn new(value: i32) -> Guess {
+        if !(1..=100).contains(&value) {
+            panic!("Guess value must be between 1 and 100, got {}.", value);
+        }
+        Guess { value }
+    }
+
+    pub fn value(&self) -> i32 {
+        self.value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn accepts_fifty() {
+        assert_eq!(Guess::new(50).value(), 50);
+    }
+
+    #[test]
+    #[should_panic(expected = "between 1 and 100")]
+    fn greater_than_100() {
+        Guess::new(200);
+    }
+}
@@ -0,0 +1,71 @@
+// Bounded queue
+//
+// A `BoundedQueue` is shared between threads and holds at most `capacity` items, handing
+// them out first in, first out. Write its methods:
+//
+// - `push` adds an item, waiting while the queue is full.
+// - `pop` takes the oldest item, waiting while the queue is empty.
+// - `try_push` adds an item if there is room, and gives it back in an `Err` if there isn't.
+// - `len` is the number of items in the queue.
+//
+// Waiting means sleeping on a `Condvar`, not checking in a loop. `E_more_concurrency.rs`
+// shows how `wait` and `notify_one` work together.
+//
+// `rust_tutor exercise check bounded_queue` runs hidden tests against your code, with
+// producer and consumer threads, so keep the signatures as they are. A queue that never
+// wakes a waiting thread makes the tests hang until the time limit stops them.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+struct BoundedQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+    fn new(capacity: usize) -> BoundedQueue<T> {
+        BoundedQueue {
+            items: Mutex::new(VecDeque::new()),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    fn push(&self, item: T) {
+        todo!("wait for room for one more of {} items", self.capacity)
+    }
+
+    fn pop(&self) -> T {
+        todo!("wait for an item, then take the oldest")
+    }
+
+    fn try_push(&self, item: T) -> Result<(), T> {
+        todo!("push only if there is room")
+    }
+
+    fn len(&self) -> usize {
+        todo!("the number of items in the queue")
+    }
+}
+
+fn main() {
+    let queue = Arc::new(BoundedQueue::new(2));
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for job in 1..=5 {
+                queue.push(job);
+                println!("queued job {}", job);
+            }
+        })
+    };
+    for _ in 1..=5 {
+        println!("working on job {}", queue.pop());
+    }
+    producer.join().unwrap();
+}
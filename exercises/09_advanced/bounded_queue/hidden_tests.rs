@@ -0,0 +1,99 @@
+use super::*;
+
+// These tests don't rely on timing: a thread that waits forever makes them hang, but how
+// long any thread takes doesn't change the outcome.
+
+#[test]
+fn first_in_first_out() {
+    let queue = BoundedQueue::new(3);
+    queue.push("a");
+    queue.push("b");
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pop(), "a");
+    queue.push("c");
+    assert_eq!(queue.pop(), "b");
+    assert_eq!(queue.pop(), "c");
+    assert_eq!(queue.len(), 0);
+}
+
+#[test]
+fn try_push_gives_the_item_back_when_full() {
+    let queue = BoundedQueue::new(2);
+    assert_eq!(queue.try_push(1), Ok(()));
+    assert_eq!(queue.try_push(2), Ok(()));
+    assert_eq!(queue.try_push(3), Err(3));
+    assert_eq!(queue.len(), 2);
+    assert_eq!(queue.pop(), 1);
+    assert_eq!(queue.try_push(3), Ok(()));
+}
+
+#[test]
+fn pop_waits_for_a_push() {
+    let queue = Arc::new(BoundedQueue::new(1));
+    let consumer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || queue.pop())
+    };
+    queue.push(String::from("late"));
+    assert_eq!(consumer.join().unwrap(), "late");
+}
+
+#[test]
+fn push_waits_for_room() {
+    let queue = Arc::new(BoundedQueue::new(1));
+    queue.push(1);
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || queue.push(2))
+    };
+    assert_eq!(queue.pop(), 1);
+    assert_eq!(queue.pop(), 2);
+    producer.join().unwrap();
+}
+
+#[test]
+fn never_holds_more_than_its_capacity() {
+    let queue = Arc::new(BoundedQueue::new(3));
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for n in 0..200 {
+                queue.push(n);
+                assert!(queue.len() <= 3);
+            }
+        })
+    };
+    let received: Vec<i32> = (0..200).map(|_| queue.pop()).collect();
+    producer.join().unwrap();
+    assert_eq!(received, (0..200).collect::<Vec<i32>>());
+}
+
+#[test]
+fn several_producers_and_consumers() {
+    let queue = Arc::new(BoundedQueue::new(2));
+    let producers: Vec<_> = (0..4)
+        .map(|p| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || {
+                for n in 0..100 {
+                    queue.push(p * 100 + n);
+                }
+            })
+        })
+        .collect();
+    let consumers: Vec<_> = (0..2)
+        .map(|_| {
+            let queue = Arc::clone(&queue);
+            thread::spawn(move || (0..200).map(|_| queue.pop()).collect::<Vec<i32>>())
+        })
+        .collect();
+    for producer in producers {
+        producer.join().unwrap();
+    }
+    let mut received: Vec<i32> = consumers
+        .into_iter()
+        .flat_map(|c| c.join().unwrap())
+        .collect();
+    received.sort();
+    assert_eq!(received, (0..400).collect::<Vec<i32>>());
+}
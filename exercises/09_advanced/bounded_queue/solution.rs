@@ -0,0 +1,74 @@
+// Bounded queue
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+
+struct BoundedQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+    fn new(capacity: usize) -> BoundedQueue<T> {
+        BoundedQueue {
+            items: Mutex::new(VecDeque::new()),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    fn push(&self, item: T) {
+        let mut items = self.items.lock().unwrap();
+        while items.len() >= self.capacity {
+            items = self.not_full.wait(items).unwrap();
+        }
+        items.push_back(item);
+        self.not_empty.notify_one();
+    }
+
+    fn pop(&self) -> T {
+        let mut items = self.items.lock().unwrap();
+        loop {
+            if let Some(item) = items.pop_front() {
+                self.not_full.notify_one();
+                return item;
+            }
+            items = self.not_empty.wait(items).unwrap();
+        }
+    }
+
+    fn try_push(&self, item: T) -> Result<(), T> {
+        let mut items = self.items.lock().unwrap();
+        if items.len() >= self.capacity {
+            return Err(item);
+        }
+        items.push_back(item);
+        self.not_empty.notify_one();
+        Ok(())
+    }
+
+    fn len(&self) -> usize {
+        self.items.lock().unwrap().len()
+    }
+}
+
+fn main() {
+    let queue = Arc::new(BoundedQueue::new(2));
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            for job in 1..=5 {
+                queue.push(job);
+                println!("queued job {}", job);
+            }
+        })
+    };
+    for _ in 1..=5 {
+        println!("working on job {}", queue.pop());
+    }
+    producer.join().unwrap();
+}
@@ -0,0 +1,71 @@
+// Page statistics
+//
+// A web server records every page it serves in a `PageStats`, from many threads at once, and
+// only through a `&PageStats`: there's no `Mutex` around the whole thing. Write:
+//
+// - `record`, which counts one more visit to `page`
+// - `total`, the number of visits to all pages
+// - `hits`, the number of visits to one page (0 for a page never visited)
+// - `top`, the `n` most visited pages with their visits, most visited first, and pages with
+//   the same number of visits in alphabetical order
+//
+// `total` is an `AtomicUsize`, updated with `fetch_add`. `pages` maps each page to its own
+// counter: most visits are to pages already in the map, which only need the read lock and a
+// `fetch_add`. Take the write lock only to add a page that isn't there yet.
+//
+// `rust_tutor exercise check page_stats` runs hidden tests against your code, recording from
+// several threads, so keep the signatures as they are.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::thread;
+
+struct PageStats {
+    total: AtomicUsize,
+    pages: RwLock<HashMap<String, AtomicUsize>>,
+}
+
+impl PageStats {
+    fn new() -> PageStats {
+        PageStats {
+            total: AtomicUsize::new(0),
+            pages: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn record(&self, page: &str) {
+        todo!("count a visit to {}", page)
+    }
+
+    fn total(&self) -> usize {
+        todo!("load the total with {:?} ordering", Ordering::Relaxed)
+    }
+
+    fn hits(&self, page: &str) -> usize {
+        todo!("the visits to {}", page)
+    }
+
+    fn top(&self, n: usize) -> Vec<(String, usize)> {
+        todo!("the {} most visited pages", n)
+    }
+}
+
+fn main() {
+    let stats = PageStats::new();
+    let pages = ["/", "/lessons", "/", "/exercises", "/lessons", "/"];
+    thread::scope(|s| {
+        for worker in 0..3 {
+            let stats = &stats;
+            s.spawn(move || {
+                for page in pages.iter().skip(worker).step_by(3) {
+                    stats.record(page);
+                }
+            });
+        }
+    });
+    println!("{} visits", stats.total());
+    for (page, hits) in stats.top(3) {
+        println!("{:<12} {}", page, hits);
+    }
+}
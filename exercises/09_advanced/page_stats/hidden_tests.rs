@@ -0,0 +1,85 @@
+use super::*;
+
+#[test]
+fn counts_on_one_thread() {
+    let stats = PageStats::new();
+    for page in ["/", "/about", "/"] {
+        stats.record(page);
+    }
+    assert_eq!(stats.total(), 3);
+    assert_eq!(stats.hits("/"), 2);
+    assert_eq!(stats.hits("/about"), 1);
+    assert_eq!(stats.hits("/missing"), 0);
+}
+
+#[test]
+fn nothing_recorded() {
+    let stats = PageStats::new();
+    assert_eq!(stats.total(), 0);
+    assert!(stats.top(5).is_empty());
+}
+
+#[test]
+fn top_pages_in_order() {
+    let stats = PageStats::new();
+    for page in ["/b", "/a", "/c", "/c", "/b", "/c", "/d"] {
+        stats.record(page);
+    }
+    let pair = |page: &str, hits| (page.to_string(), hits);
+    assert_eq!(stats.top(3), [pair("/c", 3), pair("/b", 2), pair("/a", 1)]);
+    assert_eq!(stats.top(10).len(), 4);
+    assert!(stats.top(0).is_empty());
+}
+
+// However the threads interleave, no visit may be lost.
+#[test]
+fn no_visit_is_lost_across_threads() {
+    let stats = PageStats::new();
+    thread::scope(|s| {
+        for worker in 0..8 {
+            let stats = &stats;
+            s.spawn(move || {
+                for i in 0..1000 {
+                    stats.record(&format!("/page/{}", (i + worker) % 10));
+                }
+            });
+        }
+    });
+    assert_eq!(stats.total(), 8000);
+    for page in 0..10 {
+        assert_eq!(stats.hits(&format!("/page/{}", page)), 800);
+    }
+}
+
+#[test]
+fn new_pages_from_many_threads_at_once() {
+    let stats = PageStats::new();
+    thread::scope(|s| {
+        for _ in 0..8 {
+            let stats = &stats;
+            s.spawn(move || {
+                for i in 0..200 {
+                    stats.record(&format!("/new/{}", i));
+                }
+            });
+        }
+    });
+    assert_eq!(stats.total(), 1600);
+    assert_eq!(stats.top(1000).len(), 200);
+    assert!(stats.top(1000).iter().all(|(_, hits)| *hits == 8));
+}
+
+#[test]
+fn stats_can_be_shared_with_arc() {
+    let stats = std::sync::Arc::new(PageStats::new());
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let stats = std::sync::Arc::clone(&stats);
+            thread::spawn(move || stats.record("/"))
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    assert_eq!(stats.hits("/"), 4);
+}
@@ -0,0 +1,79 @@
+// Page statistics
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::RwLock;
+use std::thread;
+
+struct PageStats {
+    total: AtomicUsize,
+    pages: RwLock<HashMap<String, AtomicUsize>>,
+}
+
+impl PageStats {
+    fn new() -> PageStats {
+        PageStats {
+            total: AtomicUsize::new(0),
+            pages: RwLock::new(HashMap::new()),
+        }
+    }
+
+    fn record(&self, page: &str) {
+        self.total.fetch_add(1, Ordering::Relaxed);
+        if let Some(hits) = self.pages.read().unwrap().get(page) {
+            hits.fetch_add(1, Ordering::Relaxed);
+            return;
+        }
+        // Another thread may have added the page between the two locks, so this goes through
+        // `entry` rather than inserting a new counter.
+        self.pages
+            .write()
+            .unwrap()
+            .entry(page.to_string())
+            .or_insert_with(|| AtomicUsize::new(0))
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn total(&self) -> usize {
+        self.total.load(Ordering::Relaxed)
+    }
+
+    fn hits(&self, page: &str) -> usize {
+        match self.pages.read().unwrap().get(page) {
+            Some(hits) => hits.load(Ordering::Relaxed),
+            None => 0,
+        }
+    }
+
+    fn top(&self, n: usize) -> Vec<(String, usize)> {
+        let mut pages: Vec<(String, usize)> = self
+            .pages
+            .read()
+            .unwrap()
+            .iter()
+            .map(|(page, hits)| (page.clone(), hits.load(Ordering::Relaxed)))
+            .collect();
+        pages.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+        pages.truncate(n);
+        pages
+    }
+}
+
+fn main() {
+    let stats = PageStats::new();
+    let pages = ["/", "/lessons", "/", "/exercises", "/lessons", "/"];
+    thread::scope(|s| {
+        for worker in 0..3 {
+            let stats = &stats;
+            s.spawn(move || {
+                for page in pages.iter().skip(worker).step_by(3) {
+                    stats.record(page);
+                }
+            });
+        }
+    });
+    println!("{} visits", stats.total());
+    for (page, hits) in stats.top(3) {
+        println!("{:<12} {}", page, hits);
+    }
+}
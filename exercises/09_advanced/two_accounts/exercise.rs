@@ -0,0 +1,71 @@
+// Two accounts
+//
+// The counter in `A_concurrency.rs` was a single `Arc<Mutex<i32>>`. A bank has one mutex per
+// account, so transfers between different accounts can happen at the same time. Write
+// `transfer`, which moves `amount` from account `from` to account `to`:
+//
+// - it returns an `Err`, and changes nothing, if `from` and `to` are the same account or
+//   `from` has less than `amount`
+// - otherwise it holds both accounts' locks while it changes the balances, so no other thread
+//   ever sees the money in neither account, or in both
+//
+// The accounts are `TrackedMutex`es from the tutor's `common/tracked_mutex.rs`, which
+// `exercise start` puts next to this file. They work like `std::sync::Mutex`, but watch the
+// order each thread takes them in. If one transfer locks account 0 and then account 1 while
+// another locks 1 and then 0, two threads doing both at once can each get one lock and wait
+// forever for the other. That's reported even when, as in `main`, the threads never meet.
+//
+// `rust_tutor exercise check two_accounts` runs hidden tests against your code, so keep the
+// signatures as they are.
+
+#[allow(dead_code)]
+mod tracked_mutex;
+
+use std::sync::Arc;
+use std::thread;
+use tracked_mutex::TrackedMutex;
+
+struct Bank {
+    accounts: Vec<TrackedMutex<i64>>,
+}
+
+impl Bank {
+    fn new(balances: &[i64]) -> Bank {
+        let accounts = balances
+            .iter()
+            .enumerate()
+            .map(|(n, &balance)| TrackedMutex::named(&format!("account {}", n), balance))
+            .collect();
+        Bank { accounts }
+    }
+
+    fn balance(&self, account: usize) -> i64 {
+        *self.accounts[account].lock().unwrap()
+    }
+}
+
+fn transfer(bank: &Bank, from: usize, to: usize, amount: i64) -> Result<(), String> {
+    todo!(
+        "move {} from account {} to account {} of {}",
+        amount,
+        from,
+        to,
+        bank.accounts.len()
+    )
+}
+
+fn main() {
+    let bank = Arc::new(Bank::new(&[100, 50]));
+    // One transfer each way, on two threads, but one after the other.
+    for (from, to, amount) in [(0, 1, 30), (1, 0, 20)] {
+        let bank = Arc::clone(&bank);
+        let result = thread::spawn(move || transfer(&bank, from, to, amount))
+            .join()
+            .unwrap();
+        println!("{} from {} to {}: {:?}", amount, from, to, result);
+    }
+    println!("balances: {} and {}", bank.balance(0), bank.balance(1));
+    if tracked_mutex::potential_deadlocks().is_empty() {
+        println!("no potential deadlocks");
+    }
+}
@@ -0,0 +1,61 @@
+use super::*;
+
+// `locks_are_always_taken_in_the_same_order` makes transfers both ways between every pair of
+// accounts, one thread after another, so two that lock in opposite orders are caught without
+// ever really deadlocking.
+
+#[test]
+fn moves_the_money() {
+    let bank = Bank::new(&[100, 50]);
+    assert_eq!(transfer(&bank, 0, 1, 30), Ok(()));
+    assert_eq!((bank.balance(0), bank.balance(1)), (70, 80));
+    assert_eq!(transfer(&bank, 1, 0, 80), Ok(()));
+    assert_eq!((bank.balance(0), bank.balance(1)), (150, 0));
+}
+
+#[test]
+fn not_enough_money_changes_nothing() {
+    let bank = Bank::new(&[10, 0]);
+    assert!(transfer(&bank, 0, 1, 11).is_err());
+    assert_eq!((bank.balance(0), bank.balance(1)), (10, 0));
+}
+
+#[test]
+fn paying_yourself_is_an_error() {
+    let bank = Bank::new(&[10]);
+    assert!(transfer(&bank, 0, 0, 5).is_err());
+    assert_eq!(bank.balance(0), 10);
+}
+
+#[test]
+fn locks_are_always_taken_in_the_same_order() {
+    let bank = Arc::new(Bank::new(&[100, 100, 100]));
+    // One thread after another, so even locks taken in opposite orders can't deadlock here.
+    for (from, to) in [(0, 1), (1, 0), (1, 2), (2, 0), (2, 1), (0, 2)] {
+        let bank = Arc::clone(&bank);
+        thread::spawn(move || transfer(&bank, from, to, 10).unwrap())
+            .join()
+            .unwrap();
+    }
+    assert_eq!(tracked_mutex::potential_deadlocks(), Vec::<String>::new());
+}
+
+#[test]
+fn concurrent_transfers_keep_the_total() {
+    let bank = Bank::new(&[1000, 0, 1000]);
+    // Every thread sends money the same way, into account 1, so none of them can deadlock.
+    thread::scope(|s| {
+        for from in [0, 2, 0, 2] {
+            let bank = &bank;
+            s.spawn(move || {
+                for _ in 0..100 {
+                    transfer(bank, from, 1, 1).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(
+        (bank.balance(0), bank.balance(1), bank.balance(2)),
+        (800, 400, 800)
+    );
+}
@@ -0,0 +1,35 @@
+// Word count on several threads
+//
+// `word_count` counts how often each word appears in a list of texts, splitting the work
+// between `threads` threads. Words are separated by whitespace, compared in lowercase, and
+// lose any ASCII punctuation at either end: "Hello," and "hello" are the same word.
+//
+// Split `texts` into at most `threads` chunks and count each chunk on its own thread, then
+// add the counts together. The texts are borrowed, so `thread::spawn` can't take them:
+// use `thread::scope`, as in `E_more_concurrency.rs`. `threads` is always at least 1.
+//
+// `rust_tutor exercise check word_count` runs hidden tests against your code,
+// so keep the signatures as they are.
+
+use std::collections::BTreeMap;
+use std::thread;
+
+// The count for one chunk of texts, on one thread.
+fn count_chunk(texts: &[&str]) -> BTreeMap<String, usize> {
+    todo!("count the words in {:?}", texts)
+}
+
+fn word_count(texts: &[&str], threads: usize) -> BTreeMap<String, usize> {
+    todo!("count {} texts on {} threads", texts.len(), threads)
+}
+
+fn main() {
+    let texts = [
+        "The quick brown fox",
+        "jumps over the lazy dog.",
+        "The dog sleeps; the fox runs!",
+    ];
+    for (word, count) in word_count(&texts, 2) {
+        println!("{}: {}", word, count);
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+fn counts(pairs: &[(&str, usize)]) -> BTreeMap<String, usize> {
+    pairs
+        .iter()
+        .map(|&(word, count)| (word.to_string(), count))
+        .collect()
+}
+
+const TEXTS: [&str; 3] = [
+    "The quick brown fox",
+    "jumps over the lazy dog.",
+    "The dog sleeps; the fox runs!",
+];
+
+#[test]
+fn one_chunk() {
+    assert_eq!(
+        count_chunk(&["Hello, world!", "hello again"]),
+        counts(&[("again", 1), ("hello", 2), ("world", 1)])
+    );
+}
+
+#[test]
+fn punctuation_and_case() {
+    assert_eq!(
+        count_chunk(&["Don't STOP -- don't!"]),
+        counts(&[("don't", 2), ("stop", 1)])
+    );
+}
+
+#[test]
+fn the_same_on_any_number_of_threads() {
+    let expected = counts(&[
+        ("brown", 1),
+        ("dog", 2),
+        ("fox", 2),
+        ("jumps", 1),
+        ("lazy", 1),
+        ("over", 1),
+        ("quick", 1),
+        ("runs", 1),
+        ("sleeps", 1),
+        ("the", 4),
+    ]);
+    for threads in 1..=5 {
+        assert_eq!(word_count(&TEXTS, threads), expected, "{} threads", threads);
+    }
+}
+
+#[test]
+fn many_texts() {
+    let texts: Vec<String> = (0..100).map(|n| format!("word{} common", n % 7)).collect();
+    let texts: Vec<&str> = texts.iter().map(String::as_str).collect();
+    let counted = word_count(&texts, 4);
+    assert_eq!(counted["common"], 100);
+    assert_eq!(counted["word0"], 15);
+    assert_eq!(counted["word6"], 14);
+    assert_eq!(counted.values().sum::<usize>(), 200);
+}
+
+#[test]
+fn nothing_to_count() {
+    assert!(word_count(&[], 3).is_empty());
+    assert!(word_count(&["", "  ", "..."], 2).is_empty());
+}
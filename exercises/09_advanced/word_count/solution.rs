@@ -0,0 +1,47 @@
+// Word count on several threads
+
+use std::collections::BTreeMap;
+use std::thread;
+
+// The count for one chunk of texts, on one thread.
+fn count_chunk(texts: &[&str]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for text in texts {
+        for word in text.split_whitespace() {
+            let word = word.trim_matches(|c: char| c.is_ascii_punctuation());
+            if !word.is_empty() {
+                *counts.entry(word.to_lowercase()).or_insert(0) += 1;
+            }
+        }
+    }
+    counts
+}
+
+fn word_count(texts: &[&str], threads: usize) -> BTreeMap<String, usize> {
+    // Rounded up, so there are never more than `threads` chunks.
+    let chunk_size = texts.len().div_ceil(threads).max(1);
+    let mut total = BTreeMap::new();
+    thread::scope(|s| {
+        let handles: Vec<_> = texts
+            .chunks(chunk_size)
+            .map(|chunk| s.spawn(move || count_chunk(chunk)))
+            .collect();
+        for handle in handles {
+            for (word, count) in handle.join().unwrap() {
+                *total.entry(word).or_insert(0) += count;
+            }
+        }
+    });
+    total
+}
+
+fn main() {
+    let texts = [
+        "The quick brown fox",
+        "jumps over the lazy dog.",
+        "The dog sleeps; the fox runs!",
+    ];
+    for (word, count) in word_count(&texts, 2) {
+        println!("{}: {}", word, count);
+    }
+}
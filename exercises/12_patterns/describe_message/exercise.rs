@@ -0,0 +1,48 @@
+// Describe a message
+//
+// Write `describe`, which turns a `Message` into a line of text:
+//
+//     Message::Quit                                  "quit"
+//     Message::Move { x: 0, y: 0 }                   "stay"
+//     Message::Move { x, y }                         "move to (x, y)"
+//     Message::Write(text) with an empty text        "write nothing"
+//     Message::Write(text)                           "write <text>"
+//     Message::ChangeColor(Color::Rgb(0, 0, 0))      "black"
+//     Message::ChangeColor(Color::Rgb(r, g, b))      "rgb(r, g, b)"
+//     Message::ChangeColor(Color::Hsv(h, s, v))      "hsv(h, s, v)"
+//
+// Name every variant of `Message` in your match rather than catching the rest with `_`: when
+// grading, a new variant is added to `Message`, and your match must notice it's missing.
+//
+// `rust_tutor exercise check describe_message` runs hidden tests against your code,
+// so keep the `Message` and `Color` types and the signature of `describe` as they are.
+
+#[derive(Debug, Clone, PartialEq)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Hsv(u16, u8, u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+fn describe(message: &Message) -> String {
+    todo!("describe {:?}", message)
+}
+
+fn main() {
+    let messages = [
+        Message::Move { x: 10, y: 20 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Rgb(0, 160, 255)),
+        Message::Quit,
+    ];
+    for message in &messages {
+        println!("{}", describe(message));
+    }
+}
@@ -0,0 +1,48 @@
+use super::*;
+
+#[test]
+fn quit() {
+    assert_eq!(describe(&Message::Quit), "quit");
+}
+
+#[test]
+fn move_nowhere() {
+    assert_eq!(describe(&Message::Move { x: 0, y: 0 }), "stay");
+}
+
+#[test]
+fn move_somewhere() {
+    assert_eq!(describe(&Message::Move { x: 0, y: -3 }), "move to (0, -3)");
+    assert_eq!(
+        describe(&Message::Move { x: 10, y: 20 }),
+        "move to (10, 20)"
+    );
+}
+
+#[test]
+fn write_text() {
+    assert_eq!(describe(&Message::Write(String::from("hi"))), "write hi");
+}
+
+#[test]
+fn write_nothing() {
+    assert_eq!(describe(&Message::Write(String::new())), "write nothing");
+}
+
+#[test]
+fn rgb_color() {
+    let message = Message::ChangeColor(Color::Rgb(0, 160, 255));
+    assert_eq!(describe(&message), "rgb(0, 160, 255)");
+}
+
+#[test]
+fn black() {
+    let message = Message::ChangeColor(Color::Rgb(0, 0, 0));
+    assert_eq!(describe(&message), "black");
+}
+
+#[test]
+fn hsv_color() {
+    let message = Message::ChangeColor(Color::Hsv(0, 0, 0));
+    assert_eq!(describe(&message), "hsv(0, 0, 0)");
+}
@@ -0,0 +1,40 @@
+// Describe a message
+
+#[derive(Debug, Clone, PartialEq)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Hsv(u16, u8, u8),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+fn describe(message: &Message) -> String {
+    match message {
+        Message::Quit => String::from("quit"),
+        Message::Move { x: 0, y: 0 } => String::from("stay"),
+        Message::Move { x, y } => format!("move to ({}, {})", x, y),
+        Message::Write(text) if text.is_empty() => String::from("write nothing"),
+        Message::Write(text) => format!("write {}", text),
+        Message::ChangeColor(Color::Rgb(0, 0, 0)) => String::from("black"),
+        Message::ChangeColor(Color::Rgb(r, g, b)) => format!("rgb({}, {}, {})", r, g, b),
+        Message::ChangeColor(Color::Hsv(h, s, v)) => format!("hsv({}, {}, {})", h, s, v),
+    }
+}
+
+fn main() {
+    let messages = [
+        Message::Move { x: 10, y: 20 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Rgb(0, 160, 255)),
+        Message::Quit,
+    ];
+    for message in &messages {
+        println!("{}", describe(message));
+    }
+}
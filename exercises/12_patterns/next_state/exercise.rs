@@ -0,0 +1,57 @@
+// The next state
+//
+// A timer is `Idle`, `Running`, `Paused` or `Done`, and moves between these on events. Write
+// `next`, which returns the state after `event`:
+//
+//     Idle       + Start     Running with 0 ticks
+//     Idle       + Stop      Done with 0 ticks
+//     Running    + Tick      Running with one tick more, or Done once it reaches 10 ticks
+//     Running    + Pause     Paused, keeping its ticks
+//     Paused     + Resume    Running, keeping its ticks
+//     Running or Paused + Stop    Done, keeping its ticks
+//
+// Any other event leaves the state as it is.
+//
+// Match on `(state, event)`. You can use `_` for the event, but name every state: when
+// grading, a new variant is added to `State`, and your match must notice it's missing.
+//
+// `rust_tutor exercise check next_state` runs hidden tests against your code,
+// so keep the `State` and `Event` types and the signature of `next` as they are.
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    Running { ticks: u32 },
+    Paused { ticks: u32 },
+    Done { ticks: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Event {
+    Start,
+    Tick,
+    Pause,
+    Resume,
+    Stop,
+}
+
+fn next(state: State, event: Event) -> State {
+    todo!("what comes after {:?} on {:?}", state, event)
+}
+
+fn main() {
+    let events = [
+        Event::Start,
+        Event::Tick,
+        Event::Pause,
+        Event::Tick,
+        Event::Resume,
+        Event::Tick,
+        Event::Stop,
+    ];
+    let mut state = State::Idle;
+    for event in events {
+        state = next(state, event);
+        println!("{:?} -> {:?}", event, state);
+    }
+}
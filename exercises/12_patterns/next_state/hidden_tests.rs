@@ -0,0 +1,61 @@
+use super::*;
+
+#[test]
+fn start() {
+    assert_eq!(next(State::Idle, Event::Start), State::Running { ticks: 0 });
+}
+
+#[test]
+fn stop_before_starting() {
+    assert_eq!(next(State::Idle, Event::Stop), State::Done { ticks: 0 });
+}
+
+#[test]
+fn tick() {
+    assert_eq!(
+        next(State::Running { ticks: 3 }, Event::Tick),
+        State::Running { ticks: 4 }
+    );
+}
+
+#[test]
+fn tenth_tick_is_done() {
+    assert_eq!(
+        next(State::Running { ticks: 9 }, Event::Tick),
+        State::Done { ticks: 10 }
+    );
+}
+
+#[test]
+fn pause_and_resume() {
+    let paused = next(State::Running { ticks: 5 }, Event::Pause);
+    assert_eq!(paused, State::Paused { ticks: 5 });
+    assert_eq!(next(paused, Event::Tick), paused);
+    assert_eq!(next(paused, Event::Resume), State::Running { ticks: 5 });
+}
+
+#[test]
+fn stop_keeps_ticks() {
+    assert_eq!(
+        next(State::Running { ticks: 2 }, Event::Stop),
+        State::Done { ticks: 2 }
+    );
+    assert_eq!(
+        next(State::Paused { ticks: 7 }, Event::Stop),
+        State::Done { ticks: 7 }
+    );
+}
+
+#[test]
+fn other_events_change_nothing() {
+    assert_eq!(next(State::Idle, Event::Tick), State::Idle);
+    assert_eq!(next(State::Idle, Event::Resume), State::Idle);
+    assert_eq!(
+        next(State::Running { ticks: 1 }, Event::Start),
+        State::Running { ticks: 1 }
+    );
+    assert_eq!(
+        next(State::Done { ticks: 4 }, Event::Start),
+        State::Done { ticks: 4 }
+    );
+}
@@ -0,0 +1,50 @@
+// The next state
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Idle,
+    Running { ticks: u32 },
+    Paused { ticks: u32 },
+    Done { ticks: u32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Event {
+    Start,
+    Tick,
+    Pause,
+    Resume,
+    Stop,
+}
+
+fn next(state: State, event: Event) -> State {
+    match (state, event) {
+        (State::Idle, Event::Start) => State::Running { ticks: 0 },
+        (State::Idle, Event::Stop) => State::Done { ticks: 0 },
+        (State::Running { ticks }, Event::Tick) if ticks + 1 >= 10 => State::Done { ticks: 10 },
+        (State::Running { ticks }, Event::Tick) => State::Running { ticks: ticks + 1 },
+        (State::Running { ticks }, Event::Pause) => State::Paused { ticks },
+        (State::Paused { ticks }, Event::Resume) => State::Running { ticks },
+        (State::Running { ticks } | State::Paused { ticks }, Event::Stop) => State::Done { ticks },
+        (State::Idle | State::Running { .. } | State::Paused { .. } | State::Done { .. }, _) => {
+            state
+        }
+    }
+}
+
+fn main() {
+    let events = [
+        Event::Start,
+        Event::Tick,
+        Event::Pause,
+        Event::Tick,
+        Event::Resume,
+        Event::Tick,
+        Event::Stop,
+    ];
+    let mut state = State::Idle;
+    for event in events {
+        state = next(state, event);
+        println!("{:?} -> {:?}", event, state);
+    }
+}
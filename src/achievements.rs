@@ -0,0 +1,316 @@
+// Achievements for curriculum milestones, and the `rust_tutor progress` overview.
+//
+// The achievements and their rules are data, in `data/achievements.txt`. Rules are evaluated
+// against the progress log; an achievement that is earned is recorded there too, as an
+// `achieved` event with the time of the event that earned it, so it stays earned.
+//
+// Days, for streaks and dates, are UTC days.
+
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::challenges;
+use crate::exercises;
+use crate::lessons;
+use crate::progress::{self, Event, Store};
+use crate::{Context, Report};
+
+const DAY: u64 = 24 * 60 * 60;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    All {
+        kind: String,
+        subjects: String,
+    },
+    Count {
+        n: usize,
+        kind: String,
+        subjects: String,
+    },
+    Clean {
+        subjects: String,
+    },
+    Streak {
+        days: usize,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Achievement {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub rule: Rule,
+}
+
+impl Achievement {
+    pub fn subject(&self) -> String {
+        format!("achievement/{}", self.id)
+    }
+}
+
+pub fn definitions_path() -> PathBuf {
+    lessons::root().join("data").join("achievements.txt")
+}
+
+pub fn definitions() -> crate::Result<Vec<Achievement>> {
+    let path = definitions_path();
+    let text = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    parse(&text).with_context(|| format!("in {}", path.display()))
+}
+
+// Reads blocks of `key: value` lines separated by blank lines. `#` starts a comment line.
+pub fn parse(text: &str) -> crate::Result<Vec<Achievement>> {
+    let mut achievements = Vec::new();
+    let lines: Vec<&str> = text.lines().filter(|line| !line.starts_with('#')).collect();
+    for block in lines.split(|line| line.trim().is_empty()) {
+        if block.is_empty() {
+            continue;
+        }
+        let value = |key: &str| -> crate::Result<String> {
+            block
+                .iter()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+                .map(|value| value.trim().to_string())
+                .ok_or_else(|| Report::msg(format!("an achievement has no `{}`: {:?}", key, block)))
+        };
+        achievements.push(Achievement {
+            id: value("id")?,
+            title: value("title")?,
+            description: value("description")?,
+            rule: parse_rule(&value("rule")?)?,
+        });
+    }
+    Ok(achievements)
+}
+
+fn parse_rule(text: &str) -> crate::Result<Rule> {
+    let words: Vec<&str> = text.split_whitespace().collect();
+    let rule = match words[..] {
+        ["all", kind, subjects] => Rule::All {
+            kind: kind.to_string(),
+            subjects: subjects.to_string(),
+        },
+        ["count", n, kind, subjects] => Rule::Count {
+            n: n.parse()?,
+            kind: kind.to_string(),
+            subjects: subjects.to_string(),
+        },
+        ["clean", subjects] => Rule::Clean {
+            subjects: subjects.to_string(),
+        },
+        ["streak", days] => Rule::Streak {
+            days: days.parse()?,
+        },
+        _ => return Err(Report::msg(format!("unknown achievement rule `{}`", text))),
+    };
+    Ok(rule)
+}
+
+// Whether `subject` matches `pattern`, where `*` matches any run of characters.
+pub fn matches(pattern: &str, subject: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == subject,
+        Some((prefix, rest)) => {
+            let Some(subject) = subject.strip_prefix(prefix) else {
+                return false;
+            };
+            (0..=subject.len())
+                .filter(|&i| subject.is_char_boundary(i))
+                .any(|i| matches(rest, &subject[i..]))
+        }
+    }
+}
+
+// Every lesson, challenge and exercise, named the way the progress log names them.
+pub fn curriculum() -> io::Result<Vec<String>> {
+    let mut subjects: Vec<String> = Vec::new();
+    subjects.extend(lessons::all()?.iter().map(|l| l.subject()));
+    subjects.extend(challenges::all()?.iter().map(|c| c.subject()));
+    subjects.extend(exercises::all()?.iter().map(|e| e.subject()));
+    Ok(subjects)
+}
+
+// The UTC days (counted from 1970) on which something was recorded, in order.
+fn active_days(events: &[Event]) -> Vec<u64> {
+    let mut days: Vec<u64> = events
+        .iter()
+        .filter(|e| e.kind != "achieved")
+        .map(|e| e.time / DAY)
+        .collect();
+    days.sort();
+    days.dedup();
+    days
+}
+
+// The longest run of consecutive active days, and the run that includes `today`, if any.
+pub fn streaks(events: &[Event], today: u64) -> (usize, usize) {
+    let days = active_days(events);
+    let (mut longest, mut run) = (0, 0);
+    for (i, day) in days.iter().enumerate() {
+        run = if i > 0 && days[i - 1] + 1 == *day {
+            run + 1
+        } else {
+            1
+        };
+        longest = longest.max(run);
+    }
+    // A streak is still alive if the last active day is today or yesterday.
+    let current = match days.last() {
+        Some(&last) if last + 1 >= today => run,
+        _ => 0,
+    };
+    (longest, current)
+}
+
+pub fn satisfied(rule: &Rule, events: &[Event], curriculum: &[String]) -> bool {
+    let has = |kind: &str, subject: &str| {
+        events
+            .iter()
+            .any(|e| e.kind == kind && e.subject == subject)
+    };
+    match rule {
+        Rule::All { kind, subjects } => {
+            let mut matching = curriculum
+                .iter()
+                .filter(|s| matches(subjects, s))
+                .peekable();
+            matching.peek().is_some() && matching.all(|s| has(kind, s))
+        }
+        Rule::Count { n, kind, subjects } => {
+            curriculum
+                .iter()
+                .filter(|s| matches(subjects, s) && has(kind, s))
+                .count()
+                >= *n
+        }
+        Rule::Clean { subjects } => curriculum.iter().filter(|s| matches(subjects, s)).any(|s| {
+            // Solved before any hint was taken.
+            let mine = events.iter().filter(|e| &e.subject == s);
+            for event in mine {
+                match event.kind.as_str() {
+                    "hint" => return false,
+                    "solved" => return true,
+                    _ => {}
+                }
+            }
+            false
+        }),
+        Rule::Streak { days } => streaks(events, 0).0 >= *days,
+    }
+}
+
+// When `rule` was first satisfied: the time of the event in `events` that completed it.
+pub fn earned_at(rule: &Rule, events: &[Event], curriculum: &[String]) -> Option<u64> {
+    if !satisfied(rule, events, curriculum) {
+        return None;
+    }
+    if satisfied(rule, &[], curriculum) {
+        return Some(events.first().map_or_else(progress::now, |e| e.time));
+    }
+    // No later event can undo a rule, so a binary search finds the shortest part of the log
+    // that satisfies it: the first `high` events do, the first `low` don't.
+    let (mut low, mut high) = (0, events.len());
+    while high - low > 1 {
+        let middle = (low + high) / 2;
+        if satisfied(rule, &events[..middle], curriculum) {
+            high = middle;
+        } else {
+            low = middle;
+        }
+    }
+    Some(events[high - 1].time)
+}
+
+// Records every achievement that has been earned but not recorded yet, and returns them.
+pub fn update(store: &Store) -> crate::Result<Vec<Achievement>> {
+    let events = store.events()?;
+    let curriculum = curriculum()?;
+    let mut earned = Vec::new();
+    for achievement in definitions()? {
+        let subject = achievement.subject();
+        let recorded = events
+            .iter()
+            .any(|e| e.kind == "achieved" && e.subject == subject);
+        if recorded {
+            continue;
+        }
+        if let Some(time) = earned_at(&achievement.rule, &events, &curriculum) {
+            store.record_at(time, "achieved", &subject)?;
+            earned.push(achievement);
+        }
+    }
+    Ok(earned)
+}
+
+// Prints the achievements just earned, after a command that may have made progress.
+pub fn announce(store: &Store) -> crate::Result<()> {
+    for achievement in update(store)? {
+        println!(
+            "\nAchievement unlocked: {} ({})",
+            achievement.title, achievement.description
+        );
+    }
+    Ok(())
+}
+
+// `YYYY-MM-DD` for a Unix time, in UTC.
+pub fn date(time: u64) -> String {
+    // Howard Hinnant's `civil_from_days`.
+    let z = (time / DAY) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+// `rust_tutor progress`
+pub fn progress_command(store: &Store) -> crate::Result<()> {
+    update(store)?;
+    let events = store.events()?;
+    let curriculum = curriculum()?;
+    let done = |prefix: &str, kind: &str| {
+        let all: Vec<&String> = curriculum
+            .iter()
+            .filter(|s| s.starts_with(prefix))
+            .collect();
+        let finished = all
+            .iter()
+            .filter(|s| events.iter().any(|e| e.kind == kind && &&e.subject == *s))
+            .count();
+        format!("{}/{}", finished, all.len())
+    };
+    println!("Lessons completed:  {}", done("lesson/", "completed"));
+    println!("Challenges solved:  {}", done("challenge/", "solved"));
+    println!("Exercises solved:   {}", done("exercise/", "solved"));
+
+    let (longest, current) = streaks(&events, progress::now() / DAY);
+    println!("Streak:             {} days (longest {})", current, longest);
+
+    println!("\nAchievements:");
+    for achievement in definitions()? {
+        let earned = events
+            .iter()
+            .find(|e| e.kind == "achieved" && e.subject == achievement.subject());
+        match earned {
+            Some(event) => println!(
+                "  [x] {:<26} {}  {}",
+                achievement.title,
+                date(event.time),
+                achievement.description
+            ),
+            None => println!(
+                "  [ ] {:<26} {:<10}  {}",
+                achievement.title, "", achievement.description
+            ),
+        }
+    }
+    Ok(())
+}
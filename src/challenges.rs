@@ -0,0 +1,364 @@
+// Borrow-checker challenges: short programs that don't compile, for the learner to fix.
+//
+// Each challenge is a directory like `challenges/borrowck/01_two_mutable_borrows/` holding
+//
+//     challenge.rs   the broken program; its header names the error it fails with
+//                    (`// EXPECT_ERROR: E0499`) and the output it should produce once fixed
+//                    (one `// OUTPUT: <line>` per line)
+//     solution.rs    one way to fix it
+//     hints.txt      hints, one per line, revealed one at a time
+//
+// `rust_tutor challenge start` copies the challenge into the learner's workspace. A fix is
+// accepted when the copy compiles and prints exactly the expected output.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::compile_fail::{self, Verdict};
+use crate::compiler;
+use crate::lessons;
+use crate::progress::Store;
+use crate::sandbox::{Limit, Limits, Sandbox};
+use crate::Report;
+
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    // The path below `challenges/`, e.g. `borrowck/01_two_mutable_borrows`.
+    pub id: String,
+    pub dir: PathBuf,
+}
+
+impl Challenge {
+    // The directory name, e.g. `01_two_mutable_borrows`.
+    pub fn name(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or("")
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join("challenge.rs")
+    }
+
+    pub fn solution_path(&self) -> PathBuf {
+        self.dir.join("solution.rs")
+    }
+
+    pub fn source(&self) -> io::Result<String> {
+        fs::read_to_string(self.path())
+    }
+
+    // The first line of the header, e.g. `Two mutable borrows`.
+    pub fn title(&self) -> io::Result<String> {
+        let source = self.source()?;
+        let first = source.lines().next().unwrap_or("");
+        Ok(first.trim_start_matches("//").trim().to_string())
+    }
+
+    pub fn expected_error(&self) -> io::Result<String> {
+        Ok(header_values(&self.source()?, "EXPECT_ERROR")
+            .into_iter()
+            .next()
+            .unwrap_or_default())
+    }
+
+    pub fn expected_output(&self) -> io::Result<String> {
+        let lines = header_values(&self.source()?, "OUTPUT");
+        Ok(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+
+    pub fn hints(&self) -> io::Result<Vec<String>> {
+        let text = fs::read_to_string(self.dir.join("hints.txt"))?;
+        Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    // How the challenge is called in the progress log.
+    pub fn subject(&self) -> String {
+        format!("challenge/{}", self.id)
+    }
+
+    // The learner's copy.
+    pub fn workspace_file(&self, store: &Store) -> PathBuf {
+        store
+            .workspace()
+            .join("challenges")
+            .join(format!("{}.rs", self.id))
+    }
+}
+
+// The values of `// KEY: value` lines, in order.
+fn header_values(source: &str, key: &str) -> Vec<String> {
+    let prefix = format!("// {}:", key);
+    source
+        .lines()
+        .filter_map(|line| line.strip_prefix(&prefix))
+        .map(|value| value.strip_prefix(' ').unwrap_or(value).to_string())
+        .collect()
+}
+
+pub fn challenges_dir() -> PathBuf {
+    lessons::root().join("challenges")
+}
+
+// All challenges, ordered by set and then by number.
+pub fn all() -> io::Result<Vec<Challenge>> {
+    let mut challenges = Vec::new();
+    for set in sorted_dirs(&challenges_dir())? {
+        for dir in sorted_dirs(&set)? {
+            let id = format!("{}/{}", file_name(&set), file_name(&dir));
+            challenges.push(Challenge { id, dir });
+        }
+    }
+    Ok(challenges)
+}
+
+pub(crate) fn sorted_dirs(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut dirs: Vec<PathBuf> = fs::read_dir(dir)?
+        .map(|entry| entry.map(|e| e.path()))
+        .filter(|path| path.as_ref().map_or(true, |p| p.is_dir()))
+        .collect::<io::Result<_>>()?;
+    dirs.sort();
+    Ok(dirs)
+}
+
+pub(crate) fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+// Finds a challenge by id (`borrowck/01_two_mutable_borrows`), directory name
+// (`01_two_mutable_borrows`), number (`01`) or name without the number (`two_mutable_borrows`).
+pub fn find(name: &str) -> crate::Result<Challenge> {
+    let matches: Vec<Challenge> = all()?
+        .into_iter()
+        .filter(|c| {
+            let (number, rest) = c.name().split_once('_').unwrap_or(("", c.name()));
+            c.id == name || c.name() == name || number == name || rest == name
+        })
+        .collect();
+    match matches.len() {
+        0 => Err(Report::msg(format!(
+            "no challenge named `{}`; try `rust_tutor challenge list`",
+            name
+        ))),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|c| c.id.as_str()).collect();
+            Err(Report::msg(format!(
+                "`{}` is ambiguous: {}",
+                name,
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    Solved,
+    DoesNotCompile(String),
+    // It compiled, but crashed or was stopped by the sandbox.
+    Failed {
+        stderr: String,
+        limit: Option<Limit>,
+    },
+    WrongOutput {
+        expected: String,
+        actual: String,
+    },
+}
+
+// Compiles and runs `file` as an answer to `challenge`.
+pub fn check_file(challenge: &Challenge, file: &Path) -> crate::Result<Outcome> {
+    let mut sandbox = Sandbox::new(Limits::default())?;
+    sandbox.echo(false);
+    let binary = sandbox.dir().join(challenge.name());
+    let compiled = compiler::compile(file, &binary, &["-A", "warnings"])?;
+    if !compiled.success {
+        return Ok(Outcome::DoesNotCompile(compiled.stderr));
+    }
+
+    let run = sandbox.run(&binary, &[])?;
+    if !run.success() {
+        return Ok(Outcome::Failed {
+            stderr: run.stderr,
+            limit: run.limit,
+        });
+    }
+    let expected = challenge.expected_output()?;
+    // Trailing spaces are invisible in a terminal, so they don't count.
+    let normalize =
+        |text: &str| -> Vec<String> { text.lines().map(|l| l.trim_end().to_string()).collect() };
+    if normalize(&run.stdout) == normalize(&expected) {
+        Ok(Outcome::Solved)
+    } else {
+        Ok(Outcome::WrongOutput {
+            expected,
+            actual: run.stdout,
+        })
+    }
+}
+
+// Checks the learner's copy and records the attempt.
+pub fn check(store: &Store, challenge: &Challenge) -> crate::Result<Outcome> {
+    let file = challenge.workspace_file(store);
+    if !file.exists() {
+        return Err(Report::msg(format!(
+            "you haven't started {} yet; run `rust_tutor challenge start {}`",
+            challenge.id,
+            challenge.name()
+        )));
+    }
+    let outcome = check_file(challenge, &file)?;
+    let kind = if outcome == Outcome::Solved {
+        "solved"
+    } else {
+        "attempted"
+    };
+    store.record(kind, &challenge.subject())?;
+    Ok(outcome)
+}
+
+// Copies the challenge into the workspace, unless the learner already has a copy.
+pub fn start(store: &Store, challenge: &Challenge) -> crate::Result<PathBuf> {
+    let file = challenge.workspace_file(store);
+    if !file.exists() {
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(challenge.path(), &file)?;
+        store.record("started", &challenge.subject())?;
+    }
+    Ok(file)
+}
+
+// The next hint the learner hasn't seen, numbered from 1, if there is one left.
+pub fn hint(store: &Store, challenge: &Challenge) -> crate::Result<Option<(usize, String)>> {
+    Ok(store.next_hint(&challenge.subject(), &challenge.hints()?)?)
+}
+
+// Problems with the challenge itself: it must fail with the error it names, and its
+// solution must pass.
+pub fn verify(challenge: &Challenge) -> crate::Result<Vec<String>> {
+    let mut problems = Vec::new();
+    let code = challenge.expected_error()?;
+    if code.is_empty() {
+        problems.push(String::from("no EXPECT_ERROR line"));
+    } else {
+        match compile_fail::expect_error(&challenge.path(), &code, false)? {
+            Verdict::Failed => {}
+            Verdict::Compiled => problems.push(String::from("the challenge compiles")),
+            Verdict::WrongError(codes) => problems.push(format!(
+                "the challenge fails with {} instead of {}",
+                codes.join(", "),
+                code
+            )),
+        }
+    }
+    match check_file(challenge, &challenge.solution_path())? {
+        Outcome::Solved => {}
+        other => problems.push(format!("the solution does not pass: {:?}", other)),
+    }
+    if challenge.hints()?.is_empty() {
+        problems.push(String::from("no hints"));
+    }
+    Ok(problems)
+}
+
+// `rust_tutor challenge <list|start|hint|check|verify> [name]`
+pub fn command(store: &Store, args: &[&str]) -> crate::Result<()> {
+    match args {
+        [] | ["list"] => list(store),
+        ["start", name] => {
+            let challenge = find(name)?;
+            let file = start(store, &challenge)?;
+            println!("{}: {}\n", challenge.id, challenge.title()?);
+            println!("Your copy is {}", file.display());
+            println!(
+                "Fix it, then run `rust_tutor challenge check {}`.",
+                challenge.name()
+            );
+            Ok(())
+        }
+        ["hint", name] => {
+            let challenge = find(name)?;
+            match hint(store, &challenge)? {
+                Some((number, text)) => {
+                    let total = challenge.hints()?.len();
+                    println!("Hint {}/{}: {}", number, total, text);
+                }
+                None => println!("No more hints for this one."),
+            }
+            Ok(())
+        }
+        ["check", name] => {
+            let challenge = find(name)?;
+            match check(store, &challenge)? {
+                Outcome::Solved => {
+                    println!("Solved! It compiles and prints the expected output.");
+                    Ok(())
+                }
+                Outcome::DoesNotCompile(stderr) => {
+                    Err(Report::msg(format!("it doesn't compile yet:\n{}", stderr)))
+                }
+                Outcome::Failed {
+                    limit: Some(limit), ..
+                } => Err(Report::msg(format!("it was stopped: {}", limit))),
+                Outcome::Failed { stderr, .. } => Err(Report::msg(format!(
+                    "it compiles, but fails when run:\n{}",
+                    stderr
+                ))),
+                Outcome::WrongOutput { expected, actual } => Err(Report::msg(format!(
+                    "it compiles, but prints the wrong output.\n\nExpected:\n{}\nGot:\n{}",
+                    expected, actual
+                ))),
+            }
+        }
+        ["verify"] => {
+            let mut broken = 0;
+            for challenge in all()? {
+                let problems = verify(&challenge)?;
+                let status = if problems.is_empty() { "ok" } else { "FAILED" };
+                println!("{} ... {}", challenge.id, status);
+                for problem in &problems {
+                    println!("    {}", problem);
+                }
+                if !problems.is_empty() {
+                    broken += 1;
+                }
+            }
+            if broken > 0 {
+                return Err(Report::msg(format!("{} challenge(s) are broken", broken)));
+            }
+            Ok(())
+        }
+        _ => Err(Report::msg(
+            "usage: rust_tutor challenge <list|start|hint|check|verify> [name]",
+        )),
+    }
+}
+
+fn list(store: &Store) -> crate::Result<()> {
+    let events = store.events()?;
+    for challenge in all()? {
+        let subject = challenge.subject();
+        let has = |kind: &str| {
+            events
+                .iter()
+                .any(|e| e.kind == kind && e.subject == subject)
+        };
+        let status = if has("solved") {
+            "[solved] "
+        } else if has("started") {
+            "[started]"
+        } else {
+            "         "
+        };
+        println!("{} {:<44} {}", status, challenge.id, challenge.title()?);
+    }
+    Ok(())
+}
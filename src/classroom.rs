@@ -0,0 +1,431 @@
+// Running the tutor in a classroom: handing out assignments and grading what comes back.
+//
+// `rust_tutor assign` copies a selection of exercises, with their hidden tests and fixtures but
+// without their solutions, into a bundle directory along with a `MANIFEST.txt`. The modules
+// from `src/common` that the exercises use go in the bundle's own `src/common`:
+//
+//     curriculum 5d41402abc4b2a76
+//     exercise 03_ownership/first_word
+//     file 9f86d081...  exercises/03_ownership/first_word/exercise.rs
+//     file 2c26b46b...  src/common/tracked_mutex.rs
+//
+// `rust_tutor grade` takes a directory with one subdirectory per learner, laid out like a
+// workspace (`<learner>/exercises/03_ownership/first_word.rs`), grades every solution against
+// the bundle's hidden tests in parallel, and writes `gradebook.csv` and one feedback report
+// per learner.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::challenges::{file_name, sorted_dirs};
+use crate::exercises::{self, Exercise, Grade};
+use crate::lessons;
+use crate::sha256;
+use crate::{Context, Report};
+
+pub const MANIFEST: &str = "MANIFEST.txt";
+
+// Every file of an exercise is handed out, except this one, which stays with the instructor.
+const SOLUTION: &str = "solution.rs";
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    pub curriculum: String,
+    pub exercises: Vec<String>,
+    // Relative path and SHA-256 of every file, other than the manifest itself.
+    pub files: Vec<(String, String)>,
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> crate::Result<Manifest> {
+        let mut manifest = Manifest::default();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "curriculum" => manifest.curriculum = value.to_string(),
+                "exercise" => manifest.exercises.push(value.to_string()),
+                "file" => {
+                    let (hash, path) = value
+                        .split_once("  ")
+                        .with_context(|| format!("bad manifest line `{}`", line))?;
+                    manifest.files.push((path.to_string(), hash.to_string()));
+                }
+                _ => {}
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = format!("curriculum {}\n", self.curriculum);
+        for exercise in &self.exercises {
+            out.push_str(&format!("exercise {}\n", exercise));
+        }
+        for (path, hash) in &self.files {
+            out.push_str(&format!("file {}  {}\n", hash, path));
+        }
+        out
+    }
+
+    // Files under `dir` that are missing or no longer match their checksum.
+    pub fn verify(&self, dir: &Path) -> io::Result<Vec<String>> {
+        let mut problems = Vec::new();
+        for (path, hash) in &self.files {
+            match fs::read(dir.join(path)) {
+                Ok(bytes) if sha256::hex(&bytes) == *hash => {}
+                Ok(_) => problems.push(format!("{} has been changed", path)),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    problems.push(format!("{} is missing", path))
+                }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(problems)
+    }
+}
+
+// A short fingerprint of every exercise in the checkout, solutions and hidden tests included.
+// Two checkouts with the same curriculum version hand out and grade the same exercises.
+pub fn curriculum_version() -> io::Result<String> {
+    let mut listing = String::new();
+    for exercise in exercises::all()? {
+        for file in exercise.files()? {
+            let hash = sha256::hex(&fs::read(exercise.dir.join(&file))?);
+            listing.push_str(&format!("{}  {}/{}\n", hash, exercise.id, slashes(&file)));
+        }
+    }
+    Ok(sha256::hex(listing.as_bytes())[..16].to_string())
+}
+
+// The exercises meant by `names`: exercise ids or names, chapters, or lessons (which stand
+// for every exercise in their chapter).
+pub fn select(names: &[&str]) -> crate::Result<Vec<Exercise>> {
+    let all = exercises::all()?;
+    let mut selected: Vec<Exercise> = Vec::new();
+    for name in names {
+        let chapter = match lessons::find(name) {
+            Ok(lesson) => Some(lesson.chapter().to_string()),
+            Err(_) => all
+                .iter()
+                .any(|e| e.chapter() == *name)
+                .then(|| name.to_string()),
+        };
+        let matching: Vec<&Exercise> = match &chapter {
+            Some(chapter) => all.iter().filter(|e| e.chapter() == chapter).collect(),
+            None => all
+                .iter()
+                .filter(|e| e.id == *name || e.name() == *name)
+                .collect(),
+        };
+        if matching.is_empty() {
+            return Err(Report::msg(format!("`{}` has no exercises", name)));
+        }
+        for exercise in matching {
+            if !selected.iter().any(|e| e.id == exercise.id) {
+                selected.push(exercise.clone());
+            }
+        }
+    }
+    Ok(selected)
+}
+
+// Writes an assignment bundle with `exercises` into `out`, which must not exist yet.
+pub fn assign(exercises: &[Exercise], out: &Path) -> crate::Result<Manifest> {
+    if out.exists() {
+        return Err(Report::msg(format!("{} already exists", out.display())));
+    }
+    let mut manifest = Manifest {
+        curriculum: curriculum_version()?,
+        ..Manifest::default()
+    };
+    for exercise in exercises {
+        for (path, source) in handed_out(exercise)? {
+            // Exercises can share a common module.
+            if manifest.files.iter().any(|(p, _)| *p == path) {
+                continue;
+            }
+            let bytes = fs::read(source)?;
+            if let Some(parent) = out.join(&path).parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(out.join(&path), &bytes)?;
+            manifest.files.push((path, sha256::hex(&bytes)));
+        }
+        manifest.exercises.push(exercise.id.clone());
+    }
+    fs::write(out.join(MANIFEST), manifest.render())?;
+    Ok(manifest)
+}
+
+// The files of `exercise` that go into a bundle, common modules included: where they go, and
+// where they are now.
+fn handed_out(exercise: &Exercise) -> io::Result<Vec<(String, PathBuf)>> {
+    let dir = Path::new("exercises").join(&exercise.id);
+    let mut files: Vec<(String, PathBuf)> = exercise
+        .files()?
+        .into_iter()
+        .filter(|file| file != Path::new(SOLUTION))
+        .map(|file| (slashes(&dir.join(&file)), exercise.dir.join(&file)))
+        .collect();
+    for module in exercise.common_modules()? {
+        let path = Path::new("src").join("common").join(file_name(&module));
+        files.push((slashes(&path), module));
+    }
+    Ok(files)
+}
+
+// The path and SHA-256 of every file of `exercises` that a bundle hands out, as its manifest
+// lists them, whether the exercises come from a bundle or from the checkout.
+pub fn assigned_files(exercises: &[Exercise]) -> io::Result<Vec<(String, String)>> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    for exercise in exercises {
+        for (path, source) in handed_out(exercise)? {
+            if !files.iter().any(|(p, _)| *p == path) {
+                files.push((path, sha256::hex(&fs::read(source)?)));
+            }
+        }
+    }
+    Ok(files)
+}
+
+// Manifests use `/` on every platform.
+fn slashes(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+pub fn read_manifest(bundle: &Path) -> crate::Result<Manifest> {
+    let path = bundle.join(MANIFEST);
+    let text = fs::read_to_string(&path)
+        .with_context(|| format!("reading the bundle manifest {}", path.display()))?;
+    Manifest::parse(&text).with_context(|| format!("in {}", path.display()))
+}
+
+// Reads a bundle's manifest and checks that nothing in it was changed.
+pub fn open_bundle(bundle: &Path) -> crate::Result<Vec<Exercise>> {
+    let manifest = read_manifest(bundle)?;
+    let problems = manifest.verify(bundle)?;
+    if !problems.is_empty() {
+        return Err(Report::msg(format!(
+            "the bundle was modified:\n  {}",
+            problems.join("\n  ")
+        )));
+    }
+    Ok(manifest
+        .exercises
+        .iter()
+        .map(|id| Exercise {
+            id: id.clone(),
+            dir: bundle.join("exercises").join(id),
+        })
+        .collect())
+}
+
+// What grading one learner's solution to one exercise came to.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Mark {
+    Missing,
+    Graded(Grade),
+    // Grading itself went wrong, e.g. the disk is full.
+    Error(String),
+}
+
+impl Mark {
+    // A gradebook cell: `5/7` hidden tests passed, or what went wrong.
+    fn cell(&self) -> String {
+        match self {
+            Mark::Missing => String::from("missing"),
+            Mark::Graded(Grade::Tested(results)) => format!(
+                "{}/{}",
+                results.iter().filter(|r| r.passed).count(),
+                results.len()
+            ),
+            Mark::Graded(Grade::DoesNotCompile(_)) => String::from("does not compile"),
+            Mark::Graded(Grade::Incompatible) => String::from("incompatible"),
+            Mark::Graded(Grade::Stopped(limit)) => format!("stopped ({})", limit),
+            Mark::Graded(Grade::Crashed { .. }) => String::from("crashed"),
+            Mark::Error(_) => String::from("error"),
+        }
+    }
+
+    fn passed(&self) -> bool {
+        matches!(self, Mark::Graded(grade) if grade.passed())
+    }
+}
+
+pub struct Gradebook {
+    pub learners: Vec<String>,
+    pub exercises: Vec<String>,
+    // By learner, then exercise.
+    pub marks: BTreeMap<(String, String), Mark>,
+}
+
+// Grades every learner in `submissions` on every exercise, `jobs` solutions at a time.
+pub fn grade(submissions: &Path, exercises: &[Exercise], jobs: usize) -> io::Result<Gradebook> {
+    let learners: Vec<String> = sorted_dirs(submissions)?
+        .iter()
+        .map(|d| file_name(d))
+        .collect();
+    let work: Vec<(&String, &Exercise)> = learners
+        .iter()
+        .flat_map(|learner| exercises.iter().map(move |exercise| (learner, exercise)))
+        .collect();
+
+    let next = AtomicUsize::new(0);
+    let marks = Mutex::new(BTreeMap::new());
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            scope.spawn(|| {
+                while let Some(&(learner, exercise)) = work.get(next.fetch_add(1, Ordering::SeqCst))
+                {
+                    let file = submissions
+                        .join(learner)
+                        .join("exercises")
+                        .join(format!("{}.rs", exercise.id));
+                    let mark = if !file.exists() {
+                        Mark::Missing
+                    } else {
+                        match exercises::grade_file(exercise, &file) {
+                            Ok(grade) => Mark::Graded(grade),
+                            Err(e) => Mark::Error(format!("{:#}", e)),
+                        }
+                    };
+                    let key = (learner.clone(), exercise.id.clone());
+                    marks.lock().unwrap().insert(key, mark);
+                }
+            });
+        }
+    });
+
+    Ok(Gradebook {
+        learners,
+        exercises: exercises.iter().map(|e| e.id.clone()).collect(),
+        marks: marks.into_inner().unwrap(),
+    })
+}
+
+impl Gradebook {
+    fn mark(&self, learner: &str, exercise: &str) -> &Mark {
+        self.marks
+            .get(&(learner.to_string(), exercise.to_string()))
+            .unwrap_or(&Mark::Missing)
+    }
+
+    // One row per learner, one column per exercise, and how many exercises were passed.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec![String::from("learner")];
+        header.extend(self.exercises.iter().cloned());
+        header.push(String::from("passed"));
+        let mut out = csv_row(&header);
+        for learner in &self.learners {
+            let mut row = vec![learner.clone()];
+            let mut passed = 0;
+            for exercise in &self.exercises {
+                let mark = self.mark(learner, exercise);
+                passed += usize::from(mark.passed());
+                row.push(mark.cell());
+            }
+            row.push(format!("{}/{}", passed, self.exercises.len()));
+            out.push_str(&csv_row(&row));
+        }
+        out
+    }
+
+    pub fn feedback(&self, learner: &str) -> String {
+        let mut out = format!("Feedback for {}\n", learner);
+        for exercise in &self.exercises {
+            let mark = self.mark(learner, exercise);
+            out.push_str(&format!("\n{}: {}\n", exercise, mark.cell()));
+            match mark {
+                Mark::Graded(Grade::Tested(results)) => {
+                    for result in results {
+                        let status = if result.passed { "ok    " } else { "FAILED" };
+                        out.push_str(&format!("  {}  {}\n", status, result.name));
+                    }
+                }
+                Mark::Graded(Grade::DoesNotCompile(stderr)) => {
+                    for line in stderr.lines() {
+                        out.push_str(&format!("  {}\n", line));
+                    }
+                }
+                Mark::Graded(Grade::Incompatible) => out.push_str(
+                    "  The hidden tests can't use your code; keep the exercise's signatures.\n",
+                ),
+                Mark::Graded(Grade::Crashed { status, during }) => {
+                    if let Some(test) = during {
+                        out.push_str(&format!("  during {}\n", test));
+                    }
+                    out.push_str(&format!("  {}\n", status));
+                }
+                Mark::Error(e) => out.push_str(&format!("  {}\n", e)),
+                _ => {}
+            }
+        }
+        out
+    }
+}
+
+fn csv_row(fields: &[String]) -> String {
+    let quoted: Vec<String> = fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect();
+    format!("{}\n", quoted.join(","))
+}
+
+// `rust_tutor assign <lessons...> [--out <dir>]`
+pub fn assign_command(names: &[&str], out: &Path) -> crate::Result<()> {
+    if names.is_empty() {
+        return Err(Report::msg(
+            "usage: rust_tutor assign <lessons...> [--out <dir>]",
+        ));
+    }
+    let manifest = assign(&select(names)?, out)?;
+    println!(
+        "Wrote {} with {} exercise(s), curriculum {}:",
+        out.display(),
+        manifest.exercises.len(),
+        manifest.curriculum
+    );
+    for exercise in &manifest.exercises {
+        println!("  {}", exercise);
+    }
+    Ok(())
+}
+
+// `rust_tutor grade <submissions-dir> [--bundle <dir>] [--out <dir>]`
+pub fn grade_command(submissions: &Path, bundle: Option<&Path>, out: &Path) -> crate::Result<()> {
+    let exercises = match bundle {
+        Some(bundle) => open_bundle(bundle)?,
+        None => exercises::all()?,
+    };
+    let jobs = thread::available_parallelism().map_or(2, |n| n.get());
+    let gradebook = grade(submissions, &exercises, jobs)?;
+
+    let feedback_dir = out.join("feedback");
+    fs::create_dir_all(&feedback_dir)?;
+    fs::write(out.join("gradebook.csv"), gradebook.to_csv())?;
+    for learner in &gradebook.learners {
+        fs::write(
+            feedback_dir.join(format!("{}.txt", learner)),
+            gradebook.feedback(learner),
+        )?;
+    }
+    print!("{}", gradebook.to_csv());
+    println!(
+        "\nWrote {} and {} feedback report(s).",
+        out.join("gradebook.csv").display(),
+        gradebook.learners.len()
+    );
+    Ok(())
+}
@@ -1 +1,18 @@
+// Helpers shared by the tutor and the lessons.
+//
+// Lessons are standalone programs, so they cannot `use rust_tutor::...`. Instead a lesson pulls
+// in just the module it needs by path, which keeps it runnable with a plain `rustc`:
+//
+//     #[path = "../../common/clock.rs"]
+//     mod clock;
+//
+// For that to work, every module in here must be self-contained and only depend on `std`.
 
+pub mod clock;
+pub mod explore;
+pub mod memory;
+pub mod pipeline;
+pub mod rc_graph;
+pub mod report;
+pub mod trace;
+pub mod tracked_mutex;
@@ -0,0 +1,136 @@
+// A clock for lessons that sleep, so the tutor can speed them up.
+//
+// Lessons call `clock::sleep` where they would call `thread::sleep`. How long that really takes
+// is decided by whoever runs the lesson, through the `RUST_TUTOR_CLOCK` environment variable:
+//
+//   real            sleep for the full duration (the default)
+//   scaled:<factor> sleep for `factor` times the duration; `fast` is short for `scaled:0.01`
+//   virtual         don't sleep at all, only advance a virtual clock; `instant` also works
+//
+// Interactively the lesson keeps its pacing, while tests and exercise checks finish instantly.
+//
+// In virtual time each thread keeps its own clock. A thread started with `clock::spawn` starts
+// at its parent's time; one started with plain `thread::spawn` starts at zero.
+
+use std::env;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use std::thread;
+use std::time::{Duration, Instant};
+
+pub const ENV: &str = "RUST_TUTOR_CLOCK";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    Real,
+    Scaled(f64),
+    Virtual,
+}
+
+impl Mode {
+    pub fn parse(s: &str) -> Option<Mode> {
+        match s {
+            "real" => Some(Mode::Real),
+            "fast" => Some(Mode::Scaled(0.01)),
+            "virtual" | "instant" => Some(Mode::Virtual),
+            _ => {
+                let factor: f64 = s.strip_prefix("scaled:")?.parse().ok()?;
+                if factor >= 0.0 && factor.is_finite() {
+                    Some(Mode::Scaled(factor))
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    // The mode this process was started with. Anything unrecognized falls back to real time.
+    pub fn from_env() -> Mode {
+        env::var(ENV)
+            .ok()
+            .and_then(|value| Mode::parse(&value))
+            .unwrap_or(Mode::Real)
+    }
+}
+
+// Formats the mode the way `Mode::parse` reads it, so it can be passed on through `ENV`.
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Real => write!(f, "real"),
+            Mode::Scaled(factor) => write!(f, "scaled:{}", factor),
+            Mode::Virtual => write!(f, "virtual"),
+        }
+    }
+}
+
+struct Clock {
+    mode: Mode,
+    started: Instant,
+    // Virtual time in nanoseconds: the furthest any thread has slept so far.
+    virtual_nanos: AtomicU64,
+}
+
+fn clock() -> &'static Clock {
+    static CLOCK: OnceLock<Clock> = OnceLock::new();
+    CLOCK.get_or_init(|| Clock {
+        mode: Mode::from_env(),
+        started: Instant::now(),
+        virtual_nanos: AtomicU64::new(0),
+    })
+}
+
+thread_local! {
+    // Each thread's own virtual time. Two threads sleeping one second each in parallel should
+    // move the clock forward by one second, not two.
+    static THREAD_NANOS: std::cell::Cell<u64> = const { std::cell::Cell::new(0) };
+}
+
+pub fn mode() -> Mode {
+    clock().mode
+}
+
+// Use this instead of `thread::sleep` in lessons.
+pub fn sleep(duration: Duration) {
+    let clock = clock();
+    match clock.mode {
+        Mode::Real => thread::sleep(duration),
+        Mode::Scaled(factor) => thread::sleep(duration.mul_f64(factor)),
+        Mode::Virtual => {
+            let now = THREAD_NANOS.with(|nanos| {
+                let now = nanos.get() + duration.as_nanos() as u64;
+                nanos.set(now);
+                now
+            });
+            clock.virtual_nanos.fetch_max(now, Ordering::SeqCst);
+            // Give other threads a chance to run, like a real sleep would.
+            thread::yield_now();
+        }
+    }
+}
+
+// Use this instead of `thread::spawn` for threads that call `sleep`, so that in virtual time a
+// thread spawned at 50ms doesn't sleep as if it had started at 0ms.
+pub fn spawn<F, T>(f: F) -> thread::JoinHandle<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let parent = THREAD_NANOS.with(|nanos| nanos.get());
+    thread::spawn(move || {
+        THREAD_NANOS.with(|nanos| nanos.set(parent));
+        f()
+    })
+}
+
+// How much lesson time has passed since the clock was first used.
+pub fn elapsed() -> Duration {
+    let clock = clock();
+    match clock.mode {
+        Mode::Real => clock.started.elapsed(),
+        Mode::Scaled(factor) if factor > 0.0 => clock.started.elapsed().div_f64(factor),
+        Mode::Scaled(_) => Duration::ZERO,
+        Mode::Virtual => Duration::from_nanos(clock.virtual_nanos.load(Ordering::SeqCst)),
+    }
+}
@@ -0,0 +1,932 @@
+// Exploring the interleavings of a concurrent program, a tiny version of the `loom` crate.
+//
+// A threaded program can pass a thousand runs and fail on the next one, because the operating
+// system decides when each thread runs. A lesson that wraps its threads in `model` and uses the
+// look-alikes from here instead of `std` (`explore::sync::{Arc, Mutex}`,
+// `explore::sync::atomic`, `explore::sync::mpsc`, `explore::thread`) hands that decision to a
+// scheduler instead. The threads are real, but only one of them runs at a time, and whenever a
+// thread is about to do something the others can see (lock a mutex, load an atomic, send on a
+// channel), the scheduler decides who goes next.
+//
+// Normally `model` runs a single interleaving, where each thread keeps going until it blocks,
+// so the lesson's output is the same on every run. When the tutor sets `RUST_TUTOR_EXPLORE`
+// (`rust_tutor explore <lesson>`), it runs the model again and again, making a different choice
+// each time, until every interleaving has been tried or a bound is reached. An interleaving in
+// which a thread panics (a failed `assert!`, say) or every thread is stuck waiting is reported
+// step by step.
+//
+// The value of `RUST_TUTOR_EXPLORE` is `preemptions:<n>,interleavings:<n>`. Trying every
+// interleaving of ten threads would take forever, so the search is bounded: a thread may only
+// be switched away from `preemptions` times while it could have gone on (switching when it
+// blocks or finishes is free), and at most `interleavings` are tried. Most bugs need only one or
+// two preemptions to show up. A value that doesn't parse makes `model` panic. Memory orderings
+// aren't modelled: every atomic operation behaves as if it were `SeqCst`.
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::env;
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::Path;
+use std::sync::{Arc, Condvar, MutexGuard as StdMutexGuard, OnceLock, PoisonError};
+use std::thread as std_thread;
+
+pub const ENV: &str = "RUST_TUTOR_EXPLORE";
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    // How many times a thread may be switched away from while it could have gone on.
+    pub preemptions: usize,
+    // How many interleavings to try at most.
+    pub interleavings: usize,
+}
+
+impl Default for Bounds {
+    fn default() -> Bounds {
+        Bounds {
+            preemptions: 2,
+            interleavings: 1000,
+        }
+    }
+}
+
+impl Bounds {
+    // Reads `preemptions:<n>,interleavings:<n>`; either part can be left out.
+    pub fn parse(s: &str) -> Option<Bounds> {
+        let mut bounds = Bounds::default();
+        for part in s.split(',').filter(|p| !p.is_empty()) {
+            let (key, value) = part.split_once(':')?;
+            let value: usize = value.parse().ok()?;
+            match key {
+                "preemptions" => bounds.preemptions = value,
+                "interleavings" if value > 0 => bounds.interleavings = value,
+                _ => return None,
+            }
+        }
+        Some(bounds)
+    }
+
+    // The bounds to explore with, or `None` to run a single interleaving. Bounds that don't
+    // parse are an error: running one interleaving instead would look like a passing search.
+    pub fn from_env() -> Result<Option<Bounds>, String> {
+        match env::var(ENV) {
+            Ok(value) => Bounds::parse(&value).map(Some).ok_or_else(|| {
+                format!(
+                    "{} is `{}`, but should be `preemptions:<n>,interleavings:<n>` \
+                     with at least one interleaving",
+                    ENV, value
+                )
+            }),
+            Err(env::VarError::NotPresent) => Ok(None),
+            Err(env::VarError::NotUnicode(_)) => Err(format!("{} is not valid UTF-8", ENV)),
+        }
+    }
+}
+
+// Formats the bounds the way `Bounds::parse` reads them, so they can be passed on through `ENV`.
+impl fmt::Display for Bounds {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "preemptions:{},interleavings:{}",
+            self.preemptions, self.interleavings
+        )
+    }
+}
+
+// What went wrong in an interleaving, and the steps that led there.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Failure {
+    pub message: String,
+    pub steps: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Outcome {
+    // How many interleavings were run, including the failing one.
+    pub interleavings: usize,
+    // Whether every interleaving within the preemption bound was tried.
+    pub complete: bool,
+    pub failure: Option<Failure>,
+}
+
+// Runs `f` under the scheduler, once per interleaving, until one fails or there are no more
+// (within `bounds`).
+pub fn check<F>(bounds: Bounds, f: F) -> Outcome
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    install_panic_hook();
+    let f = Arc::new(f);
+    let mut replay = Vec::new();
+    for n in 1..=bounds.interleavings {
+        let (choices, steps, failure) = run_once(&f, replay, bounds.preemptions);
+        if let Some(message) = failure {
+            return Outcome {
+                interleavings: n,
+                complete: false,
+                failure: Some(Failure { message, steps }),
+            };
+        }
+        // Depth first: change the last choice that still has an option left, and replay the
+        // ones before it.
+        match next_choices(choices) {
+            Some(next) => replay = next,
+            None => {
+                return Outcome {
+                    interleavings: n,
+                    complete: true,
+                    failure: None,
+                }
+            }
+        }
+    }
+    Outcome {
+        interleavings: bounds.interleavings,
+        complete: false,
+        failure: None,
+    }
+}
+
+// Checks `f` and prints what happened under `name`: one interleaving normally, as many as the
+// bounds allow under `rust_tutor explore`.
+pub fn model<F>(name: &str, f: F)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let explore = match Bounds::from_env() {
+        Ok(explore) => explore,
+        Err(message) => panic!("{}: {}", name, message),
+    };
+    let bounds = explore.unwrap_or(Bounds {
+        preemptions: 0,
+        interleavings: 1,
+    });
+    let outcome = check(bounds, f);
+    match (&outcome.failure, explore) {
+        (Some(failure), _) => {
+            println!(
+                "{}: interleaving {} fails: {}",
+                name,
+                outcome.interleavings,
+                failure.message.replace('\n', "\n    ")
+            );
+            for step in &failure.steps {
+                println!("    {}", step);
+            }
+        }
+        (None, None) => println!("{}: ok in one interleaving", name),
+        (None, Some(bounds)) if outcome.complete => println!(
+            "{}: all {} interleavings pass (with a preemption bound of {})",
+            name, outcome.interleavings, bounds.preemptions
+        ),
+        (None, Some(bounds)) => println!(
+            "{}: the first {} interleavings pass (with a preemption bound of {}); there are more",
+            name, outcome.interleavings, bounds.preemptions
+        ),
+    }
+}
+
+fn next_choices(mut choices: Vec<(usize, usize)>) -> Option<Vec<(usize, usize)>> {
+    while let Some((index, options)) = choices.pop() {
+        if index + 1 < options {
+            choices.push((index + 1, options));
+            return Some(choices);
+        }
+    }
+    None
+}
+
+// THE SCHEDULER
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Resource {
+    // A mutex or channel, by its index in `State::objects`.
+    Object(usize),
+    // A thread being joined.
+    Thread(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Status {
+    Runnable,
+    Blocked(Resource),
+    Finished,
+}
+
+struct ModelThread {
+    name: String,
+    status: Status,
+    // Signalled when it's this thread's turn.
+    turn: Arc<Condvar>,
+}
+
+struct State {
+    threads: Vec<ModelThread>,
+    // The names of the mutexes, channels and atomics created in this interleaving.
+    objects: Vec<String>,
+    active: usize,
+    // The choices to make again, from the interleaving before.
+    replay: Vec<(usize, usize)>,
+    // Each choice made where there was more than one option: (the one taken, how many).
+    choices: Vec<(usize, usize)>,
+    preemptions: usize,
+    max_preemptions: usize,
+    steps: Vec<String>,
+    failure: Option<String>,
+    handles: Vec<std_thread::JoinHandle<()>>,
+}
+
+struct Execution {
+    state: std::sync::Mutex<State>,
+    // Signalled when a thread finishes, for `run_once` to notice when all have.
+    finished: Condvar,
+}
+
+// Unwinds the threads of an interleaving that has already failed.
+struct Abort;
+
+thread_local! {
+    static CURRENT: RefCell<Option<(Arc<Execution>, usize)>> = const { RefCell::new(None) };
+    static PANIC: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// The interleaving this thread is part of, and its number in it.
+fn current() -> Option<(Arc<Execution>, usize)> {
+    CURRENT.try_with(|c| c.borrow().clone()).ok().flatten()
+}
+
+impl Execution {
+    fn lock(&self) -> StdMutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    // Picks the thread to run next, replaying the earlier choice if there is one.
+    fn choose(&self, state: &mut State, me: usize) {
+        let runnable: Vec<usize> = (0..state.threads.len())
+            .filter(|&t| state.threads[t].status == Status::Runnable)
+            .collect();
+        if runnable.is_empty() {
+            if state.failure.is_none() && state.threads.iter().any(|t| t.status != Status::Finished)
+            {
+                state.failure = Some(deadlock(state));
+            }
+            return;
+        }
+        // Going on with the same thread comes first, and isn't a preemption.
+        let stays = state.threads[me].status == Status::Runnable;
+        let mut options = Vec::new();
+        if stays {
+            options.push(me);
+        }
+        if !stays || state.preemptions < state.max_preemptions {
+            options.extend(runnable.into_iter().filter(|&t| t != me));
+        }
+        let mut index = 0;
+        if options.len() > 1 {
+            let step = state.choices.len();
+            index = state
+                .replay
+                .get(step)
+                .map_or(0, |&(i, _)| i.min(options.len() - 1));
+            state.choices.push((index, options.len()));
+        }
+        let next = options[index];
+        if stays && next != me {
+            state.preemptions += 1;
+        }
+        state.active = next;
+    }
+
+    // Hands over to the chosen thread and waits for `me` to be chosen in turn.
+    fn wait_for_turn(&self, mut state: StdMutexGuard<'_, State>, me: usize) {
+        if state.failure.is_some() {
+            self.abort(state);
+        }
+        if state.active != me {
+            state.threads[state.active].turn.notify_one();
+            let turn = Arc::clone(&state.threads[me].turn);
+            while state.active != me && state.failure.is_none() {
+                state = turn.wait(state).unwrap_or_else(PoisonError::into_inner);
+            }
+            if state.failure.is_some() {
+                self.abort(state);
+            }
+        }
+    }
+
+    // Wakes every thread so they all unwind, and unwinds this one.
+    fn abort(&self, state: StdMutexGuard<'_, State>) -> ! {
+        for thread in &state.threads {
+            thread.turn.notify_one();
+        }
+        drop(state);
+        panic::resume_unwind(Box::new(Abort))
+    }
+}
+
+fn deadlock(state: &State) -> String {
+    let waiting: Vec<String> = state
+        .threads
+        .iter()
+        .filter_map(|t| match t.status {
+            Status::Blocked(Resource::Object(o)) => {
+                Some(format!("{} waits for {}", t.name, state.objects[o]))
+            }
+            Status::Blocked(Resource::Thread(j)) => Some(format!(
+                "{} waits for {} to finish",
+                t.name, state.threads[j].name
+            )),
+            _ => None,
+        })
+        .collect();
+    format!("deadlock: {}", waiting.join(", "))
+}
+
+// Lets the scheduler pick who goes next, right before `me` does something others can see.
+fn schedule(exec: &Execution, me: usize) {
+    let mut state = exec.lock();
+    exec.choose(&mut state, me);
+    exec.wait_for_turn(state, me);
+}
+
+// Stops `me` until `on` changes, and lets another thread run meanwhile.
+fn block(exec: &Execution, me: usize, on: Resource) {
+    let mut state = exec.lock();
+    state.threads[me].status = Status::Blocked(on);
+    exec.choose(&mut state, me);
+    exec.wait_for_turn(state, me);
+}
+
+fn wake(state: &mut State, on: Resource) {
+    for thread in &mut state.threads {
+        if thread.status == Status::Blocked(on) {
+            thread.status = Status::Runnable;
+        }
+    }
+}
+
+// Adds a step to the report, unless the interleaving has already failed and is unwinding.
+fn record(exec: &Execution, me: usize, what: String) {
+    let mut state = exec.lock();
+    if state.failure.is_some() {
+        return;
+    }
+    let step = format!("{}: {}", state.threads[me].name, what);
+    state.steps.push(step);
+}
+
+// Gives a mutex, channel or atomic created in a model a name like "mutex 1" for the reports.
+fn register(kind: &str) -> Option<usize> {
+    let (exec, _) = current()?;
+    let mut state = exec.lock();
+    let n = state.objects.iter().filter(|o| o.starts_with(kind)).count() + 1;
+    state.objects.push(format!("{} {}", kind, n));
+    Some(state.objects.len() - 1)
+}
+
+fn object_name(exec: &Execution, object: Option<usize>) -> String {
+    match object {
+        Some(o) => exec.lock().objects[o].clone(),
+        None => String::from("(created outside the model)"),
+    }
+}
+
+// Runs an operation other threads can see at a scheduling point, and records it.
+fn visible<R>(object: Option<usize>, op: impl FnOnce() -> R, what: impl FnOnce(&R) -> String) -> R {
+    match current() {
+        None => op(),
+        Some((exec, me)) => {
+            schedule(&exec, me);
+            let result = op();
+            let name = object_name(&exec, object);
+            record(&exec, me, format!("{} {}", what(&result), name));
+            result
+        }
+    }
+}
+
+// The body of every thread in a model, the first included.
+fn run_thread(exec: Arc<Execution>, me: usize, body: impl FnOnce()) {
+    CURRENT.with(|c| *c.borrow_mut() = Some((Arc::clone(&exec), me)));
+    let outcome = panic::catch_unwind(AssertUnwindSafe(|| {
+        exec.wait_for_turn(exec.lock(), me);
+        body();
+    }));
+    let message = PANIC.with(|p| p.borrow_mut().take());
+    let mut state = exec.lock();
+    if let Err(payload) = outcome {
+        if !payload.is::<Abort>() && state.failure.is_none() {
+            let message = message.unwrap_or_else(|| String::from("with a panic"));
+            let failure = format!("{} panicked {}", state.threads[me].name, message);
+            state.failure = Some(failure);
+        }
+    }
+    state.threads[me].status = Status::Finished;
+    wake(&mut state, Resource::Thread(me));
+    if state.failure.is_none() {
+        exec.choose(&mut state, me);
+    }
+    if state.failure.is_some() {
+        for thread in &state.threads {
+            thread.turn.notify_one();
+        }
+    } else if state.threads[state.active].status == Status::Runnable {
+        state.threads[state.active].turn.notify_one();
+    }
+    exec.finished.notify_all();
+    drop(state);
+    CURRENT.with(|c| *c.borrow_mut() = None);
+}
+
+type Choices = Vec<(usize, usize)>;
+
+fn run_once<F>(
+    f: &Arc<F>,
+    replay: Choices,
+    max_preemptions: usize,
+) -> (Choices, Vec<String>, Option<String>)
+where
+    F: Fn() + Send + Sync + 'static,
+{
+    let exec = Arc::new(Execution {
+        state: std::sync::Mutex::new(State {
+            threads: vec![ModelThread {
+                name: String::from("main"),
+                status: Status::Runnable,
+                turn: Arc::new(Condvar::new()),
+            }],
+            objects: Vec::new(),
+            active: 0,
+            replay,
+            choices: Vec::new(),
+            preemptions: 0,
+            max_preemptions,
+            steps: Vec::new(),
+            failure: None,
+            handles: Vec::new(),
+        }),
+        finished: Condvar::new(),
+    });
+    let main = {
+        let exec = Arc::clone(&exec);
+        let f = Arc::clone(f);
+        std_thread::spawn(move || run_thread(exec, 0, move || f()))
+    };
+    let mut state = exec.lock();
+    while state.threads.iter().any(|t| t.status != Status::Finished) {
+        state = exec
+            .finished
+            .wait(state)
+            .unwrap_or_else(PoisonError::into_inner);
+    }
+    let handles = std::mem::take(&mut state.handles);
+    let result = (
+        std::mem::take(&mut state.choices),
+        std::mem::take(&mut state.steps),
+        state.failure.take(),
+    );
+    drop(state);
+    let _ = main.join();
+    for handle in handles {
+        let _ = handle.join();
+    }
+    result
+}
+
+// Panics in a model are part of the report, so they aren't printed as they happen. Panics
+// anywhere else still go to the usual hook.
+fn install_panic_hook() {
+    static INSTALLED: OnceLock<()> = OnceLock::new();
+    INSTALLED.get_or_init(|| {
+        let default = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if current().is_none() {
+                return default(info);
+            }
+            let message = info.payload_as_str().unwrap_or("a panic");
+            let message = match info.location() {
+                Some(location) => {
+                    let file = Path::new(location.file()).file_name().unwrap_or_default();
+                    let line = location.line();
+                    format!("at {}:{}: {}", file.to_string_lossy(), line, message)
+                }
+                None => format!("with {}", message),
+            };
+            let _ = PANIC.try_with(|p| *p.borrow_mut() = Some(message));
+        }));
+    });
+}
+
+// THE LOOK-ALIKES
+
+pub mod thread {
+    use super::*;
+
+    pub struct JoinHandle<T> {
+        inner: Inner<T>,
+    }
+
+    enum Inner<T> {
+        Std(std_thread::JoinHandle<T>),
+        Model {
+            exec: Arc<Execution>,
+            id: usize,
+            result: Arc<std::sync::Mutex<Option<T>>>,
+        },
+    }
+
+    pub fn spawn<F, T>(f: F) -> JoinHandle<T>
+    where
+        F: FnOnce() -> T + Send + 'static,
+        T: Send + 'static,
+    {
+        let Some((exec, me)) = current() else {
+            return JoinHandle {
+                inner: Inner::Std(std_thread::spawn(f)),
+            };
+        };
+        let result = Arc::new(std::sync::Mutex::new(None));
+        let id = {
+            let mut state = exec.lock();
+            let id = state.threads.len();
+            state.threads.push(ModelThread {
+                name: format!("thread {}", id),
+                status: Status::Runnable,
+                turn: Arc::new(Condvar::new()),
+            });
+            let step = format!("{}: spawn thread {}", state.threads[me].name, id);
+            state.steps.push(step);
+            id
+        };
+        let handle = {
+            let exec = Arc::clone(&exec);
+            let result = Arc::clone(&result);
+            std_thread::spawn(move || {
+                run_thread(exec, id, move || {
+                    let value = f();
+                    *result.lock().unwrap_or_else(PoisonError::into_inner) = Some(value);
+                })
+            })
+        };
+        exec.lock().handles.push(handle);
+        JoinHandle {
+            inner: Inner::Model { exec, id, result },
+        }
+    }
+
+    impl<T> JoinHandle<T> {
+        pub fn join(self) -> std_thread::Result<T> {
+            let (exec, id, result) = match self.inner {
+                Inner::Std(handle) => return handle.join(),
+                Inner::Model { exec, id, result } => (exec, id, result),
+            };
+            let me = current().map_or(0, |(_, me)| me);
+            loop {
+                schedule(&exec, me);
+                if exec.lock().threads[id].status == Status::Finished {
+                    break;
+                }
+                block(&exec, me, Resource::Thread(id));
+            }
+            record(&exec, me, format!("join thread {}", id));
+            let value = result.lock().unwrap_or_else(PoisonError::into_inner).take();
+            value.ok_or_else(|| Box::new("the thread panicked") as Box<dyn std::any::Any + Send>)
+        }
+    }
+}
+
+pub mod sync {
+    use super::*;
+    use std::ops::{Deref, DerefMut};
+    use std::sync::LockResult;
+
+    // Cloning an `Arc` isn't something other threads can see, so the real one does.
+    pub use std::sync::Arc;
+
+    pub struct Mutex<T> {
+        object: Option<usize>,
+        // The thread holding the lock, in a model.
+        owner: std::sync::Mutex<Option<usize>>,
+        data: std::sync::Mutex<T>,
+    }
+
+    pub struct MutexGuard<'a, T> {
+        mutex: &'a Mutex<T>,
+        guard: Option<StdMutexGuard<'a, T>>,
+    }
+
+    impl<T> Mutex<T> {
+        pub fn new(value: T) -> Mutex<T> {
+            Mutex {
+                object: register("mutex"),
+                owner: std::sync::Mutex::new(None),
+                data: std::sync::Mutex::new(value),
+            }
+        }
+
+        // Never reports poisoning: a panic in a model ends the interleaving anyway.
+        pub fn lock(&self) -> LockResult<MutexGuard<'_, T>> {
+            if let Some((exec, me)) = current() {
+                loop {
+                    schedule(&exec, me);
+                    let mut owner = self.owner.lock().unwrap_or_else(PoisonError::into_inner);
+                    if owner.is_none() {
+                        *owner = Some(me);
+                        break;
+                    }
+                    drop(owner);
+                    match self.object {
+                        Some(o) => block(&exec, me, Resource::Object(o)),
+                        None => std_thread::yield_now(),
+                    }
+                }
+                record(
+                    &exec,
+                    me,
+                    format!("lock {}", object_name(&exec, self.object)),
+                );
+            }
+            let guard = self.data.lock().unwrap_or_else(PoisonError::into_inner);
+            Ok(MutexGuard {
+                mutex: self,
+                guard: Some(guard),
+            })
+        }
+    }
+
+    impl<T> Deref for MutexGuard<'_, T> {
+        type Target = T;
+
+        fn deref(&self) -> &T {
+            self.guard.as_ref().unwrap()
+        }
+    }
+
+    impl<T> DerefMut for MutexGuard<'_, T> {
+        fn deref_mut(&mut self) -> &mut T {
+            self.guard.as_mut().unwrap()
+        }
+    }
+
+    impl<T> Drop for MutexGuard<'_, T> {
+        fn drop(&mut self) {
+            self.guard.take();
+            let Some((exec, me)) = current() else {
+                return;
+            };
+            *self
+                .mutex
+                .owner
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner) = None;
+            if let Some(o) = self.mutex.object {
+                wake(&mut exec.lock(), Resource::Object(o));
+            }
+            let name = object_name(&exec, self.mutex.object);
+            record(&exec, me, format!("unlock {}", name));
+        }
+    }
+
+    pub mod atomic {
+        use super::super::*;
+
+        // Accepted and ignored: every operation acts as `SeqCst` in a model.
+        pub use std::sync::atomic::Ordering;
+
+        pub struct AtomicUsize {
+            object: Option<usize>,
+            value: std::sync::atomic::AtomicUsize,
+        }
+
+        impl AtomicUsize {
+            pub fn new(value: usize) -> AtomicUsize {
+                AtomicUsize {
+                    object: register("atomic"),
+                    value: std::sync::atomic::AtomicUsize::new(value),
+                }
+            }
+
+            pub fn load(&self, order: Ordering) -> usize {
+                visible(
+                    self.object,
+                    || self.value.load(order),
+                    |v| format!("load {} from", v),
+                )
+            }
+
+            pub fn store(&self, value: usize, order: Ordering) {
+                visible(
+                    self.object,
+                    || self.value.store(value, order),
+                    |_| format!("store {} in", value),
+                )
+            }
+
+            pub fn fetch_add(&self, value: usize, order: Ordering) -> usize {
+                visible(
+                    self.object,
+                    || self.value.fetch_add(value, order),
+                    |old| format!("add {} to {} in", value, old),
+                )
+            }
+
+            pub fn compare_exchange(
+                &self,
+                current: usize,
+                new: usize,
+                success: Ordering,
+                failure: Ordering,
+            ) -> Result<usize, usize> {
+                visible(
+                    self.object,
+                    || self.value.compare_exchange(current, new, success, failure),
+                    |result| match result {
+                        Ok(_) => format!("swap {} for {} in", current, new),
+                        Err(actual) => format!("find {}, not {}, in", actual, current),
+                    },
+                )
+            }
+        }
+    }
+
+    pub mod mpsc {
+        use super::super::*;
+
+        pub use std::sync::mpsc::{RecvError, SendError};
+
+        struct Queue<T> {
+            items: VecDeque<T>,
+            senders: usize,
+            receiver: bool,
+        }
+
+        struct Channel<T> {
+            object: Option<usize>,
+            queue: std::sync::Mutex<Queue<T>>,
+            // Outside a model, `recv` waits on this.
+            ready: Condvar,
+        }
+
+        impl<T> Channel<T> {
+            fn queue(&self) -> StdMutexGuard<'_, Queue<T>> {
+                self.queue.lock().unwrap_or_else(PoisonError::into_inner)
+            }
+
+            // Lets a receiver waiting on this channel look again.
+            fn changed(&self) {
+                self.ready.notify_all();
+                if let (Some((exec, _)), Some(o)) = (current(), self.object) {
+                    wake(&mut exec.lock(), Resource::Object(o));
+                }
+            }
+        }
+
+        pub struct Sender<T> {
+            channel: Arc<Channel<T>>,
+        }
+
+        pub struct Receiver<T> {
+            channel: Arc<Channel<T>>,
+        }
+
+        pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+            let channel = Arc::new(Channel {
+                object: register("channel"),
+                queue: std::sync::Mutex::new(Queue {
+                    items: VecDeque::new(),
+                    senders: 1,
+                    receiver: true,
+                }),
+                ready: Condvar::new(),
+            });
+            let sender = Sender {
+                channel: Arc::clone(&channel),
+            };
+            (sender, Receiver { channel })
+        }
+
+        impl<T> Sender<T> {
+            pub fn send(&self, value: T) -> Result<(), SendError<T>> {
+                let sent = visible(
+                    self.channel.object,
+                    || {
+                        let mut queue = self.channel.queue();
+                        if !queue.receiver {
+                            return Err(SendError(value));
+                        }
+                        queue.items.push_back(value);
+                        Ok(())
+                    },
+                    |_| String::from("send on"),
+                );
+                self.channel.changed();
+                sent
+            }
+        }
+
+        impl<T> Clone for Sender<T> {
+            fn clone(&self) -> Sender<T> {
+                self.channel.queue().senders += 1;
+                Sender {
+                    channel: Arc::clone(&self.channel),
+                }
+            }
+        }
+
+        impl<T> Drop for Sender<T> {
+            fn drop(&mut self) {
+                self.channel.queue().senders -= 1;
+                self.channel.changed();
+            }
+        }
+
+        impl<T> Receiver<T> {
+            pub fn recv(&self) -> Result<T, RecvError> {
+                let channel = &self.channel;
+                let Some((exec, me)) = current() else {
+                    let mut queue = channel.queue();
+                    loop {
+                        if let Some(value) = queue.items.pop_front() {
+                            return Ok(value);
+                        }
+                        if queue.senders == 0 {
+                            return Err(RecvError);
+                        }
+                        queue = channel
+                            .ready
+                            .wait(queue)
+                            .unwrap_or_else(PoisonError::into_inner);
+                    }
+                };
+                loop {
+                    schedule(&exec, me);
+                    let mut queue = channel.queue();
+                    let received = match queue.items.pop_front() {
+                        Some(value) => Ok(value),
+                        None if queue.senders == 0 => Err(RecvError),
+                        None => {
+                            drop(queue);
+                            match channel.object {
+                                Some(o) => block(&exec, me, Resource::Object(o)),
+                                None => std_thread::yield_now(),
+                            }
+                            continue;
+                        }
+                    };
+                    drop(queue);
+                    let name = object_name(&exec, channel.object);
+                    let what = match received {
+                        Ok(_) => "receive from",
+                        Err(_) => "find no senders left on",
+                    };
+                    record(&exec, me, format!("{} {}", what, name));
+                    return received;
+                }
+            }
+
+            pub fn iter(&self) -> Iter<'_, T> {
+                Iter { receiver: self }
+            }
+        }
+
+        impl<T> Drop for Receiver<T> {
+            fn drop(&mut self) {
+                self.channel.queue().receiver = false;
+            }
+        }
+
+        pub struct Iter<'a, T> {
+            receiver: &'a Receiver<T>,
+        }
+
+        impl<T> Iterator for Iter<'_, T> {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                self.receiver.recv().ok()
+            }
+        }
+
+        pub struct IntoIter<T> {
+            receiver: Receiver<T>,
+        }
+
+        impl<T> Iterator for IntoIter<T> {
+            type Item = T;
+
+            fn next(&mut self) -> Option<T> {
+                self.receiver.recv().ok()
+            }
+        }
+
+        impl<T> IntoIterator for Receiver<T> {
+            type Item = T;
+            type IntoIter = IntoIter<T>;
+
+            fn into_iter(self) -> IntoIter<T> {
+                IntoIter { receiver: self }
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+// Checkpoints for stack/heap diagrams in the ownership lessons.
+//
+// At an interesting point a lesson describes what is on the stack right now:
+//
+//     memory::Checkpoint::new("after `let s2 = s1;`")
+//         .moved("s1")
+//         .string("s2", &s2)
+//         .emit();
+//
+// The real addresses, lengths and capacities are captured at runtime. When the tutor runs the
+// lesson, `RUST_TUTOR_MEMORY` names a file the checkpoints are written to, and the tutor draws
+// them afterwards. Without it, `emit` does nothing.
+//
+// The file has one line per stack slot, each checkpoint starting with a `checkpoint` line:
+//
+//     checkpoint  <title>
+//     string      <name> <address> <ptr> <len> <capacity> <contents>
+//     str         <name> <address> <ptr> <len> <contents>
+//     reference   <name> <address> <target address>
+//     value       <name> <address> <value>
+//     moved       <name>
+//
+// Fields are separated by tabs.
+
+use std::env;
+use std::fmt::Debug;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+pub const ENV: &str = "RUST_TUTOR_MEMORY";
+
+pub struct Checkpoint {
+    lines: Vec<String>,
+}
+
+impl Checkpoint {
+    pub fn new(title: &str) -> Checkpoint {
+        Checkpoint {
+            lines: vec![format!("checkpoint\t{}", escape(title))],
+        }
+    }
+
+    // A `String`: the ptr/len/capacity triple on the stack, the bytes on the heap.
+    pub fn string(mut self, name: &str, s: &String) -> Checkpoint {
+        self.lines.push(format!(
+            "string\t{}\t{}\t{}\t{}\t{}\t{}",
+            escape(name),
+            address(s),
+            s.as_ptr() as usize,
+            s.len(),
+            s.capacity(),
+            escape(s)
+        ));
+        self
+    }
+
+    // A string slice such as a literal: a ptr/len pair pointing at bytes that live elsewhere.
+    pub fn str(mut self, name: &str, s: &&str) -> Checkpoint {
+        self.lines.push(format!(
+            "str\t{}\t{}\t{}\t{}\t{}",
+            escape(name),
+            address(s),
+            s.as_ptr() as usize,
+            s.len(),
+            escape(s)
+        ));
+        self
+    }
+
+    // A reference to another stack slot in this checkpoint, like `&s1`.
+    pub fn reference<T>(mut self, name: &str, r: &&T) -> Checkpoint {
+        self.lines.push(format!(
+            "reference\t{}\t{}\t{}",
+            escape(name),
+            address(r),
+            address(*r)
+        ));
+        self
+    }
+
+    // A plain value that lives entirely on the stack, like an `i32`.
+    pub fn value<T: Debug>(mut self, name: &str, value: &T) -> Checkpoint {
+        self.lines.push(format!(
+            "value\t{}\t{}\t{}",
+            escape(name),
+            address(value),
+            escape(&format!("{:?}", value))
+        ));
+        self
+    }
+
+    // A variable whose value has been moved out; it is still in scope but can't be used.
+    pub fn moved(mut self, name: &str) -> Checkpoint {
+        self.lines.push(format!("moved\t{}", escape(name)));
+        self
+    }
+
+    pub fn emit(self) {
+        let path = match env::var_os(ENV) {
+            Some(path) => path,
+            None => return,
+        };
+        if let Ok(mut file) = OpenOptions::new().create(true).append(true).open(path) {
+            let _ = writeln!(file, "{}", self.lines.join("\n"));
+        }
+    }
+}
+
+fn address<T: ?Sized>(value: &T) -> usize {
+    value as *const T as *const u8 as usize
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
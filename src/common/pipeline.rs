@@ -0,0 +1,157 @@
+// Watching values flow through an iterator pipeline, for the closures & iterators chapter and
+// `rust_tutor pipeline`.
+//
+// Each stage of a chain is wrapped so that it prints every item it hands on, in its own column:
+//
+//     pipeline::header(&["source", "filter", "map", "take"]);
+//     let squares: Vec<i32> = pipeline::source(1..10)
+//         .filter(|x| x % 2 == 0).trace(1)
+//         .map(|x| x * x).trace(2)
+//         .take(2).trace(3)
+//         .collect();
+//     pipeline::finish();
+//
+// prints
+//
+//     source        filter        map           take
+//     1
+//     2          -> 2          -> 4          -> 4
+//     3
+//     4          -> 4          -> 16         -> 16
+//
+// Every row is one item pulled from the source; it stops in the column of the stage that
+// dropped it. Nothing is printed before `collect` asks for the first item, and the source is
+// never asked for a fifth: iterators are lazy, and only do the work that is asked of them.
+
+use std::cell::Cell;
+use std::fmt::Debug;
+
+// The width of a column. Values are cut short to leave room for the `-> ` of the next one.
+const WIDTH: usize = 14;
+
+thread_local! {
+    // How far along the current row we have printed.
+    static COLUMN: Cell<usize> = const { Cell::new(0) };
+    // How many items each stage has handed on, by stage.
+    static COUNTS: Cell<[usize; 16]> = const { Cell::new([0; 16]) };
+}
+
+// Prints the column headings, one per stage. Stage 0 is the source.
+pub fn header(stages: &[&str]) {
+    let mut line = String::new();
+    for stage in stages {
+        let stage = if stage.chars().count() > WIDTH - 2 {
+            format!("{}..", stage.chars().take(WIDTH - 4).collect::<String>())
+        } else {
+            stage.to_string()
+        };
+        line.push_str(&format!("{:<width$}", stage, width = WIDTH));
+    }
+    println!("{}", line.trim_end());
+}
+
+fn shown(value: &impl Debug) -> String {
+    let shown = format!("{:?}", value);
+    if shown.chars().count() > WIDTH - 4 {
+        let cut: String = shown.chars().take(WIDTH - 6).collect();
+        format!("{}..", cut)
+    } else {
+        shown
+    }
+}
+
+fn print_at(stage: usize, value: &impl Debug) {
+    // Values line up under their heading, with the arrow in front of it.
+    let text = if stage == 0 {
+        shown(value)
+    } else {
+        format!("-> {}", shown(value))
+    };
+    let start = if stage == 0 { 0 } else { stage * WIDTH - 3 };
+
+    // A new item from the source, or a stage handing on a second item for the same input
+    // (like `flat_map`), starts a new row.
+    let mut column = COLUMN.get();
+    if stage == 0 || column > start {
+        if column > 0 {
+            println!();
+        }
+        column = 0;
+    }
+    print!("{}{}", " ".repeat(start - column), text);
+    COLUMN.set(start + text.chars().count());
+
+    let mut counts = COUNTS.get();
+    if let Some(count) = counts.get_mut(stage) {
+        *count += 1;
+    }
+    COUNTS.set(counts);
+}
+
+pub struct Traced<I> {
+    inner: I,
+    stage: usize,
+}
+
+impl<I> Iterator for Traced<I>
+where
+    I: Iterator,
+    I::Item: Debug,
+{
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.inner.next();
+        if let Some(item) = &item {
+            print_at(self.stage, item);
+        }
+        item
+    }
+}
+
+impl<I> DoubleEndedIterator for Traced<I>
+where
+    I: DoubleEndedIterator,
+    I::Item: Debug,
+{
+    fn next_back(&mut self) -> Option<I::Item> {
+        let item = self.inner.next_back();
+        if let Some(item) = &item {
+            print_at(self.stage, item);
+        }
+        item
+    }
+}
+
+// The first stage: anything that can be iterated over.
+pub fn source<I: IntoIterator>(items: I) -> Traced<I::IntoIter> {
+    Traced {
+        inner: items.into_iter(),
+        stage: 0,
+    }
+}
+
+pub trait Trace: Iterator + Sized {
+    // Prints what this stage hands on, in column `stage`.
+    fn trace(self, stage: usize) -> Traced<Self> {
+        Traced { inner: self, stage }
+    }
+}
+
+impl<I: Iterator> Trace for I {}
+
+// Ends the last row, and says how many items each stage handed on.
+pub fn finish() {
+    if COLUMN.replace(0) > 0 {
+        println!();
+    }
+    let counts = COUNTS.replace([0; 16]);
+    let used = counts
+        .iter()
+        .rposition(|&n| n > 0)
+        .map_or(0, |last| last + 1);
+    let counts: Vec<String> = counts[..used].iter().map(|n| n.to_string()).collect();
+    if !counts.is_empty() {
+        println!("items handed on by each stage: {}", counts.join(", "));
+    }
+}
@@ -0,0 +1,296 @@
+// Draws graphs of `Rc`/`Weak` pointers, with their strong and weak counts.
+//
+// A node type describes its outgoing pointers by implementing `Inspect`. `walk` starts from a
+// few named variables, follows every pointer it can reach and records the counts. The result
+// can be printed as ASCII or as Graphviz DOT, with strong pointers drawn solid and weak pointers
+// dashed. Nodes caught in cycles of strong pointers are flagged: they can never be dropped.
+//
+// When `RUST_TUTOR_DOT` names a directory, `Graph::emit` also writes a `.dot` file there;
+// render it with `dot -Tsvg <file> > graph.svg`.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::rc::{Rc, Weak};
+
+pub const ENV: &str = "RUST_TUTOR_DOT";
+
+pub trait Inspect: Sized {
+    fn label(&self) -> String;
+    // The `Rc`s this node holds, each with a name for the edge, like `children[0]`.
+    fn strong(&self) -> Vec<(String, Rc<Self>)>;
+    // The `Weak`s this node holds.
+    fn weak(&self) -> Vec<(String, Weak<Self>)>;
+}
+
+pub struct Node {
+    pub label: String,
+    pub strong_count: usize,
+    pub weak_count: usize,
+}
+
+pub struct Edge {
+    pub from: usize,
+    // `None` when a weak pointer's target has already been dropped.
+    pub to: Option<usize>,
+    pub name: String,
+    pub strong: bool,
+}
+
+pub struct Graph {
+    // Named variables holding an `Rc` to a node.
+    pub roots: Vec<(String, usize)>,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+pub fn walk<T: Inspect>(roots: &[(&str, &Rc<T>)]) -> Graph {
+    // Keep exactly one clone of every node we've seen, so the counts can be corrected for it.
+    let mut seen: Vec<Rc<T>> = Vec::new();
+    let mut stack: Vec<Rc<T>> = roots.iter().map(|(_, rc)| Rc::clone(rc)).collect();
+    while let Some(rc) = stack.pop() {
+        if seen.iter().any(|s| Rc::ptr_eq(s, &rc)) {
+            continue;
+        }
+        stack.extend(rc.strong().into_iter().map(|(_, child)| child));
+        stack.extend(rc.weak().iter().filter_map(|(_, weak)| weak.upgrade()));
+        seen.push(rc);
+    }
+    let index_of = |rc: &Rc<T>| seen.iter().position(|s| Rc::ptr_eq(s, rc));
+
+    let mut edges = Vec::new();
+    for (from, rc) in seen.iter().enumerate() {
+        for (name, child) in rc.strong() {
+            edges.push(Edge {
+                from,
+                to: index_of(&child),
+                name,
+                strong: true,
+            });
+        }
+        for (name, weak) in rc.weak() {
+            // An empty `Weak::new()` doesn't point anywhere, so there's no edge to draw.
+            if weak.ptr_eq(&Weak::new()) {
+                continue;
+            }
+            edges.push(Edge {
+                from,
+                to: weak.upgrade().and_then(|target| index_of(&target)),
+                name,
+                strong: false,
+            });
+        }
+    }
+
+    // All temporary clones are gone by now; only the ones in `seen` remain.
+    let nodes = seen
+        .iter()
+        .map(|rc| Node {
+            label: rc.label(),
+            strong_count: Rc::strong_count(rc) - 1,
+            weak_count: Rc::weak_count(rc),
+        })
+        .collect();
+    let roots = roots
+        .iter()
+        .filter_map(|(name, rc)| Some((name.to_string(), index_of(rc)?)))
+        .collect();
+
+    Graph {
+        roots,
+        nodes,
+        edges,
+    }
+}
+
+impl Graph {
+    // Every group of nodes that keep each other alive through strong pointers, none of which
+    // can ever be dropped: the strongly connected components of the strong edges that have more
+    // than one node, or one node pointing at itself. Each group is in index order.
+    pub fn leaks(&self) -> Vec<Vec<usize>> {
+        let count = self.nodes.len();
+        let mut search = Tarjan {
+            order: vec![None; count],
+            lowest: vec![0; count],
+            on_stack: vec![false; count],
+            stack: Vec::new(),
+            next: 0,
+            components: Vec::new(),
+        };
+        for node in 0..count {
+            if search.order[node].is_none() {
+                self.connect(node, &mut search);
+            }
+        }
+        let mut leaks: Vec<Vec<usize>> = search
+            .components
+            .into_iter()
+            .filter(|group| {
+                group.len() > 1 || self.strong_targets(group[0]).any(|to| to == group[0])
+            })
+            .map(|mut group| {
+                group.sort();
+                group
+            })
+            .collect();
+        leaks.sort();
+        leaks
+    }
+
+    fn strong_targets(&self, node: usize) -> impl Iterator<Item = usize> + '_ {
+        self.edges
+            .iter()
+            .filter(move |e| e.strong && e.from == node)
+            .filter_map(|e| e.to)
+    }
+
+    // Tarjan's algorithm: `lowest` is the earliest node still on the stack that `node` can
+    // reach, and `node` starts a component when that is `node` itself.
+    fn connect(&self, node: usize, search: &mut Tarjan) {
+        search.order[node] = Some(search.next);
+        search.lowest[node] = search.next;
+        search.next += 1;
+        search.stack.push(node);
+        search.on_stack[node] = true;
+        for to in self.strong_targets(node) {
+            match search.order[to] {
+                None => {
+                    self.connect(to, search);
+                    search.lowest[node] = search.lowest[node].min(search.lowest[to]);
+                }
+                Some(order) if search.on_stack[to] => {
+                    search.lowest[node] = search.lowest[node].min(order);
+                }
+                Some(_) => {}
+            }
+        }
+        if Some(search.lowest[node]) == search.order[node] {
+            let mut component = Vec::new();
+            while let Some(member) = search.stack.pop() {
+                search.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            search.components.push(component);
+        }
+    }
+
+    fn describe(&self, node: usize) -> String {
+        let node = &self.nodes[node];
+        format!(
+            "[{}] strong = {}, weak = {}",
+            node.label, node.strong_count, node.weak_count
+        )
+    }
+
+    pub fn to_ascii(&self) -> String {
+        let mut out = String::new();
+        for (name, node) in &self.roots {
+            out.push_str(&format!("{} ==> {}\n", name, self.describe(*node)));
+        }
+        for index in 0..self.nodes.len() {
+            out.push_str(&format!("{}\n", self.describe(index)));
+            for edge in self.edges.iter().filter(|e| e.from == index) {
+                let arrow = if edge.strong { "==>" } else { "- ->" };
+                let target = match edge.to {
+                    Some(to) => format!("[{}]", self.nodes[to].label),
+                    None => String::from("(dropped)"),
+                };
+                out.push_str(&format!("    {} {} {}\n", edge.name, arrow, target));
+            }
+        }
+        for group in self.leaks() {
+            let labels: Vec<&str> = group
+                .iter()
+                .map(|&n| self.nodes[n].label.as_str())
+                .collect();
+            let line = match &labels[..] {
+                [one] => format!("{} holds itself", one),
+                [rest @ .., last] => format!("{} and {} hold each other", rest.join(", "), last),
+                [] => continue,
+            };
+            out.push_str(&format!(
+                "LEAK: {} through strong pointers, and can never be dropped\n",
+                line
+            ));
+        }
+        out.push_str("(==> is a strong Rc, - -> is a Weak)\n");
+        out
+    }
+
+    pub fn to_dot(&self) -> String {
+        let leaking: Vec<usize> = self.leaks().into_iter().flatten().collect();
+        let mut out = String::from("digraph rc {\n    node [shape=box];\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let color = if leaking.contains(&index) {
+                ", color=red"
+            } else {
+                ""
+            };
+            out.push_str(&format!(
+                "    n{} [label=\"{}\\nstrong = {}, weak = {}\"{}];\n",
+                index,
+                escape(&node.label),
+                node.strong_count,
+                node.weak_count,
+                color
+            ));
+        }
+        for (i, (name, node)) in self.roots.iter().enumerate() {
+            out.push_str(&format!(
+                "    root{} [label=\"{}\", shape=plaintext];\n    root{} -> n{};\n",
+                i,
+                escape(name),
+                i,
+                node
+            ));
+        }
+        for (i, edge) in self.edges.iter().enumerate() {
+            let style = if edge.strong { "solid" } else { "dashed" };
+            let target = match edge.to {
+                Some(to) => format!("n{}", to),
+                None => {
+                    out.push_str(&format!(
+                        "    dropped{} [label=\"(dropped)\", shape=plaintext];\n",
+                        i
+                    ));
+                    format!("dropped{}", i)
+                }
+            };
+            out.push_str(&format!(
+                "    n{} -> {} [label=\"{}\", style={}];\n",
+                edge.from,
+                target,
+                escape(&edge.name),
+                style
+            ));
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    // Prints the ASCII version and, if the tutor asked for it, writes `<name>.dot`.
+    pub fn emit(&self, name: &str) {
+        println!("{}", self.to_ascii());
+        if let Some(dir) = env::var_os(ENV) {
+            let _ = fs::create_dir_all(&dir);
+            let _ = fs::write(Path::new(&dir).join(format!("{}.dot", name)), self.to_dot());
+        }
+    }
+}
+
+struct Tarjan {
+    // When each node was first reached, if it has been.
+    order: Vec<Option<usize>>,
+    lowest: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
@@ -0,0 +1,199 @@
+// Error reports: an error, what the program was doing when it happened, and where.
+//
+// A `Report` holds any error. `?` turns errors into reports, and `context` wraps one in a
+// message saying what was being attempted, keeping the original as its `source`:
+//
+//     fn load(path: &str) -> Result<Config, Report> {
+//         let text = fs::read_to_string(path).context(format!("reading {}", path))?;
+//         parse(&text).context("parsing the config")
+//     }
+//
+// Printed with `{}`, a report shows the outermost message; with `{:#}`, the whole chain on one
+// line; with `{:?}`, which is what `main` uses when it returns an error, one cause per line:
+//
+//     parsing the config
+//
+//     Caused by:
+//         0: line 3: `port` is not a number
+//         1: invalid digit found in string
+//
+// followed by a backtrace of where the report was made, when `RUST_BACKTRACE=1` is set.
+// The tutor reports its own errors this way; `09_advanced/D_error_reports.rs` shows it in use.
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::error::Error;
+use std::fmt::{self, Debug, Display};
+
+pub struct Report {
+    // `Send + Sync`, so a report can be handed to another thread.
+    error: Box<dyn Error + Send + Sync + 'static>,
+    backtrace: Backtrace,
+}
+
+impl Report {
+    pub fn new<E: Error + Send + Sync + 'static>(error: E) -> Report {
+        Report {
+            error: Box::new(error),
+            // Only records anything when `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` asks for it,
+            // as capturing one is slow.
+            backtrace: Backtrace::capture(),
+        }
+    }
+
+    // A report that is just a message, for errors that don't come from another error.
+    pub fn msg(message: impl Display) -> Report {
+        Report::new(Message(message.to_string()))
+    }
+
+    // Wraps the error in `context`. The backtrace still points at where the error was first
+    // reported, which is the more useful place.
+    pub fn context(self, context: impl Display) -> Report {
+        Report {
+            error: Box::new(WithContext {
+                context: context.to_string(),
+                source: self.error,
+            }),
+            backtrace: self.backtrace,
+        }
+    }
+
+    // The error and its sources, outermost first.
+    pub fn chain(&self) -> Chain<'_> {
+        Chain {
+            next: Some(&*self.error),
+        }
+    }
+
+    // The error at the bottom of the chain, which everything else was caused by.
+    pub fn root_cause(&self) -> &(dyn Error + 'static) {
+        self.chain().last().unwrap_or(&*self.error)
+    }
+
+    // The first error in the chain of type `E`, if any.
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.chain().find_map(|error| error.downcast_ref::<E>())
+    }
+
+    pub fn backtrace(&self) -> &Backtrace {
+        &self.backtrace
+    }
+}
+
+// `?` on any error converts it into a report. `Report` itself doesn't implement `Error`: if it
+// did, this impl would overlap with the standard `impl<T> From<T> for T`.
+impl<E: Error + Send + Sync + 'static> From<E> for Report {
+    fn from(error: E) -> Report {
+        Report::new(error)
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        if f.alternate() {
+            for cause in self.chain().skip(1) {
+                write!(f, ": {}", cause)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Debug for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.error)?;
+        let causes: Vec<&(dyn Error + 'static)> = self.chain().skip(1).collect();
+        if !causes.is_empty() {
+            write!(f, "\n\nCaused by:")?;
+            for (n, cause) in causes.iter().enumerate() {
+                write!(f, "\n    {}: {}", n, cause)?;
+            }
+        }
+        if self.backtrace.status() == BacktraceStatus::Captured {
+            write!(f, "\n\nStack backtrace:\n{}", self.backtrace)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Chain<'a> {
+    next: Option<&'a (dyn Error + 'static)>,
+}
+
+impl<'a> Iterator for Chain<'a> {
+    type Item = &'a (dyn Error + 'static);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let error = self.next?;
+        self.next = error.source();
+        Some(error)
+    }
+}
+
+#[derive(Debug)]
+struct Message(String);
+
+impl Display for Message {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Error for Message {}
+
+// An error together with what was being done when it happened.
+#[derive(Debug)]
+struct WithContext {
+    context: String,
+    source: Box<dyn Error + Send + Sync + 'static>,
+}
+
+impl Display for WithContext {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.context)
+    }
+}
+
+impl Error for WithContext {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.source)
+    }
+}
+
+// `.context(...)` on results and options, turning the error, or the `None`, into a report.
+pub trait Context<T> {
+    fn context(self, context: impl Display) -> Result<T, Report>;
+
+    // Like `context`, but only builds the message when there is an error.
+    fn with_context<D: Display>(self, context: impl FnOnce() -> D) -> Result<T, Report>;
+}
+
+impl<T, E: Error + Send + Sync + 'static> Context<T> for Result<T, E> {
+    fn context(self, context: impl Display) -> Result<T, Report> {
+        self.map_err(|error| Report::new(error).context(context))
+    }
+
+    fn with_context<D: Display>(self, context: impl FnOnce() -> D) -> Result<T, Report> {
+        self.map_err(|error| Report::new(error).context(context()))
+    }
+}
+
+impl<T> Context<T> for Result<T, Report> {
+    fn context(self, context: impl Display) -> Result<T, Report> {
+        self.map_err(|report| report.context(context))
+    }
+
+    fn with_context<D: Display>(self, context: impl FnOnce() -> D) -> Result<T, Report> {
+        self.map_err(|report| report.context(context()))
+    }
+}
+
+impl<T> Context<T> for Option<T> {
+    fn context(self, context: impl Display) -> Result<T, Report> {
+        self.ok_or_else(|| Report::msg(context))
+    }
+
+    fn with_context<D: Display>(self, context: impl FnOnce() -> D) -> Result<T, Report> {
+        self.ok_or_else(|| Report::msg(context()))
+    }
+}
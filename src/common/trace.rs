@@ -0,0 +1,116 @@
+// Structured trace events for concurrency lessons.
+//
+// A lesson calls these functions at interesting points: a thread starting, a value sent on a
+// channel, a mutex being locked. When the tutor runs the lesson it points `RUST_TUTOR_TRACE` at
+// a file, collects the events afterwards and draws them as a timeline with one lane per thread.
+// Without the environment variable the functions only print what they would print anyway.
+//
+// Each event is one line in the file: `seq<TAB>nanos<TAB>lane<TAB>kind<TAB>detail`, where `seq`
+// gives the order the events happened in and `lane` names the thread.
+
+use std::cell::RefCell;
+use std::env;
+use std::fmt::Debug;
+use std::fs::File;
+use std::io::Write;
+use std::sync::{Mutex, OnceLock};
+use std::thread;
+use std::time::Instant;
+
+pub const ENV: &str = "RUST_TUTOR_TRACE";
+
+struct Tracer {
+    file: Option<File>,
+    next_seq: u64,
+    started: Instant,
+}
+
+fn tracer() -> &'static Mutex<Tracer> {
+    static TRACER: OnceLock<Mutex<Tracer>> = OnceLock::new();
+    TRACER.get_or_init(|| {
+        let file = env::var_os(ENV).and_then(|path| File::create(path).ok());
+        Mutex::new(Tracer {
+            file,
+            next_seq: 0,
+            started: Instant::now(),
+        })
+    })
+}
+
+thread_local! {
+    static LANE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// The lane this thread's events go into: the name given to `spawned`, the thread's own name,
+// or its id as a last resort.
+fn lane() -> String {
+    LANE.with(|lane| {
+        lane.borrow().clone().unwrap_or_else(|| {
+            let current = thread::current();
+            match current.name() {
+                Some(name) => name.to_string(),
+                None => format!("{:?}", current.id()),
+            }
+        })
+    })
+}
+
+fn record(kind: &str, detail: &str) {
+    let lane = lane();
+    // A panicking thread should not stop the others from tracing.
+    let mut tracer = tracer().lock().unwrap_or_else(|e| e.into_inner());
+    let seq = tracer.next_seq;
+    tracer.next_seq += 1;
+    let nanos = tracer.started.elapsed().as_nanos();
+    if let Some(file) = tracer.file.as_mut() {
+        let _ = writeln!(
+            file,
+            "{}\t{}\t{}\t{}\t{}",
+            seq,
+            nanos,
+            escape(&lane),
+            kind,
+            escape(detail)
+        );
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace('\t', "\\t")
+        .replace('\n', "\\n")
+}
+
+// Call first thing in a newly spawned thread to give its lane a name.
+pub fn spawned(name: &str) {
+    LANE.with(|lane| *lane.borrow_mut() = Some(name.to_string()));
+    record("spawned", name);
+}
+
+// Call after `join` returns, with the name the joined thread gave itself.
+pub fn joined(name: &str) {
+    record("joined", name);
+}
+
+// Call right before `send`, since sending moves the value.
+pub fn sent<T: Debug>(channel: &str, value: &T) {
+    record("sent", &format!("{} {:?}", channel, value));
+}
+
+pub fn received<T: Debug>(channel: &str, value: &T) {
+    record("received", &format!("{} {:?}", channel, value));
+}
+
+pub fn locked(mutex: &str) {
+    record("locked", mutex);
+}
+
+pub fn unlocked(mutex: &str) {
+    record("unlocked", mutex);
+}
+
+// Prints a line like `println!` does, and also puts it on the timeline.
+pub fn say(text: &str) {
+    println!("{}", text);
+    record("say", text);
+}
@@ -0,0 +1,259 @@
+// A `Mutex` that watches the order threads take locks in, to find deadlocks that didn't happen.
+//
+// Two threads that both need locks A and B deadlock if one takes A then B while the other takes
+// B then A, but only when each gets its first lock before the other gets its second. Most runs
+// miss that moment, so the bug hides. `TrackedMutex` looks at the orders instead: whenever a
+// thread locks one while holding others, it adds "held, then taken" edges to a lock-order graph
+// shared by the whole program. A circle in that graph means there is a timing in which every
+// thread on it waits for the next, even if this run got lucky.
+//
+// Each circle is reported once, on stderr, with the threads and the lines (from
+// `#[track_caller]`) where they took each lock. `potential_deadlocks` returns the reports, for
+// tests. Otherwise it behaves like `std::sync::Mutex`, except that locking a mutex the thread
+// already holds panics rather than hanging: that one can only ever deadlock.
+
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::fmt;
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{LockResult, Mutex, MutexGuard, OnceLock, PoisonError};
+use std::thread;
+
+pub struct TrackedMutex<T> {
+    id: usize,
+    inner: Mutex<T>,
+}
+
+pub struct TrackedMutexGuard<'a, T> {
+    id: usize,
+    guard: MutexGuard<'a, T>,
+}
+
+// The first time some thread took `to` while holding `from`.
+struct Edge {
+    thread: String,
+    held_at: &'static Location<'static>,
+    taken_at: &'static Location<'static>,
+}
+
+#[derive(Default)]
+struct Graph {
+    names: BTreeMap<usize, String>,
+    edges: BTreeMap<(usize, usize), Edge>,
+    // Each circle as its sorted edges, so the same one found from another edge isn't repeated.
+    reported: BTreeSet<Vec<(usize, usize)>>,
+    reports: Vec<String>,
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(1);
+
+thread_local! {
+    // The mutexes this thread holds, in the order it locked them, and where.
+    static HELD: RefCell<Vec<(usize, &'static Location<'static>)>> = const { RefCell::new(Vec::new()) };
+}
+
+fn graph() -> MutexGuard<'static, Graph> {
+    static GRAPH: OnceLock<Mutex<Graph>> = OnceLock::new();
+    GRAPH
+        .get_or_init(Mutex::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+// `bank.rs:12`: the file name is enough to find the line in a lesson or an exercise.
+fn place(location: &Location) -> String {
+    let file = Path::new(location.file())
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    format!("{}:{}", file, location.line())
+}
+
+fn thread_name() -> String {
+    let current = thread::current();
+    match current.name() {
+        Some(name) => name.to_string(),
+        None => format!("{:?}", current.id()),
+    }
+}
+
+// The reports of every potential deadlock found so far, in the order they were found.
+pub fn potential_deadlocks() -> Vec<String> {
+    graph().reports.clone()
+}
+
+impl<T> TrackedMutex<T> {
+    // Named after the line that creates it, like `mutex from bank.rs:12`.
+    #[track_caller]
+    pub fn new(value: T) -> TrackedMutex<T> {
+        let name = format!("mutex from {}", place(Location::caller()));
+        TrackedMutex::named(&name, value)
+    }
+
+    // For mutexes created on the same line, such as one per account in a loop.
+    pub fn named(name: &str, value: T) -> TrackedMutex<T> {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        graph().names.insert(id, name.to_string());
+        TrackedMutex {
+            id,
+            inner: Mutex::new(value),
+        }
+    }
+
+    #[track_caller]
+    pub fn lock(&self) -> LockResult<TrackedMutexGuard<'_, T>> {
+        let taken_at = Location::caller();
+        // Before waiting, so the report comes out even if this lock never returns.
+        self.record_order(taken_at);
+        let result = self.inner.lock();
+        HELD.with(|held| held.borrow_mut().push((self.id, taken_at)));
+        match result {
+            Ok(guard) => Ok(TrackedMutexGuard { id: self.id, guard }),
+            Err(poisoned) => Err(PoisonError::new(TrackedMutexGuard {
+                id: self.id,
+                guard: poisoned.into_inner(),
+            })),
+        }
+    }
+
+    pub fn get_mut(&mut self) -> LockResult<&mut T> {
+        self.inner.get_mut()
+    }
+
+    pub fn into_inner(self) -> LockResult<T> {
+        self.inner.into_inner()
+    }
+
+    fn record_order(&self, taken_at: &'static Location<'static>) {
+        let held = HELD.with(|held| held.borrow().clone());
+        if held.is_empty() {
+            return;
+        }
+        let thread = thread_name();
+        let mut graph = graph();
+        for (id, held_at) in held {
+            if id == self.id {
+                let name = graph.names[&id].clone();
+                drop(graph);
+                panic!(
+                    "deadlock: `{}` locks `{}` at {} while already holding it since {}",
+                    thread,
+                    name,
+                    place(taken_at),
+                    place(held_at)
+                );
+            }
+            if graph.edges.contains_key(&(id, self.id)) {
+                continue;
+            }
+            graph.edges.insert(
+                (id, self.id),
+                Edge {
+                    thread: thread.clone(),
+                    held_at,
+                    taken_at,
+                },
+            );
+            // The new edge closes a circle if there was already a way back from here.
+            if let Some(path) = graph.path(self.id, id) {
+                let mut circle = vec![(id, self.id)];
+                circle.extend(path.windows(2).map(|pair| (pair[0], pair[1])));
+                graph.report(circle);
+            }
+        }
+    }
+}
+
+impl<T: Default> Default for TrackedMutex<T> {
+    #[track_caller]
+    fn default() -> TrackedMutex<T> {
+        TrackedMutex::new(T::default())
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for TrackedMutex<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = graph().names[&self.id].clone();
+        f.debug_struct("TrackedMutex")
+            .field("name", &name)
+            .field("inner", &self.inner)
+            .finish()
+    }
+}
+
+impl Graph {
+    // The mutexes on a way from `from` to `to` through the edges, both ends included.
+    fn path(&self, from: usize, to: usize) -> Option<Vec<usize>> {
+        let mut came_from = BTreeMap::new();
+        let mut queue = VecDeque::from([from]);
+        while let Some(node) = queue.pop_front() {
+            if node == to {
+                let mut path = vec![to];
+                while let Some(&previous) = came_from.get(path.last().unwrap()) {
+                    path.push(previous);
+                }
+                path.reverse();
+                return Some(path);
+            }
+            for &(a, b) in self.edges.keys() {
+                if a == node && b != from && !came_from.contains_key(&b) {
+                    came_from.insert(b, node);
+                    queue.push_back(b);
+                }
+            }
+        }
+        None
+    }
+
+    fn report(&mut self, circle: Vec<(usize, usize)>) {
+        let mut key = circle.clone();
+        key.sort();
+        if !self.reported.insert(key) {
+            return;
+        }
+        let mut report =
+            String::from("potential deadlock: these threads take the same locks in a circle");
+        for (from, to) in circle {
+            let edge = &self.edges[&(from, to)];
+            report.push_str(&format!(
+                "\n  `{}`: {} locks `{}`, then {} locks `{}`",
+                edge.thread,
+                place(edge.held_at),
+                self.names[&from],
+                place(edge.taken_at),
+                self.names[&to]
+            ));
+        }
+        eprintln!("{}", report);
+        self.reports.push(report);
+    }
+}
+
+impl<T> Deref for TrackedMutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.guard
+    }
+}
+
+impl<T> DerefMut for TrackedMutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.guard
+    }
+}
+
+impl<T> Drop for TrackedMutexGuard<'_, T> {
+    fn drop(&mut self) {
+        // Guards can be dropped in any order, so remove this one wherever it is.
+        let _ = HELD.try_with(|held| {
+            let mut held = held.borrow_mut();
+            if let Some(index) = held.iter().rposition(|&(id, _)| id == self.id) {
+                held.remove(index);
+            }
+        });
+    }
+}
@@ -0,0 +1,466 @@
+// `rust_tutor compare <name>`: the learner's solution next to the reference solution.
+//
+// Both files go through rustfmt first, so that the diff shows differences in the code rather
+// than in formatting. The comment block at the top of each file is left out: the learner's copy
+// still has the instructions there, where the reference only has the title. Lines are matched up with a longest common subsequence, and within a
+// changed line the same is done for its tokens, so the parts that actually differ stand out.
+//
+// The reference solution is only shown once the exercise or challenge is solved, or all of its
+// hints have been taken: before that, it would give the answer away.
+
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+use crate::challenges;
+use crate::exercises;
+use crate::progress::Store;
+use crate::{Context, Report};
+
+// Lines of unchanged code shown around each change in a unified diff.
+const CONTEXT: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum View {
+    Unified,
+    SideBySide { width: usize },
+}
+
+// One step of turning one sequence into another.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Op {
+    Same(usize, usize),
+    Removed(usize),
+    Added(usize),
+}
+
+// The shortest edit from `old` to `new`, through their longest common subsequence.
+pub fn lcs<T: PartialEq>(old: &[T], new: &[T]) -> Vec<Op> {
+    // lengths[i][j] is the length of the LCS of old[i..] and new[j..].
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i] == new[j] {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+    let (mut i, mut j) = (0, 0);
+    let mut ops = Vec::new();
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push(Op::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lengths[i + 1][j] >= lengths[i][j + 1]) {
+            ops.push(Op::Removed(i));
+            i += 1;
+        } else {
+            ops.push(Op::Added(j));
+            j += 1;
+        }
+    }
+    ops
+}
+
+// Formats `source` with rustfmt. If rustfmt isn't installed or can't parse the code, only
+// trailing whitespace is removed.
+pub fn normalize(source: &str) -> String {
+    let formatted = (|| -> io::Result<Option<String>> {
+        let mut child = Command::new(rustfmt())
+            .args(["--edition", "2021", "--emit", "stdout"])
+            .current_dir(env::temp_dir())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+        child
+            .stdin
+            .take()
+            .expect("stdin is piped")
+            .write_all(source.as_bytes())?;
+        let output = child.wait_with_output()?;
+        Ok(output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned()))
+    })();
+    match formatted {
+        Ok(Some(formatted)) => formatted,
+        _ => source
+            .lines()
+            .map(|l| format!("{}\n", l.trim_end()))
+            .collect(),
+    }
+}
+
+// `source` without its leading comment lines, and the blank lines among and after them.
+pub fn without_header(source: &str) -> &str {
+    let header: usize = source
+        .split_inclusive('\n')
+        .take_while(|line| line.trim().is_empty() || line.trim_start().starts_with("//"))
+        .map(str::len)
+        .sum();
+    &source[header..]
+}
+
+fn rustfmt() -> String {
+    env::var("RUSTFMT").unwrap_or_else(|_| String::from("rustfmt"))
+}
+
+// Splits a line into words, numbers, runs of spaces, and single punctuation characters.
+pub fn tokens(line: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut start = 0;
+    let class = |c: char| {
+        if c.is_alphanumeric() || c == '_' {
+            0
+        } else if c.is_whitespace() {
+            1
+        } else {
+            2
+        }
+    };
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let next = chars.peek().map(|&(_, n)| n);
+        if class(c) == 2 || next.is_none_or(|n| class(n) != class(c)) {
+            let end = i + c.len_utf8();
+            tokens.push(&line[start..end]);
+            start = end;
+        }
+    }
+    tokens
+}
+
+// A piece of a line, and whether it differs from the other side.
+pub type Segment = (String, bool);
+
+// The tokens of `old` and `new`, marked where they differ from each other.
+fn highlight(old: &str, new: &str) -> (Vec<Segment>, Vec<Segment>) {
+    let (a, b) = (tokens(old), tokens(new));
+    let (mut left, mut right): (Vec<Segment>, Vec<Segment>) = (Vec::new(), Vec::new());
+    let push = |side: &mut Vec<Segment>, text: &str, changed: bool| match side.last_mut() {
+        Some((last, c)) if *c == changed => last.push_str(text),
+        _ => side.push((text.to_string(), changed)),
+    };
+    for op in lcs(&a, &b) {
+        match op {
+            Op::Same(i, j) => {
+                push(&mut left, a[i], false);
+                push(&mut right, b[j], false);
+            }
+            Op::Removed(i) => push(&mut left, a[i], true),
+            Op::Added(j) => push(&mut right, b[j], true),
+        }
+    }
+    (left, right)
+}
+
+// A line of the diff. Changed lines that replace each other carry token highlights.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Row {
+    Same(String),
+    Removed(Vec<Segment>),
+    Added(Vec<Segment>),
+    Changed(Vec<Segment>, Vec<Segment>),
+}
+
+// Diffs two sources line by line, pairing up removed and added lines that replace each other.
+pub fn diff(old: &str, new: &str) -> Vec<Row> {
+    let (a, b): (Vec<&str>, Vec<&str>) = (old.lines().collect(), new.lines().collect());
+    let ops = lcs(&a, &b);
+    let mut rows = Vec::new();
+    let mut k = 0;
+    while k < ops.len() {
+        if let Op::Same(i, _) = ops[k] {
+            rows.push(Row::Same(a[i].to_string()));
+            k += 1;
+            continue;
+        }
+        // A run of changes: removed lines first, then added ones.
+        let mut removed = Vec::new();
+        let mut added = Vec::new();
+        while let Some(&op) = ops.get(k) {
+            match op {
+                Op::Removed(i) => removed.push(a[i]),
+                Op::Added(j) => added.push(b[j]),
+                Op::Same(..) => break,
+            }
+            k += 1;
+        }
+        for n in 0..removed.len().max(added.len()) {
+            rows.push(match (removed.get(n), added.get(n)) {
+                (Some(old), Some(new)) => {
+                    let (left, right) = highlight(old, new);
+                    Row::Changed(left, right)
+                }
+                (Some(old), None) => Row::Removed(vec![(old.to_string(), false)]),
+                (None, Some(new)) => Row::Added(vec![(new.to_string(), false)]),
+                (None, None) => unreachable!(),
+            });
+        }
+    }
+    rows
+}
+
+// Writes segments, marking the changed ones: reversed colors on a terminal, and otherwise
+// `[-removed-]` and `{+added+}`, like `git diff --word-diff`.
+fn paint(segments: &[Segment], added: bool, color: bool) -> String {
+    let mut out = String::new();
+    for (text, changed) in segments {
+        match (changed, color, added) {
+            (false, _, _) => out.push_str(text),
+            (true, true, _) => out.push_str(&format!("\x1b[7m{}\x1b[27m", text)),
+            (true, false, false) => out.push_str(&format!("[-{}-]", text)),
+            (true, false, true) => out.push_str(&format!("{{+{}+}}", text)),
+        }
+    }
+    out
+}
+
+fn colored(line: String, code: &str, color: bool) -> String {
+    if color {
+        format!("\x1b[{}m{}\x1b[0m", code, line)
+    } else {
+        line
+    }
+}
+
+// The rows as a unified diff, with `CONTEXT` lines around each change.
+pub fn unified(rows: &[Row], old_name: &str, new_name: &str, color: bool) -> String {
+    let mut out = format!("--- {}\n+++ {}\n", old_name, new_name);
+    let changed: Vec<usize> = (0..rows.len())
+        .filter(|&n| !matches!(rows[n], Row::Same(_)))
+        .collect();
+    let shown = |n: usize| {
+        changed
+            .iter()
+            .any(|&c| n + CONTEXT >= c && n <= c + CONTEXT)
+    };
+    // Line numbers in the old and new file where each row starts.
+    let (mut old_line, mut new_line) = (1, 1);
+    let mut n = 0;
+    while n < rows.len() {
+        if !shown(n) {
+            if let Row::Same(_) = rows[n] {
+                old_line += 1;
+                new_line += 1;
+            }
+            n += 1;
+            continue;
+        }
+        let start = n;
+        while n < rows.len() && shown(n) {
+            n += 1;
+        }
+        let hunk = &rows[start..n];
+        let count = |old: bool| {
+            hunk.iter()
+                .filter(|row| match row {
+                    Row::Same(_) | Row::Changed(..) => true,
+                    Row::Removed(_) => old,
+                    Row::Added(_) => !old,
+                })
+                .count()
+        };
+        let (old_count, new_count) = (count(true), count(false));
+        out.push_str(&colored(
+            format!(
+                "@@ -{},{} +{},{} @@",
+                old_line, old_count, new_line, new_count
+            ),
+            "36",
+            color,
+        ));
+        out.push('\n');
+        // Within a hunk, show all removals before the additions that replace them.
+        let mut pending: Vec<String> = Vec::new();
+        for row in hunk {
+            match row {
+                Row::Same(text) => {
+                    out.extend(pending.drain(..));
+                    out.push_str(&format!(" {}\n", text));
+                }
+                Row::Removed(old) => {
+                    out.push_str(&colored(
+                        format!("-{}", paint(old, false, color)),
+                        "31",
+                        color,
+                    ));
+                    out.push('\n');
+                }
+                Row::Added(new) => pending.push(format!(
+                    "{}\n",
+                    colored(format!("+{}", paint(new, true, color)), "32", color)
+                )),
+                Row::Changed(old, new) => {
+                    out.push_str(&colored(
+                        format!("-{}", paint(old, false, color)),
+                        "31",
+                        color,
+                    ));
+                    out.push('\n');
+                    pending.push(format!(
+                        "{}\n",
+                        colored(format!("+{}", paint(new, true, color)), "32", color)
+                    ));
+                }
+            }
+        }
+        out.extend(pending.drain(..));
+        old_line += old_count;
+        new_line += new_count;
+    }
+    out
+}
+
+// Cuts segments to `width` characters, and pads them to it.
+fn fit(segments: &[Segment], width: usize) -> Vec<Segment> {
+    let mut left = width;
+    let mut out = Vec::new();
+    for (text, changed) in segments {
+        let text: String = text.chars().take(left).collect();
+        left -= text.chars().count();
+        out.push((text, *changed));
+    }
+    out.push((" ".repeat(left), false));
+    out
+}
+
+// The rows in two columns, `width` characters wide in total, like `diff --side-by-side`.
+pub fn side_by_side(
+    rows: &[Row],
+    old_name: &str,
+    new_name: &str,
+    width: usize,
+    color: bool,
+) -> String {
+    let column = width.saturating_sub(3) / 2;
+    let plain = |text: &str| vec![(text.to_string(), false)];
+    let header = |name: &str| paint(&fit(&plain(name), column), false, false);
+    let mut out = format!("{} | {}\n", header(old_name), header(new_name).trim_end());
+    out.push_str(&format!("{}\n", "-".repeat(column * 2 + 3)));
+    for row in rows {
+        // Markers in the middle column: `|` changed, `<` only yours, `>` only the reference.
+        let (left, marker, right) = match row {
+            Row::Same(text) => (plain(text), ' ', plain(text)),
+            Row::Removed(old) => (old.clone(), '<', Vec::new()),
+            Row::Added(new) => (Vec::new(), '>', new.clone()),
+            Row::Changed(old, new) => (old.clone(), '|', new.clone()),
+        };
+        // Without color, the markers for changed tokens take up room too.
+        let (left, right) = if color {
+            (fit(&left, column), fit(&right, column))
+        } else {
+            (
+                fit(&plain(&paint(&left, false, false)), column),
+                fit(&plain(&paint(&right, true, false)), column),
+            )
+        };
+        let code = match marker {
+            '<' => "31",
+            '>' => "32",
+            '|' => "33",
+            _ => "0",
+        };
+        let line = format!(
+            "{} {} {}",
+            paint(&left, false, color),
+            colored(marker.to_string(), code, color && marker != ' '),
+            paint(&right, true, color)
+        );
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+// Whatever `rust_tutor compare` can compare: an exercise or a challenge.
+struct Solved {
+    id: String,
+    subject: String,
+    hints: usize,
+    yours: PathBuf,
+    reference: PathBuf,
+}
+
+fn find(store: &Store, name: &str) -> crate::Result<Solved> {
+    if let Ok(exercise) = exercises::find(name) {
+        return Ok(Solved {
+            id: exercise.id.clone(),
+            subject: exercise.subject(),
+            hints: exercise.hints()?.len(),
+            yours: exercise.workspace_file(store),
+            reference: exercise.solution_path(),
+        });
+    }
+    match challenges::find(name) {
+        Ok(challenge) => Ok(Solved {
+            id: challenge.id.clone(),
+            subject: challenge.subject(),
+            hints: challenge.hints()?.len(),
+            yours: challenge.workspace_file(store),
+            reference: challenge.solution_path(),
+        }),
+        Err(_) => Err(Report::msg(format!(
+            "no exercise or challenge called `{}`",
+            name
+        ))),
+    }
+}
+
+// Whether the reference solution may be shown: after solving, or after the last hint.
+pub fn unlocked(store: &Store, subject: &str, hints: usize) -> io::Result<bool> {
+    Ok(store.count("solved", subject)? > 0 || store.count("hint", subject)? >= hints)
+}
+
+fn label(path: &Path) -> String {
+    path.display().to_string()
+}
+
+// `rust_tutor compare <name> [--view unified|side-by-side] [--width <n>]`
+pub fn command(store: &Store, name: &str, view: View) -> crate::Result<()> {
+    let solved = find(store, name)?;
+    if !solved.yours.exists() {
+        return Err(Report::msg(format!(
+            "you haven't started {} yet",
+            solved.id
+        )));
+    }
+    if !unlocked(store, &solved.subject, solved.hints)? {
+        return Err(Report::msg(format!(
+            "the reference solution for {} is shown once you've solved it, \
+             or taken all {} of its hints",
+            solved.id, solved.hints
+        )));
+    }
+    let read = |path: &Path| {
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))
+    };
+    let yours = normalize(without_header(&read(&solved.yours)?));
+    let reference = normalize(without_header(&read(&solved.reference)?));
+    let rows = diff(&yours, &reference);
+    if rows.iter().all(|row| matches!(row, Row::Same(_))) {
+        println!("Your solution is the reference solution, apart from formatting.");
+        return Ok(());
+    }
+
+    let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+    let (old_name, new_name) = (
+        format!("yours: {}", label(&solved.yours)),
+        format!("reference: {}", solved.id),
+    );
+    match view {
+        View::Unified => print!("{}", unified(&rows, &old_name, &new_name, color)),
+        View::SideBySide { width } => print!(
+            "{}",
+            side_by_side(&rows, &old_name, &new_name, width, color)
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Checks that the "this would not compile" examples in the lessons really don't compile.
+//
+// An example is a block of commented-out lines directly below an annotation naming the error
+// the compiler should report:
+//
+//     // COMPILE_FAIL(E0382)
+//     // println!("s1 is {}, s2 is {}", s1, s2);
+//
+// Each block is uncommented on its own, with every other example left commented out, and the
+// lesson is type-checked. The check passes only if rustc reports that exact error code.
+//
+// In a lesson made of several files, examples can be in any of them. The lesson is copied with
+// the one file changed, and checked as a whole: with rustc for a module tree, with
+// `cargo check` for a workspace.
+
+use std::fs;
+use std::path::Path;
+
+use crate::compiler::{self, Compiled, ScratchDir};
+use crate::lessons::{self, Layout, Lesson};
+use crate::Report;
+
+const ANNOTATION: &str = "// COMPILE_FAIL(";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Example {
+    // 1-based line number of the annotation.
+    pub line: usize,
+    // The expected error code, e.g. `E0382`.
+    pub code: String,
+    // The lines of the block, still commented out.
+    pub block: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Verdict {
+    Failed,
+    // The example compiled, so the lesson's claim is out of date.
+    Compiled,
+    // The example failed, but with other errors; these are the codes rustc reported.
+    WrongError(Vec<String>),
+}
+
+pub fn examples(source: &str) -> Vec<Example> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut examples = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let Some(code) = line
+            .trim()
+            .strip_prefix(ANNOTATION)
+            .and_then(|rest| rest.strip_suffix(')'))
+        else {
+            continue;
+        };
+        let block = lines[i + 1..]
+            .iter()
+            .take_while(|line| line.trim_start().starts_with("//"))
+            .map(|line| line.to_string())
+            .collect();
+        examples.push(Example {
+            line: i + 1,
+            code: code.to_string(),
+            block,
+        });
+    }
+    examples
+}
+
+// The lesson's source with just this example uncommented.
+fn uncommented(source: &str, example: &Example, lesson_dir: &Path) -> String {
+    let mut out = String::new();
+    for (i, line) in source.lines().enumerate() {
+        let in_block = i >= example.line && i < example.line + example.block.len();
+        if in_block {
+            // Only the first `//` goes; a trailing comment on the line stays a comment.
+            let indent = line.len() - line.trim_start().len();
+            let code = &line.trim_start()[2..];
+            out.push_str(&line[..indent]);
+            out.push_str(code.strip_prefix(' ').unwrap_or(code));
+        } else if let Some(path) = line
+            .trim()
+            .strip_prefix("#[path = \"")
+            .and_then(|rest| rest.strip_suffix("\"]"))
+        {
+            // The copy is compiled from elsewhere, so shared modules need an absolute path.
+            out.push_str(&format!(
+                "#[path = {:?}]",
+                lesson_dir.join(path).to_string_lossy()
+            ));
+        } else {
+            out.push_str(line);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+// The distinct error codes in rustc's output, like `E0382` from `error[E0382]: ...`.
+fn error_codes(stderr: &str) -> Vec<String> {
+    let mut codes: Vec<String> = stderr
+        .lines()
+        .filter_map(|line| line.strip_prefix("error[")?.split_once(']'))
+        .map(|(code, _)| code.to_string())
+        .collect();
+    codes.sort();
+    codes.dedup();
+    codes
+}
+
+// Checks an example from `file`, one of the lesson's files.
+pub fn check(lesson: &Lesson, file: &Path, example: &Example) -> crate::Result<Verdict> {
+    let source = fs::read_to_string(file)?;
+    let file_dir = file.parent().unwrap_or(Path::new("."));
+    let scratch = ScratchDir::new("compile_fail")?;
+    let source = uncommented(&source, example, file_dir);
+    match lesson.layout() {
+        Layout::File => {
+            let copy = scratch.path().join(format!("{}.rs", lesson.name()));
+            fs::write(&copy, source)?;
+            expect_error(&copy, &example.code, lesson.is_test_only()?)
+        }
+        // The example may be in any file of the module tree, so the whole tree is copied.
+        Layout::ModuleTree => {
+            let copy = scratch.path().join(lesson.name());
+            compiler::copy_dir(&lesson.path, &copy)?;
+            fs::write(copy.join(file.strip_prefix(&lesson.path)?), source)?;
+            expect_error(&copy.join("main.rs"), &example.code, false)
+        }
+        Layout::Workspace => {
+            let copy = scratch.path().join(lesson.name());
+            compiler::copy_dir(&lesson.path, &copy)?;
+            fs::write(copy.join(file.strip_prefix(&lesson.path)?), source)?;
+            let checked = compiler::cargo_run(&copy, &scratch.path().join("target"), "check")?;
+            Ok(verdict(&checked, &example.code))
+        }
+    }
+}
+
+// Type-checks `file` and reports whether it fails with the error `code`.
+pub fn expect_error(file: &Path, code: &str, test_only: bool) -> crate::Result<Verdict> {
+    let scratch = ScratchDir::new("expect_error")?;
+    // Borrow checking happens before code generation, so there's no need to build a binary.
+    let mut args = vec!["--emit=metadata", "-A", "warnings"];
+    if test_only {
+        args.push("--test");
+    }
+    let compiled = compiler::compile(file, &scratch.path().join("out.rmeta"), &args)?;
+    Ok(verdict(&compiled, code))
+}
+
+fn verdict(compiled: &Compiled, code: &str) -> Verdict {
+    if compiled.success {
+        return Verdict::Compiled;
+    }
+    // Any other error means the example has gone stale, even if the one it shows is still there.
+    let codes = error_codes(&compiled.stderr);
+    if codes == [code] {
+        Verdict::Failed
+    } else {
+        Verdict::WrongError(codes)
+    }
+}
+
+// `rust_tutor compile-fail [lesson]`
+pub fn command(name: Option<&str>) -> crate::Result<()> {
+    let lessons = match name {
+        Some(name) => vec![lessons::find(name)?],
+        None => lessons::all()?,
+    };
+    let mut failures = 0;
+    for lesson in &lessons {
+        for file in lesson.files()? {
+            for example in examples(&fs::read_to_string(&file)?) {
+                let verdict = check(lesson, &file, &example)?;
+                let outcome = match &verdict {
+                    Verdict::Failed => String::from("ok"),
+                    Verdict::Compiled => String::from("FAILED: it compiles"),
+                    Verdict::WrongError(codes) if codes.is_empty() => {
+                        String::from("FAILED: no error code reported")
+                    }
+                    Verdict::WrongError(codes) => format!("FAILED: got {}", codes.join(", ")),
+                };
+                println!(
+                    "{}:{} {} ... {}",
+                    lesson.file_id(&file),
+                    example.line,
+                    example.code,
+                    outcome
+                );
+                if verdict != Verdict::Failed {
+                    failures += 1;
+                }
+            }
+        }
+    }
+    if failures > 0 {
+        return Err(Report::msg(format!(
+            "{} example(s) did not fail as annotated",
+            failures
+        )));
+    }
+    Ok(())
+}
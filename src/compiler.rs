@@ -0,0 +1,175 @@
+// Helpers for invoking `rustc` on lessons and on generated programs, and Cargo on the lessons
+// that are workspaces.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+// Lessons are written against the 2021 edition, same as the tutor itself.
+pub const EDITION: &str = "2021";
+
+// A temporary directory that is removed again when it goes out of scope.
+pub struct ScratchDir {
+    path: PathBuf,
+}
+
+impl ScratchDir {
+    pub fn new(prefix: &str) -> io::Result<ScratchDir> {
+        static NEXT: AtomicUsize = AtomicUsize::new(0);
+        let n = NEXT.fetch_add(1, Ordering::Relaxed);
+        let path = env::temp_dir().join(format!(
+            "rust_tutor-{}-{}-{}",
+            prefix,
+            std::process::id(),
+            n
+        ));
+        if path.exists() {
+            fs::remove_dir_all(&path)?;
+        }
+        fs::create_dir_all(&path)?;
+        Ok(ScratchDir { path })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for ScratchDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+// Copies the directory `from` to `to`, leaving out any Cargo build output.
+pub fn copy_dir(from: &Path, to: &Path) -> io::Result<()> {
+    fs::create_dir_all(to)?;
+    for entry in fs::read_dir(from)? {
+        let path = entry?.path();
+        let target = to.join(path.file_name().unwrap_or_default());
+        if path.is_dir() {
+            if !path.ends_with("target") {
+                copy_dir(&path, &target)?;
+            }
+        } else {
+            fs::copy(&path, &target)?;
+        }
+    }
+    Ok(())
+}
+
+// The result of a single `rustc` invocation.
+pub struct Compiled {
+    pub success: bool,
+    pub stderr: String,
+    pub binary: PathBuf,
+}
+
+// Compiles `source` into the executable `binary`. `extra_args` are passed to rustc as-is,
+// e.g. `--test` or `-A warnings`.
+pub fn compile(source: &Path, binary: &Path, extra_args: &[&str]) -> io::Result<Compiled> {
+    let output = Command::new(rustc())
+        .arg("--edition")
+        .arg(EDITION)
+        .args(extra_args)
+        .arg(source)
+        .arg("-o")
+        .arg(binary)
+        .output()?;
+
+    Ok(Compiled {
+        success: output.status.success(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        binary: binary.to_path_buf(),
+    })
+}
+
+// Respect `RUSTC` the same way Cargo does, so a pinned toolchain is used consistently.
+pub fn rustc() -> String {
+    env::var("RUSTC").unwrap_or_else(|_| String::from("rustc"))
+}
+
+// The toolchain in use, as `rustc -V` prints it.
+pub fn version() -> io::Result<String> {
+    let output = Command::new(rustc()).arg("-V").output()?;
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+// Respect `CARGO` too; Cargo sets it for the tests, and it points at the matching toolchain.
+pub fn cargo() -> String {
+    env::var("CARGO").unwrap_or_else(|_| String::from("cargo"))
+}
+
+// Runs `cargo <subcommand>` on the workspace in `dir`, with the build output kept in
+// `target_dir` so the lesson directory stays clean. Workspace lessons have no dependencies
+// beyond their own crates, so nothing is ever downloaded, and the committed `Cargo.lock` is
+// used as it is.
+pub fn cargo_run(dir: &Path, target_dir: &Path, subcommand: &str) -> io::Result<Compiled> {
+    let output = cargo_command(dir, target_dir, subcommand).output()?;
+
+    Ok(Compiled {
+        success: output.status.success(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        binary: PathBuf::new(),
+    })
+}
+
+fn cargo_command(dir: &Path, target_dir: &Path, subcommand: &str) -> Command {
+    let mut command = Command::new(cargo());
+    command
+        .arg(subcommand)
+        .args(["--offline", "--locked", "--quiet"])
+        .current_dir(dir)
+        .env("CARGO_TARGET_DIR", target_dir)
+        .env("RUSTFLAGS", "-A warnings");
+    command
+}
+
+// Builds the workspace in `dir` and copies its one executable to `binary`.
+pub fn cargo_build(dir: &Path, target_dir: &Path, binary: &Path) -> io::Result<Compiled> {
+    // Cargo names what it built in JSON messages on stdout; errors still go to stderr as text.
+    let output = cargo_command(dir, target_dir, "build")
+        .arg("--message-format=json-render-diagnostics")
+        .output()?;
+    let mut compiled = Compiled {
+        success: output.status.success(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        binary: PathBuf::new(),
+    };
+    if !compiled.success {
+        return Ok(compiled);
+    }
+    let built = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(executable)
+        .next_back()
+        .ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("{} did not build an executable", dir.display()),
+            )
+        })?;
+    fs::copy(&built, binary)?;
+    compiled.binary = binary.to_path_buf();
+    Ok(compiled)
+}
+
+// The `"executable"` path in one of Cargo's JSON messages. It is `null` for libraries, and
+// missing from messages about anything but a finished artifact.
+fn executable(message: &str) -> Option<PathBuf> {
+    const KEY: &str = "\"executable\":\"";
+    let start = message.find(KEY)? + KEY.len();
+    let mut path = String::new();
+    let mut chars = message[start..].chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => return Some(PathBuf::from(path)),
+            '\\' => path.push(chars.next()?),
+            c => path.push(c),
+        }
+    }
+    None
+}
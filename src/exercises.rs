@@ -0,0 +1,508 @@
+// Exercises: small programs for the learner to complete, graded by tests they can't see.
+//
+// Each exercise is a directory like `exercises/03_ownership/first_word/` holding
+//
+//     exercise.rs       the starting point; its first line is the title
+//     solution.rs       one way to solve it
+//     hidden_tests.rs   the tests used for grading, written as the body of a module inside
+//                       the learner's file (so they start with `use super::*;`)
+//     hints.txt         hints, one per line, revealed one at a time
+//     exhaustive.txt    optional: a variant to add to one of the exercise's enums, like
+//                       `Message: Resize { width: u32, height: u32 }`
+//     fixtures/         optional: files the hidden tests read, copied into the sandbox the
+//                       tests run in
+//     common.txt        optional: modules from `src/common` the exercise uses, one name per
+//                       line; they're put next to the learner's file, which declares them
+//                       with `mod tracked_mutex;`
+//
+// The learner works on a copy in their workspace. To grade it, the copy is compiled with
+// `rustc --test` together with the hidden tests, which are linked in with a `#[path]`
+// attribute pointing back into `exercises/`. Only the name and result of each hidden test is
+// reported, never its source or its panic message.
+//
+// Exercises on `match` can also be graded on whether the learner's match is exhaustive on its
+// own, rather than thanks to a `_` arm. Their copy is compiled with `unreachable_patterns`
+// denied, so an arm that can never run is an error. Then the variant from `exhaustive.txt` is
+// added to the enum: if the copy still compiles, some match let the new variant through
+// without naming it, and the extra `exhaustive_without_wildcard` result fails.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::challenges::{file_name, sorted_dirs};
+use crate::compile_fail::{self, Verdict};
+use crate::compiler;
+use crate::lessons;
+use crate::progress::Store;
+use crate::sandbox::{Limit, Limits, Sandbox};
+use crate::Report;
+
+// The name of the result added for exercises with an `exhaustive.txt`.
+pub const EXHAUSTIVE: &str = "exhaustive_without_wildcard";
+
+// The module the hidden tests are linked in as. Unusual enough not to clash with the learner's.
+const HIDDEN_MODULE: &str = "__rust_tutor_hidden";
+
+#[derive(Debug, Clone)]
+pub struct Exercise {
+    // The path below `exercises/`, e.g. `03_ownership/first_word`.
+    pub id: String,
+    pub dir: PathBuf,
+}
+
+impl Exercise {
+    // The chapter directory, e.g. `03_ownership`.
+    pub fn chapter(&self) -> &str {
+        self.id.split('/').next().unwrap_or("")
+    }
+
+    // The exercise directory, e.g. `first_word`.
+    pub fn name(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or("")
+    }
+
+    pub fn path(&self) -> PathBuf {
+        self.dir.join("exercise.rs")
+    }
+
+    pub fn solution_path(&self) -> PathBuf {
+        self.dir.join("solution.rs")
+    }
+
+    pub fn hidden_tests_path(&self) -> PathBuf {
+        self.dir.join("hidden_tests.rs")
+    }
+
+    pub fn title(&self) -> io::Result<String> {
+        let source = fs::read_to_string(self.path())?;
+        let first = source.lines().next().unwrap_or("");
+        Ok(first.trim_start_matches("//").trim().to_string())
+    }
+
+    pub fn hints(&self) -> io::Result<Vec<String>> {
+        let text = fs::read_to_string(self.dir.join("hints.txt"))?;
+        Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(String::from)
+            .collect())
+    }
+
+    // Every file of the exercise, as paths relative to its directory, in order.
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        fn walk(dir: &Path, relative: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+            for entry in fs::read_dir(dir)? {
+                let path = entry?.path();
+                let relative = relative.join(path.file_name().unwrap_or_default());
+                if path.is_dir() {
+                    walk(&path, &relative, files)?;
+                } else {
+                    files.push(relative);
+                }
+            }
+            Ok(())
+        }
+        let mut files = Vec::new();
+        walk(&self.dir, Path::new(""), &mut files)?;
+        files.sort();
+        Ok(files)
+    }
+
+    pub fn fixtures_dir(&self) -> PathBuf {
+        self.dir.join("fixtures")
+    }
+
+    // The `src/common` files listed in `common.txt`, if the exercise has one. They're found
+    // next to the `exercises/` directory this exercise is in, so an exercise from a bundle
+    // uses the bundle's own copies rather than whatever the grader's checkout has.
+    pub fn common_modules(&self) -> io::Result<Vec<PathBuf>> {
+        let path = self.dir.join("common.txt");
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        // `exercises/03_ownership/first_word` is three levels below the root.
+        let root = self
+            .dir
+            .ancestors()
+            .nth(self.id.split('/').count() + 1)
+            .unwrap_or(Path::new(""));
+        let text = fs::read_to_string(path)?;
+        Ok(text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|name| root.join("src").join("common").join(format!("{}.rs", name)))
+            .collect())
+    }
+
+    // The enum and the variant to add to it from `exhaustive.txt`, if the exercise has one.
+    pub fn probe(&self) -> io::Result<Option<(String, String)>> {
+        let path = self.dir.join("exhaustive.txt");
+        if !path.exists() {
+            return Ok(None);
+        }
+        let text = fs::read_to_string(path)?;
+        Ok(text
+            .lines()
+            .filter(|line| !line.trim().is_empty() && !line.starts_with('#'))
+            .find_map(|line| line.split_once(':'))
+            .map(|(name, variant)| (name.trim().to_string(), variant.trim().to_string())))
+    }
+
+    // How the exercise is called in the progress log.
+    pub fn subject(&self) -> String {
+        format!("exercise/{}", self.id)
+    }
+
+    // The learner's copy.
+    pub fn workspace_file(&self, store: &Store) -> PathBuf {
+        store
+            .workspace()
+            .join("exercises")
+            .join(format!("{}.rs", self.id))
+    }
+}
+
+pub fn exercises_dir() -> PathBuf {
+    lessons::root().join("exercises")
+}
+
+// All exercises, ordered by chapter and then by name.
+pub fn all() -> io::Result<Vec<Exercise>> {
+    let mut exercises = Vec::new();
+    for chapter in sorted_dirs(&exercises_dir())? {
+        for dir in sorted_dirs(&chapter)? {
+            let id = format!("{}/{}", file_name(&chapter), file_name(&dir));
+            exercises.push(Exercise { id, dir });
+        }
+    }
+    Ok(exercises)
+}
+
+// Finds an exercise by id (`03_ownership/first_word`) or name (`first_word`).
+pub fn find(name: &str) -> crate::Result<Exercise> {
+    let matches: Vec<Exercise> = all()?
+        .into_iter()
+        .filter(|e| e.id == name || e.name() == name)
+        .collect();
+    match matches.len() {
+        0 => Err(Report::msg(format!(
+            "no exercise named `{}`; try `rust_tutor exercise list`",
+            name
+        ))),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|e| e.id.as_str()).collect();
+            Err(Report::msg(format!(
+                "`{}` is ambiguous: {}",
+                name,
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TestResult {
+    pub name: String,
+    pub passed: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Grade {
+    // The learner's code doesn't compile on its own; these are rustc's errors.
+    DoesNotCompile(String),
+    // It compiles on its own, but not with the hidden tests: a signature was changed.
+    Incompatible,
+    // The test binary was stopped by the sandbox, e.g. by an endless loop.
+    Stopped(Limit),
+    // The test binary died before finishing, e.g. from a stack overflow or `process::exit`:
+    // how it ended, and the test that was running if that's known.
+    Crashed {
+        status: String,
+        during: Option<String>,
+    },
+    Tested(Vec<TestResult>),
+}
+
+impl Grade {
+    pub fn passed(&self) -> bool {
+        match self {
+            Grade::Tested(results) => !results.is_empty() && results.iter().all(|r| r.passed),
+            _ => false,
+        }
+    }
+}
+
+// Grades `file` against the hidden tests of `exercise`.
+pub fn grade_file(exercise: &Exercise, file: &Path) -> crate::Result<Grade> {
+    let mut sandbox = Sandbox::new(Limits::default())?;
+    sandbox.echo(false);
+    let source = fs::read_to_string(file)?;
+    let binary = sandbox.dir().join(exercise.name());
+    let probe = exercise.probe()?;
+    let mut test_args = vec!["--test", "-A", "warnings"];
+    if probe.is_some() {
+        test_args.extend(["-D", "unreachable_patterns"]);
+    }
+
+    // `mod tracked_mutex;` in the learner's file finds these next to it.
+    for module in exercise.common_modules()? {
+        fs::copy(&module, sandbox.dir().join(file_name(&module)))?;
+    }
+
+    // First on its own, so compile errors only ever show the learner's code.
+    let own = sandbox.dir().join("own.rs");
+    fs::write(&own, &source)?;
+    let compiled = compiler::compile(&own, &binary, &test_args)?;
+    if !compiled.success {
+        return Ok(Grade::DoesNotCompile(compiled.stderr));
+    }
+
+    let linked = sandbox.dir().join(format!("{}.rs", exercise.name()));
+    fs::write(
+        &linked,
+        format!(
+            "{}\n#[cfg(test)]\n#[path = {:?}]\nmod {};\n",
+            source,
+            exercise.hidden_tests_path().to_string_lossy(),
+            HIDDEN_MODULE
+        ),
+    )?;
+    if !compiler::compile(&linked, &binary, &test_args)?.success {
+        return Ok(Grade::Incompatible);
+    }
+
+    // The tests open these by relative paths, like `empty.txt`.
+    if exercise.fixtures_dir().is_dir() {
+        sandbox.fixtures(&exercise.fixtures_dir())?;
+    }
+    let filter = format!("{}::", HIDDEN_MODULE);
+    let run = sandbox.run(&binary, &[&filter, "--test-threads", "1"])?;
+    if let Some(limit) = run.limit {
+        return Ok(Grade::Stopped(limit));
+    }
+    // libtest exits with 101 when a test fails, and prints its summary either way.
+    let finished = run
+        .stdout
+        .lines()
+        .any(|line| line.starts_with("test result: "));
+    if !finished || !(run.status.success() || run.status.code() == Some(101)) {
+        return Ok(Grade::Crashed {
+            status: run.status.to_string(),
+            during: running_test(&run.stdout),
+        });
+    }
+    let mut results = parse_results(&run.stdout);
+    if let Some((name, variant)) = probe {
+        let probed = sandbox.dir().join("probe.rs");
+        let passed = match with_variant(&source, &name, &variant) {
+            Some(source) => {
+                fs::write(&probed, source)?;
+                // The new variant must make some match fail for not covering it.
+                compile_fail::expect_error(&probed, "E0004", true)? == Verdict::Failed
+            }
+            // The enum isn't there any more, so there's nothing to check against.
+            None => false,
+        };
+        results.push(TestResult {
+            name: EXHAUSTIVE.to_string(),
+            passed,
+        });
+    }
+    Ok(Grade::Tested(results))
+}
+
+// `source` with `variant` added as the first variant of `enum name`, right after its `{`.
+pub fn with_variant(source: &str, name: &str, variant: &str) -> Option<String> {
+    let mut offset = 0;
+    for line in source.split_inclusive('\n') {
+        let declares = strip_visibility(line.trim_start())
+            .strip_prefix("enum ")
+            .and_then(|rest| rest.trim_start().strip_prefix(name))
+            .is_some_and(|rest| rest.trim_start().starts_with('{'));
+        if declares {
+            let brace = offset + line.find('{')?;
+            let after = &source[brace + 1..];
+            // Keep a multi-line enum multi-line; `enum X { A, B }` gets it on the same line.
+            let added = if after
+                .trim_start_matches([' ', '\t', '\r'])
+                .starts_with('\n')
+            {
+                format!("\n    {},", variant)
+            } else {
+                format!(" {},", variant)
+            };
+            return Some(format!("{}{}{}", &source[..=brace], added, after));
+        }
+        offset += line.len();
+    }
+    None
+}
+
+// `line` without a leading `pub`, `pub(crate)`, `pub(in path)` and the like.
+fn strip_visibility(line: &str) -> &str {
+    match line.strip_prefix("pub") {
+        Some(rest) if rest.starts_with('(') => match rest.find(')') {
+            Some(end) => rest[end + 1..].trim_start(),
+            None => line,
+        },
+        Some(rest) if rest.starts_with(char::is_whitespace) => rest.trim_start(),
+        _ => line,
+    }
+}
+
+// The test whose name libtest printed last but never got a result, like
+// `test __rust_tutor_hidden::deep ... `.
+fn running_test(stdout: &str) -> Option<String> {
+    let prefix = format!("test {}::", HIDDEN_MODULE);
+    let (name, result) = stdout
+        .lines()
+        .next_back()?
+        .strip_prefix(&prefix)?
+        .split_once(" ...")?;
+    result.trim().is_empty().then(|| name.to_string())
+}
+
+// Reads lines like `test __rust_tutor_hidden::no_spaces ... ok` from libtest's output.
+fn parse_results(stdout: &str) -> Vec<TestResult> {
+    let prefix = format!("test {}::", HIDDEN_MODULE);
+    stdout
+        .lines()
+        .filter_map(|line| {
+            let (name, result) = line.strip_prefix(&prefix)?.split_once(" ... ")?;
+            Some(TestResult {
+                name: name.trim_end_matches(" - should panic").to_string(),
+                passed: result == "ok",
+            })
+        })
+        .collect()
+}
+
+// Grades the learner's copy and records the attempt.
+pub fn check(store: &Store, exercise: &Exercise) -> crate::Result<Grade> {
+    let file = exercise.workspace_file(store);
+    if !file.exists() {
+        return Err(Report::msg(format!(
+            "you haven't started {} yet; run `rust_tutor exercise start {}`",
+            exercise.id,
+            exercise.name()
+        )));
+    }
+    let grade = grade_file(exercise, &file)?;
+    let kind = if grade.passed() {
+        "solved"
+    } else {
+        "attempted"
+    };
+    store.record(kind, &exercise.subject())?;
+    Ok(grade)
+}
+
+// Copies the exercise into the workspace, unless the learner already has a copy. The common
+// modules it uses are always copied, so the learner's file compiles with a plain `rustc`.
+pub fn start(store: &Store, exercise: &Exercise) -> crate::Result<PathBuf> {
+    let file = exercise.workspace_file(store);
+    let dir = file.parent().unwrap_or(Path::new("."));
+    fs::create_dir_all(dir)?;
+    for module in exercise.common_modules()? {
+        fs::copy(&module, dir.join(file_name(&module)))?;
+    }
+    if !file.exists() {
+        fs::copy(exercise.path(), &file)?;
+        store.record("started", &exercise.subject())?;
+    }
+    Ok(file)
+}
+
+// `rust_tutor exercise <list|start|hint|check> [name]`
+pub fn command(store: &Store, args: &[&str]) -> crate::Result<()> {
+    match args {
+        [] | ["list"] => list(store),
+        ["start", name] => {
+            let exercise = find(name)?;
+            let file = start(store, &exercise)?;
+            println!("{}: {}\n", exercise.id, exercise.title()?);
+            println!("Your copy is {}", file.display());
+            println!(
+                "When you're done, run `rust_tutor exercise check {}`.",
+                exercise.name()
+            );
+            Ok(())
+        }
+        ["hint", name] => {
+            let exercise = find(name)?;
+            let hints = exercise.hints()?;
+            match store.next_hint(&exercise.subject(), &hints)? {
+                Some((number, text)) => println!("Hint {}/{}: {}", number, hints.len(), text),
+                None => println!("No more hints for this one."),
+            }
+            Ok(())
+        }
+        ["check", name] => {
+            let exercise = find(name)?;
+            match check(store, &exercise)? {
+                Grade::DoesNotCompile(stderr) => {
+                    Err(Report::msg(format!("it doesn't compile yet:\n{}", stderr)))
+                }
+                Grade::Incompatible => Err(Report::msg(
+                    "your code compiles, but the hidden tests can't use it; \
+                     keep the types and signatures from the exercise as they are",
+                )),
+                Grade::Stopped(limit) => {
+                    Err(Report::msg(format!("the tests were stopped: {}", limit)))
+                }
+                Grade::Crashed { status, during } => Err(Report::msg(match during {
+                    Some(test) => format!("the tests crashed during `{}` ({})", test, status),
+                    None => format!("the tests crashed ({})", status),
+                })),
+                Grade::Tested(results) => {
+                    for result in &results {
+                        let mark = if result.passed { "ok" } else { "FAILED" };
+                        println!("  {:<32} {}", result.name, mark);
+                    }
+                    let failed = results.iter().filter(|r| !r.passed).count();
+                    if failed > 0 {
+                        return Err(Report::msg(format!(
+                            "{} of {} hidden tests failed",
+                            failed,
+                            results.len()
+                        )));
+                    }
+                    println!("All {} hidden tests pass!", results.len());
+                    Ok(())
+                }
+            }
+        }
+        _ => Err(Report::msg(
+            "usage: rust_tutor exercise <list|start|hint|check> [name]",
+        )),
+    }
+}
+
+fn list(store: &Store) -> crate::Result<()> {
+    let events = store.events()?;
+    let mut chapter = String::new();
+    for exercise in all()? {
+        if exercise.chapter() != chapter {
+            chapter = exercise.chapter().to_string();
+            println!("{}", chapter);
+        }
+        let subject = exercise.subject();
+        let has = |kind: &str| {
+            events
+                .iter()
+                .any(|e| e.kind == kind && e.subject == subject)
+        };
+        let status = if has("solved") {
+            "[solved] "
+        } else if has("started") {
+            "[started]"
+        } else {
+            "         "
+        };
+        println!("  {} {:<20} {}", status, exercise.name(), exercise.title()?);
+    }
+    Ok(())
+}
@@ -0,0 +1,439 @@
+// `rust_tutor explain <lesson>:<line>`: the prose that goes with a line of a lesson.
+//
+// The lessons explain themselves in comments, so explaining a line means finding the right
+// comments: the block of comment lines nearest above it, the section heading it falls under
+// (an all-caps comment line like `// MUTABLE REFERENCES`), and comments at the end of the line
+// itself. Glossary entries from `data/glossary.txt` are added for the names and operators the
+// line uses. The output is plain text, so editors can show it for the line under the cursor.
+
+use std::fs;
+use std::path::PathBuf;
+
+use crate::compile_fail;
+use crate::lessons;
+use crate::{Context, Report};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry {
+    pub term: String,
+    pub matches: Vec<String>,
+    pub text: String,
+    pub see: Option<String>,
+    pub docs: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Explanation {
+    // 1-based, as editors and compiler messages count lines.
+    pub line: usize,
+    pub code: String,
+    pub section: Option<String>,
+    pub prose: Vec<String>,
+    pub inline: Vec<String>,
+    // The error code, for a line of a COMPILE_FAIL example.
+    pub compile_fail: Option<String>,
+    pub entries: Vec<Entry>,
+}
+
+pub fn glossary_path() -> PathBuf {
+    lessons::root().join("data").join("glossary.txt")
+}
+
+pub fn glossary() -> crate::Result<Vec<Entry>> {
+    let path = glossary_path();
+    let text = fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))?;
+    parse_glossary(&text).with_context(|| format!("in {}", path.display()))
+}
+
+// Reads blocks of `key: value` lines separated by blank lines. `#` starts a comment line.
+pub fn parse_glossary(text: &str) -> crate::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let lines: Vec<&str> = text.lines().filter(|line| !line.starts_with('#')).collect();
+    for block in lines.split(|line| line.trim().is_empty()) {
+        if block.is_empty() {
+            continue;
+        }
+        let value = |key: &str| {
+            block
+                .iter()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix(':'))
+                .map(|value| value.trim().to_string())
+        };
+        let required = |key: &str| -> crate::Result<String> {
+            value(key).ok_or_else(|| {
+                Report::msg(format!("a glossary entry has no `{}`: {:?}", key, block))
+            })
+        };
+        entries.push(Entry {
+            term: required("term")?,
+            matches: required("matches")?
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            text: required("text")?,
+            see: value("see"),
+            docs: value("docs"),
+        });
+    }
+    Ok(entries)
+}
+
+// Splits a line into its code, with the insides of string literals blanked out, and the text
+// of a `//` comment at its end, if any.
+fn split(line: &str) -> (String, Option<String>) {
+    let chars: Vec<char> = line.chars().collect();
+    let mut code = String::new();
+    let mut in_string = false;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if in_string {
+            if c == '\\' {
+                i += 1;
+            } else if c == '"' {
+                in_string = false;
+                code.push('"');
+            } else {
+                code.push(' ');
+            }
+        } else if c == '"' {
+            in_string = true;
+            code.push('"');
+        } else if c == '\'' && chars.get(i + 1) == Some(&'"') && chars.get(i + 2) == Some(&'\'') {
+            code.push_str("' '");
+            i += 2;
+        } else if c == '/' && chars.get(i + 1) == Some(&'/') {
+            let comment: String = chars[i..].iter().collect();
+            let text = comment.trim_start_matches(['/', '!']).trim();
+            return (code, Some(text.to_string()));
+        } else {
+            code.push(c);
+        }
+        i += 1;
+    }
+    (code, None)
+}
+
+fn comment_only(line: &str) -> bool {
+    line.trim_start().starts_with("//")
+}
+
+fn indent(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+// The text of a section heading: an all-caps comment line, optionally written as
+// `// SECTION: NAME`.
+fn heading(line: &str) -> Option<String> {
+    if !comment_only(line) {
+        return None;
+    }
+    let text = split(line).1?;
+    let allowed = |c: char| c.is_ascii_uppercase() || c.is_ascii_digit() || " -,:'&".contains(c);
+    let letters = text.chars().filter(char::is_ascii_uppercase).count();
+    if letters >= 4 && text.chars().all(allowed) {
+        let text = text.strip_prefix("SECTION:").unwrap_or(&text);
+        Some(text.trim().to_string())
+    } else {
+        None
+    }
+}
+
+// The prose of the comment block around line `end`, up to a COMPILE_FAIL annotation, after
+// which the block is commented-out code.
+fn block(lines: &[&str], end: usize) -> Vec<String> {
+    let mut start = end;
+    while start > 0 && comment_only(lines[start - 1]) {
+        start -= 1;
+    }
+    let mut end = end;
+    while end + 1 < lines.len() && comment_only(lines[end + 1]) {
+        end += 1;
+    }
+    lines[start..=end]
+        .iter()
+        .filter(|line| heading(line).is_none())
+        .filter_map(|line| split(line).1)
+        .take_while(|text| !text.starts_with("COMPILE_FAIL"))
+        .collect()
+}
+
+// The nearest comment block above line `i`, without looking past the start of the item
+// (`fn`, `struct`, ...) that the line belongs to, except for the comments right above it or
+// at the end of the item's first line.
+fn governing(lines: &[&str], i: usize) -> Vec<String> {
+    if comment_only(lines[i]) {
+        return block(lines, i);
+    }
+    above(lines, i)
+}
+
+fn above(lines: &[&str], i: usize) -> Vec<String> {
+    let mut k = i;
+    while k > 0 {
+        let above = k - 1;
+        if comment_only(lines[above]) {
+            return match continued(lines, above) {
+                Some(owner) => inline(lines, owner),
+                None => block(lines, above),
+            };
+        }
+        let starts_item = !lines[k].trim().is_empty() && indent(lines[k]) == 0;
+        if starts_item {
+            return if k == i { Vec::new() } else { inline(lines, k) };
+        }
+        k = above;
+    }
+    Vec::new()
+}
+
+// The heading of the section around line `i`. A heading indented inside `main` doesn't reach
+// past the end of `main`: code indented less than the heading ends its section.
+fn section(lines: &[&str], i: usize) -> Option<String> {
+    let mut least = usize::MAX;
+    for line in lines[..=i].iter().rev() {
+        if let Some(text) = heading(line) {
+            if indent(line) <= least {
+                return Some(text);
+            }
+        } else if !line.trim().is_empty() && !comment_only(line) {
+            least = least.min(indent(line));
+        }
+    }
+    None
+}
+
+// The comment at the end of line `i`, and the comment lines below that continue it, indented
+// further than the line itself.
+fn inline(lines: &[&str], i: usize) -> Vec<String> {
+    let (code, comment) = split(lines[i]);
+    let Some(comment) = comment.filter(|_| !code.trim().is_empty()) else {
+        return Vec::new();
+    };
+    let mut texts = vec![comment];
+    for line in &lines[i + 1..] {
+        if !comment_only(line) || indent(line) <= indent(lines[i]) {
+            break;
+        }
+        texts.extend(split(line).1);
+    }
+    texts
+}
+
+// For a comment line that continues the comment at the end of a line of code, that line.
+fn continued(lines: &[&str], i: usize) -> Option<usize> {
+    let mut k = i;
+    while comment_only(lines[k]) && indent(lines[k]) > 0 {
+        k = k.checked_sub(1)?;
+    }
+    let continues = k != i && indent(lines[i]) > indent(lines[k]);
+    continues
+        .then_some(k)
+        .filter(|&k| inline(lines, k).len() > i - k)
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+// The names and operators in a piece of code, as the glossary matches them. A path like
+// `std::thread::spawn` gives the whole path, every pair of neighbouring segments, and each
+// segment. A method call `.len()` gives `.len` as well as `len`. `&x` gives `&` and `&x`,
+// `&mut` gives `&mut`, and any lifetime gives `'`.
+pub fn tokens(code: &str) -> Vec<String> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        if is_ident(c) && !c.is_ascii_digit() {
+            let start_of_path = i;
+            let mut segments = Vec::new();
+            loop {
+                let start = i;
+                while i < chars.len() && is_ident(chars[i]) {
+                    i += 1;
+                }
+                segments.push(chars[start..i].iter().collect::<String>());
+                let more = chars.get(i) == Some(&':')
+                    && chars.get(i + 1) == Some(&':')
+                    && chars.get(i + 2).is_some_and(|&c| is_ident(c));
+                if !more {
+                    break;
+                }
+                i += 2;
+            }
+            let method = start_of_path > 0 && chars[start_of_path - 1] == '.';
+            if method {
+                tokens.push(format!(".{}", segments[0]));
+            }
+            if segments.len() > 1 {
+                tokens.push(segments.join("::"));
+                tokens.extend(segments.windows(2).map(|pair| pair.join("::")));
+            }
+            tokens.extend(segments);
+            continue;
+        }
+        match c {
+            '&' if chars.get(i + 1) == Some(&'&') => i += 1,
+            '&' => {
+                let rest: String = chars[i + 1..]
+                    .iter()
+                    .take_while(|&&c| is_ident(c))
+                    .collect();
+                if rest == "mut" {
+                    tokens.push(String::from("&mut"));
+                } else if !rest.is_empty() {
+                    tokens.push(String::from("&"));
+                    tokens.push(format!("&{}", rest));
+                } else if chars.get(i + 1).is_some_and(|c| "'[(*".contains(*c)) {
+                    tokens.push(String::from("&"));
+                }
+            }
+            '\'' => {
+                let len = chars[i + 1..].iter().take_while(|&&c| is_ident(c)).count();
+                if len > 0 && chars.get(i + 1 + len) != Some(&'\'') {
+                    tokens.push(String::from("'"));
+                }
+                i += len;
+            }
+            '?' if i > 0 && (is_ident(chars[i - 1]) || ")]".contains(chars[i - 1])) => {
+                tokens.push(String::from("?"));
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    tokens
+}
+
+// The glossary entries for a line: those matching its code, or the `code` spans of its
+// comment.
+fn entries(code: &str, comment: Option<&str>, glossary: &[Entry]) -> Vec<Entry> {
+    let mut found = tokens(code);
+    if let Some(comment) = comment {
+        for span in comment.split('`').skip(1).step_by(2) {
+            found.extend(tokens(span));
+        }
+    }
+    glossary
+        .iter()
+        .filter(|entry| entry.matches.iter().any(|m| found.contains(m)))
+        .cloned()
+        .collect()
+}
+
+// Explains line `line` (1-based) of `source`, or returns `None` if there is no such line.
+pub fn explain(source: &str, line: usize, glossary: &[Entry]) -> Option<Explanation> {
+    let lines: Vec<&str> = source.lines().collect();
+    let i = line.checked_sub(1).filter(|&i| i < lines.len())?;
+    let mut explanation = Explanation {
+        line,
+        code: lines[i].trim().to_string(),
+        section: section(&lines, i),
+        prose: Vec::new(),
+        inline: Vec::new(),
+        compile_fail: None,
+        entries: Vec::new(),
+    };
+
+    // A line of a COMPILE_FAIL example is commented-out code: explain it as code, with the
+    // prose from above the example.
+    let example = compile_fail::examples(source)
+        .into_iter()
+        .find(|e| (e.line..=e.line + e.block.len()).contains(&line));
+    if let Some(example) = example {
+        let text = lines[i].trim_start().trim_start_matches('/');
+        let (code, comment) = split(text);
+        explanation.prose = above(&lines, example.line - 1);
+        explanation.inline.extend(comment.clone());
+        explanation.entries = entries(&code, comment.as_deref(), glossary);
+        explanation.compile_fail = Some(example.code);
+        return Some(explanation);
+    }
+
+    let i = continued(&lines, i).unwrap_or(i);
+    let (code, comment) = split(lines[i]);
+    explanation.prose = governing(&lines, i);
+    explanation.inline = inline(&lines, i);
+    explanation.entries = entries(&code, comment.as_deref(), glossary);
+    Some(explanation)
+}
+
+// Splits `<lesson>:<line>` or `<lesson>:<line>:<column>`, as editors pass positions. The
+// lesson may also be given as a path to its file, or for a lesson of several files, as
+// `<lesson>/<file>`.
+fn position(target: &str) -> crate::Result<(&str, usize)> {
+    let usage = || {
+        format!(
+            "expected <lesson>:<line>, like B_borrowing:19, not `{}`",
+            target
+        )
+    };
+    let mut parts: Vec<&str> = target.rsplitn(3, ':').collect();
+    parts.reverse();
+    let (lesson, line) = match parts[..] {
+        [lesson, line, column] if column.parse::<usize>().is_ok() => (lesson, line),
+        [first, lesson, line] => (&target[..first.len() + 1 + lesson.len()], line),
+        [lesson, line] => (lesson, line),
+        _ => return Err(Report::msg(usage())),
+    };
+    let line = line.parse().map_err(|_| Report::msg(usage()))?;
+    let lesson = lesson
+        .rsplit_once("src/topics/")
+        .map_or(lesson, |(_, rest)| rest);
+    Ok((lesson, line))
+}
+
+// `rust_tutor explain <lesson>:<line>`
+pub fn command(target: &str) -> crate::Result<()> {
+    let (name, line) = position(target)?;
+    let (lesson, file) = lessons::find_file(name)?;
+    let id = lesson.file_id(&file);
+    let explanation = explain(&fs::read_to_string(&file)?, line, &glossary()?)
+        .with_context(|| format!("{} has no line {}", id, line))?;
+
+    println!("{}:{}  {}", id, line, explanation.code);
+    if let Some(section) = &explanation.section {
+        println!("\nSection: {}", section);
+    }
+    if !explanation.prose.is_empty() {
+        println!();
+        for text in &explanation.prose {
+            println!("{}", text);
+        }
+    }
+    if let Some(code) = &explanation.compile_fail {
+        println!(
+            "\nThis line is commented out: uncommented, it fails to compile with {}.",
+            code
+        );
+        println!(
+            "`rust_tutor compile-fail {}` checks that it still does.",
+            lesson.name()
+        );
+    }
+    if !explanation.inline.is_empty() {
+        println!("\nOn this line: {}", explanation.inline.join(" "));
+    }
+    let (api, terms): (Vec<&Entry>, Vec<&Entry>) =
+        explanation.entries.iter().partition(|e| e.docs.is_some());
+    if !terms.is_empty() {
+        println!("\nGlossary:");
+        for entry in terms {
+            print!("  {}: {}", entry.term, entry.text);
+            match &entry.see {
+                Some(see) => println!(" (see {})", see),
+                None => println!(),
+            }
+        }
+    }
+    if !api.is_empty() {
+        println!("\nAPI:");
+        for entry in api {
+            println!("  {}: {}", entry.term, entry.text);
+            println!("    {}", entry.docs.as_deref().unwrap_or(""));
+        }
+    }
+    Ok(())
+}
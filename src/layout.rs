@@ -0,0 +1,643 @@
+// `rust_tutor layout`: how the types defined in the lessons are laid out in memory.
+//
+// The struct and enum definitions are copied out of the lessons into a probe program that
+// prints `size_of`, `align_of` and field offsets. We compile and run it, then draw each struct
+// byte by byte, so you can see padding, field reordering, and why `List::Cons` needs a `Box`.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::compiler::{self, ScratchDir};
+use crate::lessons;
+use crate::sandbox::{Limits, Sandbox};
+use crate::Report;
+
+#[derive(Debug, Clone)]
+pub struct TypeDef {
+    pub name: String,
+    pub lesson: String,
+    pub source: String,
+    pub kind: Kind,
+}
+
+#[derive(Debug, Clone)]
+pub enum Kind {
+    // Named fields, or `0`, `1`, ... for tuple structs, with their types.
+    Struct(Vec<(String, String)>),
+    Enum(Vec<Variant>),
+}
+
+#[derive(Debug, Clone)]
+pub struct Variant {
+    pub name: String,
+    // The source text of the variant, like `Move { x: i32, y: i32 }`.
+    pub source: String,
+    pub fields: Vec<String>,
+}
+
+// Pairs worth comparing side by side, printed after the lesson types.
+const COMPARISONS: [(&str, &str); 4] = [
+    ("Box<List>", "Option<Box<List>>"),
+    ("Rc<RcList>", "Option<Rc<RcList>>"),
+    ("i32", "Option<i32>"),
+    ("&str", "String"),
+];
+
+// Every non-generic struct and enum defined by a lesson. Generic types like `Point<T, U>` have
+// no single layout, so they are left out. Lessons may reuse a name like `Message` for a
+// different type, so each lesson keeps its own.
+pub fn lesson_types() -> crate::Result<Vec<TypeDef>> {
+    let mut types: Vec<TypeDef> = Vec::new();
+    for lesson in lessons::all()? {
+        for file in lesson.files()? {
+            for def in extract(&fs::read_to_string(&file)?, &lesson.id) {
+                if !types
+                    .iter()
+                    .any(|t| t.lesson == def.lesson && t.name == def.name)
+                {
+                    types.push(def);
+                }
+            }
+        }
+    }
+    Ok(types)
+}
+
+// Finds `struct` and `enum` items in a lesson's source.
+pub fn extract(source: &str, lesson: &str) -> Vec<TypeDef> {
+    let lines: Vec<&str> = source.lines().collect();
+    let mut types = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let code = without_visibility(strip_comment(line));
+        let words: Vec<&str> = code.split_whitespace().collect();
+        let (keyword, rest) = match words[..] {
+            [keyword @ ("struct" | "enum"), rest, ..] => (keyword, rest),
+            _ => continue,
+        };
+        let name: String = rest
+            .chars()
+            .take_while(|c| c.is_alphanumeric() || *c == '_')
+            .collect();
+        if rest[name.len()..].starts_with('<') {
+            continue;
+        }
+
+        // The item runs until its brackets are balanced again.
+        let mut item = String::new();
+        let mut depth = 0;
+        for line in &lines[i..] {
+            let code = strip_comment(line);
+            item.push_str(code.trim());
+            item.push('\n');
+            depth += code.matches(['{', '(']).count() as i32;
+            depth -= code.matches(['}', ')']).count() as i32;
+            if depth <= 0 && (code.contains('}') || code.trim_end().ends_with(';')) {
+                break;
+            }
+        }
+        let item = without_visibility(&item);
+
+        let kind = if keyword == "struct" {
+            Kind::Struct(struct_fields(&item))
+        } else {
+            Kind::Enum(enum_variants(&item))
+        };
+        types.push(TypeDef {
+            name,
+            lesson: lesson.to_string(),
+            source: item,
+            kind,
+        });
+    }
+    types
+}
+
+// Removes `pub`, `pub(crate)`, `pub(super)`, `pub(in path)` and the like. The probe puts every
+// type at its crate root, where `pub(super)` doesn't even compile.
+fn without_visibility(text: &str) -> String {
+    let mut out = String::new();
+    let mut rest = text;
+    while let Some(index) = rest.find("pub") {
+        let (before, after) = (&rest[..index], &rest[index + 3..]);
+        out.push_str(before);
+        let starts_word = before
+            .chars()
+            .next_back()
+            .is_none_or(|c| !c.is_alphanumeric() && c != '_');
+        let restricted = after.starts_with('(');
+        if starts_word && (restricted || after.starts_with(char::is_whitespace)) {
+            rest = match after.find(')') {
+                Some(end) if restricted => after[end + 1..].trim_start(),
+                _ => after.trim_start(),
+            };
+        } else {
+            out.push_str("pub");
+            rest = after;
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.find("//") {
+        Some(index) => &line[..index],
+        None => line,
+    }
+}
+
+// The text between the first opening bracket and its partner, e.g. the body of `{ ... }`.
+fn inner(text: &str, open: char, close: char) -> Option<&str> {
+    let start = text.find(open)?;
+    let end = text.rfind(close)?;
+    (start < end).then(|| &text[start + 1..end])
+}
+
+// Splits at commas that are not nested inside brackets.
+fn split_top_level(text: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut depth = 0;
+    let mut current = String::new();
+    for c in text.chars() {
+        match c {
+            '<' | '(' | '[' | '{' => depth += 1,
+            '>' | ')' | ']' | '}' => depth -= 1,
+            ',' if depth == 0 => {
+                parts.push(current.trim().to_string());
+                current.clear();
+                continue;
+            }
+            _ => {}
+        }
+        current.push(c);
+    }
+    parts.push(current.trim().to_string());
+    parts.into_iter().filter(|p| !p.is_empty()).collect()
+}
+
+fn struct_fields(item: &str) -> Vec<(String, String)> {
+    let header = item.split(['{', '(', ';']).next().unwrap_or("");
+    if item[header.len()..].starts_with('(') {
+        let types = inner(item, '(', ')')
+            .map(split_top_level)
+            .unwrap_or_default();
+        types
+            .into_iter()
+            .enumerate()
+            .map(|(i, ty)| (i.to_string(), ty))
+            .collect()
+    } else {
+        named_fields(inner(item, '{', '}').unwrap_or(""))
+            .into_iter()
+            .collect()
+    }
+}
+
+fn named_fields(body: &str) -> Vec<(String, String)> {
+    split_top_level(body)
+        .into_iter()
+        .filter_map(|field| {
+            let (name, ty) = field.split_once(':')?;
+            Some((name.trim().to_string(), ty.trim().to_string()))
+        })
+        .collect()
+}
+
+fn enum_variants(item: &str) -> Vec<Variant> {
+    split_top_level(inner(item, '{', '}').unwrap_or(""))
+        .into_iter()
+        .map(|variant| {
+            let name: String = variant
+                .chars()
+                .take_while(|c| c.is_alphanumeric() || *c == '_')
+                .collect();
+            let rest = variant[name.len()..].trim();
+            let fields = if rest.starts_with('(') {
+                inner(rest, '(', ')')
+                    .map(split_top_level)
+                    .unwrap_or_default()
+            } else if rest.starts_with('{') {
+                named_fields(inner(rest, '{', '}').unwrap_or(""))
+                    .into_iter()
+                    .map(|(_, ty)| ty)
+                    .collect()
+            } else {
+                Vec::new()
+            };
+            Variant {
+                name,
+                source: variant.split_whitespace().collect::<Vec<_>>().join(" "),
+                fields,
+            }
+        })
+        .collect()
+}
+
+// Everything the probe program measured.
+#[derive(Default)]
+pub struct Measurements {
+    // Size and alignment, by lesson and type (as written in that lesson's source).
+    pub sizes: BTreeMap<(String, String), (usize, usize)>,
+    // Field offsets, by lesson, type and field.
+    pub offsets: BTreeMap<(String, String, String), usize>,
+    // Size and alignment of the types being compared.
+    pub compared: BTreeMap<String, (usize, usize)>,
+    // Lessons whose types were left out because they don't compile in the probe.
+    pub dropped: Vec<String>,
+}
+
+impl Measurements {
+    pub fn size(&self, lesson: &str, ty: &str) -> Option<(usize, usize)> {
+        self.sizes
+            .get(&(lesson.to_string(), ty.to_string()))
+            .copied()
+    }
+
+    fn size_of(&self, def: &TypeDef, ty: &str) -> usize {
+        self.sizes[&(def.lesson.clone(), ty.to_string())].0
+    }
+}
+
+const PROBE_IMPORTS: &str = "\
+    use std::cell::RefCell;\n\
+    use std::io;\n\
+    use std::mem::{align_of, offset_of, size_of};\n\
+    use std::rc::{Rc, Weak};\n";
+
+// Each lesson's types go in a module of their own, so two lessons can both define a `Message`.
+// A comparison is measured next to the first lesson type it mentions.
+fn probe_program(types: &[TypeDef], comparisons: &[&str]) -> String {
+    let mut lessons: Vec<&str> = types.iter().map(|def| def.lesson.as_str()).collect();
+    lessons.dedup();
+    let home = |ty: &str| {
+        lessons.iter().position(|&lesson| {
+            types
+                .iter()
+                .any(|def| def.lesson == lesson && mentions(ty, &def.name))
+        })
+    };
+
+    let mut program = String::from("#![allow(dead_code, unused_imports)]\n");
+    program.push_str(PROBE_IMPORTS);
+    for (i, lesson) in lessons.iter().enumerate() {
+        let own: Vec<&TypeDef> = types.iter().filter(|def| def.lesson == *lesson).collect();
+        program.push_str(&format!("\nmod lesson_{} {{\n{}\n", i, PROBE_IMPORTS));
+        for def in &own {
+            program.push_str(&def.source);
+            program.push('\n');
+        }
+        program.push_str("pub fn probe() {\n");
+        for ty in measured_types(&own) {
+            program.push_str(&print_size("size", Some(lesson), &ty));
+        }
+        for def in &own {
+            if let Kind::Struct(fields) = &def.kind {
+                for (field, _) in fields {
+                    program.push_str(&format!(
+                        "    println!(\"offset\\t{}\\t{}\\t{}\\t{{}}\", offset_of!({}, {}));\n",
+                        lesson.escape_default(),
+                        def.name,
+                        field,
+                        def.name,
+                        field
+                    ));
+                }
+            }
+        }
+        for ty in comparisons.iter().filter(|ty| home(ty) == Some(i)) {
+            program.push_str(&print_size("compared", None, ty));
+        }
+        program.push_str("}\n}\n");
+    }
+
+    program.push_str("\nfn main() {\n");
+    for i in 0..lessons.len() {
+        program.push_str(&format!("    lesson_{}::probe();\n", i));
+    }
+    for ty in comparisons.iter().filter(|ty| home(ty).is_none()) {
+        program.push_str(&print_size("compared", None, ty));
+    }
+    program.push_str("}\n");
+    program
+}
+
+// The types, field types and variant payloads of one lesson's types, each once.
+fn measured_types(types: &[&TypeDef]) -> Vec<String> {
+    let mut measured: Vec<String> = Vec::new();
+    let mut measure = |ty: &str| {
+        if !measured.iter().any(|m| m == ty) {
+            measured.push(ty.to_string());
+        }
+    };
+    for def in types {
+        measure(&def.name);
+        match &def.kind {
+            Kind::Struct(fields) => fields.iter().for_each(|(_, ty)| measure(ty)),
+            Kind::Enum(variants) => variants
+                .iter()
+                .flat_map(|v| &v.fields)
+                .for_each(|ty| measure(ty)),
+        }
+        // The payload of each variant, measured as a tuple of its fields.
+        if let Kind::Enum(variants) = &def.kind {
+            for variant in variants {
+                measure(&payload(variant));
+            }
+        }
+    }
+    measured
+}
+
+// A line of the probe that prints `kind`, the lesson if any, `ty`, its size and its alignment.
+fn print_size(kind: &str, lesson: Option<&str>, ty: &str) -> String {
+    let lesson = lesson
+        .map(|lesson| format!("{}\\t", lesson.escape_default()))
+        .unwrap_or_default();
+    format!(
+        "    println!(\"{}\\t{}{}\\t{{}}\\t{{}}\", size_of::<{}>(), align_of::<{}>());\n",
+        kind,
+        lesson,
+        ty.escape_default(),
+        ty,
+        ty
+    )
+}
+
+fn payload(variant: &Variant) -> String {
+    match variant.fields.len() {
+        0 => String::from("()"),
+        1 => format!("({},)", variant.fields[0]),
+        _ => format!("({})", variant.fields.join(", ")),
+    }
+}
+
+// Compiles a probe for `types` and `comparisons`, or returns the compiler's errors.
+fn compile_probe(
+    scratch: &ScratchDir,
+    types: &[TypeDef],
+    comparisons: &[&str],
+) -> io::Result<Result<PathBuf, String>> {
+    let source = scratch.path().join("probe.rs");
+    let binary = scratch.path().join("probe");
+    fs::write(&source, probe_program(types, comparisons))?;
+    let compiled = compiler::compile(&source, &binary, &[])?;
+    Ok(if compiled.success {
+        Ok(binary)
+    } else {
+        Err(compiled.stderr)
+    })
+}
+
+// Measures `types` and `comparisons` with one probe program. If a lesson's types don't compile
+// there, they are left out rather than costing every other lesson its layout.
+pub fn measure(types: &[TypeDef], comparisons: &[&str]) -> crate::Result<Measurements> {
+    let scratch = ScratchDir::new("layout")?;
+    let mut compiled = compile_probe(&scratch, types, comparisons)?;
+    let mut dropped: Vec<String> = Vec::new();
+    if compiled.is_err() {
+        let mut lessons: Vec<&str> = types.iter().map(|def| def.lesson.as_str()).collect();
+        lessons.dedup();
+        for lesson in lessons {
+            let own: Vec<TypeDef> = types
+                .iter()
+                .filter(|def| def.lesson == lesson)
+                .cloned()
+                .collect();
+            if compile_probe(&scratch, &own, &[])?.is_err() {
+                dropped.push(lesson.to_string());
+            }
+        }
+        let (kept, left_out): (Vec<TypeDef>, Vec<TypeDef>) = types
+            .iter()
+            .cloned()
+            .partition(|def| !dropped.contains(&def.lesson));
+        let comparisons: Vec<&str> = comparisons
+            .iter()
+            .copied()
+            .filter(|ty| !left_out.iter().any(|def| mentions(ty, &def.name)))
+            .collect();
+        compiled = compile_probe(&scratch, &kept, &comparisons)?;
+    }
+    let binary = compiled
+        .map_err(|errors| Report::msg(format!("the layout probe does not compile:\n{}", errors)))?;
+
+    let run = Sandbox::new(Limits::default())?.run(&binary, &[])?;
+    if !run.success() {
+        return Err(Report::msg(format!(
+            "the layout probe failed:\n{}",
+            run.stderr
+        )));
+    }
+
+    let mut measurements = Measurements {
+        dropped,
+        ..Measurements::default()
+    };
+    for line in run.stdout.lines() {
+        let fields: Vec<&str> = line.split('\t').collect();
+        match fields[..] {
+            ["size", lesson, ty, size, align] => {
+                measurements.sizes.insert(
+                    (lesson.to_string(), ty.to_string()),
+                    (size.parse()?, align.parse()?),
+                );
+            }
+            ["offset", lesson, ty, field, offset] => {
+                measurements.offsets.insert(
+                    (lesson.to_string(), ty.to_string(), field.to_string()),
+                    offset.parse()?,
+                );
+            }
+            ["compared", ty, size, align] => {
+                measurements
+                    .compared
+                    .insert(ty.to_string(), (size.parse()?, align.parse()?));
+            }
+            _ => {}
+        }
+    }
+    Ok(measurements)
+}
+
+const BYTES_PER_ROW: usize = 8;
+
+fn render_struct(def: &TypeDef, fields: &[(String, String)], m: &Measurements) -> String {
+    let size = m.size_of(def, &def.name);
+    let mut out = String::new();
+    let mut owner = vec!['.'; size];
+
+    let mut placed: Vec<(usize, usize, &str, &str)> = fields
+        .iter()
+        .map(|(field, ty)| {
+            let offset = m.offsets[&(def.lesson.clone(), def.name.clone(), field.clone())];
+            (offset, m.size_of(def, ty), field.as_str(), ty.as_str())
+        })
+        .collect();
+    // In memory order, which is not necessarily the order in the source.
+    placed.sort();
+    let width = placed
+        .iter()
+        .map(|(_, _, field, ty)| field.len() + ty.len() + 2)
+        .max()
+        .unwrap_or(0);
+    for (i, (offset, field_size, field, ty)) in placed.iter().enumerate() {
+        let letter = (b'A' + i as u8) as char;
+        owner[*offset..offset + field_size].fill(letter);
+        out.push_str(&format!(
+            "  {}  {:<w$}  offset {:>3}, {}\n",
+            letter,
+            format!("{}: {}", field, ty),
+            offset,
+            bytes(*field_size),
+            w = width
+        ));
+    }
+    out.push('\n');
+    for (row, bytes) in owner.chunks(BYTES_PER_ROW).enumerate() {
+        let cells: Vec<String> = bytes.iter().map(|c| c.to_string()).collect();
+        out.push_str(&format!(
+            "  {:#04x} | {}\n",
+            row * BYTES_PER_ROW,
+            cells.join(" ")
+        ));
+    }
+    let padding = owner.iter().filter(|&&c| c == '.').count();
+    if padding > 0 {
+        out.push_str(&format!("  (. = {} of padding)\n", bytes(padding)));
+    }
+    out
+}
+
+fn render_enum(def: &TypeDef, variants: &[Variant], m: &Measurements) -> String {
+    let size = m.size_of(def, &def.name);
+    let mut out = String::new();
+    let mut largest = 0;
+    for variant in variants {
+        let payload_size = m.size_of(def, &payload(variant));
+        largest = largest.max(payload_size);
+        out.push_str(&format!(
+            "  {:<36} payload {}\n",
+            variant.source,
+            bytes(payload_size)
+        ));
+    }
+    if largest == 0 {
+        out.push_str(&format!(
+            "  No variant carries data, so all it stores is which one it is: {}.\n",
+            bytes(size)
+        ));
+    } else if variants.len() > 1 && size == largest {
+        out.push_str(
+            "  No room is spent on a tag: the variant is stored in a value the payload can never\n  \
+             hold (a \"niche\"), like a null pointer or an unused enum value.\n",
+        );
+    } else if variants.len() > 1 {
+        out.push_str(&format!(
+            "  The tag that says which variant this is, plus padding, takes {}.\n",
+            bytes(size - largest)
+        ));
+    }
+    out
+}
+
+// Whether the type `ty` is or contains the type called `name`.
+fn mentions(ty: &str, name: &str) -> bool {
+    ty.split(|c: char| !c.is_alphanumeric() && c != '_')
+        .any(|word| word == name)
+}
+
+fn bytes(count: usize) -> String {
+    if count == 1 {
+        String::from("1 byte")
+    } else {
+        format!("{} bytes", count)
+    }
+}
+
+// A note for types that contain themselves through a pointer, like `List` through `Box<List>`.
+fn recursion_note(def: &TypeDef, m: &Measurements) -> Option<String> {
+    let field_types: Vec<&String> = match &def.kind {
+        Kind::Struct(fields) => fields.iter().map(|(_, ty)| ty).collect(),
+        Kind::Enum(variants) => variants.iter().flat_map(|v| &v.fields).collect(),
+    };
+    let pointer = field_types.into_iter().find(|ty| mentions(ty, &def.name))?;
+    Some(format!(
+        "  `{}` refers to itself through `{}`, which is only {}.\n  \
+         Without that indirection a {} would have to contain a whole {},\n  \
+         so its size would be infinite (error E0072).\n",
+        def.name,
+        pointer,
+        bytes(m.size_of(def, pointer)),
+        def.name,
+        def.name
+    ))
+}
+
+// `rust_tutor layout [type...]`
+pub fn run(names: &[&str]) -> crate::Result<()> {
+    let all = lesson_types()?;
+    let shown: Vec<&TypeDef> = all
+        .iter()
+        .filter(|def| names.is_empty() || names.contains(&def.name.as_str()))
+        .collect();
+    if shown.is_empty() {
+        let mut known: Vec<&str> = all.iter().map(|d| d.name.as_str()).collect();
+        known.sort();
+        known.dedup();
+        return Err(Report::msg(format!(
+            "no such type; the lessons define {}",
+            known.join(", ")
+        )));
+    }
+    // Pairs that mention one of the requested types, or all of them when nothing was requested.
+    let comparisons: Vec<(&str, &str)> = COMPARISONS
+        .iter()
+        .copied()
+        .filter(|(a, _)| names.is_empty() || names.iter().any(|name| mentions(a, name)))
+        .collect();
+    let compared: Vec<&str> = comparisons.iter().flat_map(|&(a, b)| [a, b]).collect();
+    let m = measure(&all, &compared)?;
+    if !m.dropped.is_empty() {
+        eprintln!(
+            "note: left out the types from {}, which don't compile in the layout probe\n",
+            m.dropped.join(", ")
+        );
+    }
+
+    for def in shown.iter() {
+        let Some((size, align)) = m.size(&def.lesson, &def.name) else {
+            continue;
+        };
+        println!(
+            "{}  ({})  size {}, align {}",
+            def.name, def.lesson, size, align
+        );
+        match &def.kind {
+            Kind::Struct(fields) => print!("{}", render_struct(def, fields, &m)),
+            Kind::Enum(variants) => print!("{}", render_enum(def, variants, &m)),
+        }
+        if let Some(note) = recursion_note(def, &m) {
+            print!("{}", note);
+        }
+        println!();
+    }
+
+    if !comparisons.is_empty() {
+        println!("Side by side:");
+        for (a, b) in comparisons
+            .into_iter()
+            .filter(|(a, b)| m.compared.contains_key(*a) && m.compared.contains_key(*b))
+        {
+            println!(
+                "  {:<20} {:>9}    {:<20} {:>9}",
+                a,
+                bytes(m.compared[a].0),
+                b,
+                bytes(m.compared[b].0)
+            );
+        }
+    }
+    Ok(())
+}
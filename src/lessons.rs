@@ -0,0 +1,239 @@
+// Finding lessons on disk.
+//
+// A lesson is a standalone program under `src/topics/<chapter>/<lesson>.rs`, for example
+// `src/topics/03_ownership/A_ownership.rs`. Its id is the path below `src/topics` without the
+// extension: `03_ownership/A_ownership`.
+//
+// Lessons about code spread over several files are directories instead, in one of two forms:
+//
+//     <lesson>/main.rs       a crate with its own module tree, compiled from `main.rs`
+//     <lesson>/Cargo.toml    a small Cargo workspace, built with Cargo; it has one binary
+//
+// Their id is the path of the directory: `11_modules/A_module_tree`.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::Report;
+
+#[derive(Debug, Clone)]
+pub struct Lesson {
+    pub id: String,
+    // The lesson's file, or its directory.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Layout {
+    File,
+    ModuleTree,
+    Workspace,
+}
+
+impl Lesson {
+    // The chapter directory, e.g. `03_ownership`.
+    pub fn chapter(&self) -> &str {
+        self.id.split('/').next().unwrap_or("")
+    }
+
+    // The file name without extension, e.g. `A_ownership`.
+    pub fn name(&self) -> &str {
+        self.id.rsplit('/').next().unwrap_or("")
+    }
+
+    pub fn layout(&self) -> Layout {
+        if self.path.is_file() {
+            Layout::File
+        } else if self.path.join("Cargo.toml").is_file() {
+            Layout::Workspace
+        } else {
+            Layout::ModuleTree
+        }
+    }
+
+    // The file the program starts from: the lesson itself, the `main.rs` of a module tree, or
+    // the `main.rs` of the workspace's binary.
+    pub fn main_file(&self) -> io::Result<PathBuf> {
+        match self.layout() {
+            Layout::File => Ok(self.path.clone()),
+            Layout::ModuleTree => Ok(self.path.join("main.rs")),
+            Layout::Workspace => {
+                let mut files = Vec::new();
+                rust_files(&self.path, &mut files)?;
+                files
+                    .into_iter()
+                    .find(|file| file.ends_with("src/main.rs"))
+                    .ok_or_else(|| {
+                        io::Error::new(
+                            io::ErrorKind::NotFound,
+                            format!("{} has no src/main.rs", self.id),
+                        )
+                    })
+            }
+        }
+    }
+
+    // Every Rust file of the lesson, the main file first.
+    pub fn files(&self) -> io::Result<Vec<PathBuf>> {
+        if self.layout() == Layout::File {
+            return Ok(vec![self.path.clone()]);
+        }
+        let mut files = Vec::new();
+        rust_files(&self.path, &mut files)?;
+        // Without the extension, `garden.rs` sorts before the modules in `garden/`.
+        files.sort_by_key(|file| file.with_extension(""));
+        let main = self.main_file()?;
+        files.retain(|file| *file != main);
+        files.insert(0, main);
+        Ok(files)
+    }
+
+    // How to refer to one of the lesson's files: the lesson id for a single-file lesson,
+    // otherwise the id with the file's path inside the lesson, like
+    // `11_modules/A_module_tree/garden/vegetables.rs`.
+    pub fn file_id(&self, file: &Path) -> String {
+        match file.strip_prefix(&self.path) {
+            Ok(inner) if !inner.as_os_str().is_empty() => {
+                format!("{}/{}", self.id, inner.to_string_lossy())
+            }
+            _ => self.id.clone(),
+        }
+    }
+
+    // Files the lesson reads while it runs, if it has any: the directory
+    // `<chapter>/fixtures/<lesson>/`. They are copied into the sandbox before it starts.
+    pub fn fixtures(&self) -> Option<PathBuf> {
+        let dir = self.path.parent()?.join("fixtures").join(self.name());
+        dir.is_dir().then_some(dir)
+    }
+
+    // The source of the main file.
+    pub fn source(&self) -> io::Result<String> {
+        fs::read_to_string(self.main_file()?)
+    }
+
+    // How the lesson is called in the progress log.
+    pub fn subject(&self) -> String {
+        format!("lesson/{}", self.id)
+    }
+
+    // Lessons like `08_testing/A_writing_tests.rs` have no `main` and are run as a test binary.
+    pub fn is_test_only(&self) -> io::Result<bool> {
+        Ok(!self.source()?.contains("fn main("))
+    }
+}
+
+fn rust_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            // Build output, in case someone ran Cargo in the lesson itself.
+            if !path.ends_with("target") {
+                rust_files(&path, files)?;
+            }
+        } else if path.extension().and_then(|e| e.to_str()) == Some("rs") {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+// The root of the checkout the tutor was built from.
+pub fn root() -> &'static Path {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+}
+
+pub fn topics_dir() -> PathBuf {
+    root().join("src").join("topics")
+}
+
+// All lessons, ordered by chapter and then by lesson.
+pub fn all() -> io::Result<Vec<Lesson>> {
+    let mut lessons = Vec::new();
+    let mut chapters: Vec<PathBuf> = fs::read_dir(topics_dir())?
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<io::Result<_>>()?;
+    chapters.sort();
+
+    for chapter in chapters.iter().filter(|p| p.is_dir()) {
+        let chapter_name = file_name(chapter);
+        let mut files: Vec<PathBuf> = fs::read_dir(chapter)?
+            .map(|entry| entry.map(|e| e.path()))
+            .collect::<io::Result<_>>()?;
+        files.sort();
+
+        for file in files {
+            let is_lesson = if file.is_dir() {
+                file.join("main.rs").is_file() || file.join("Cargo.toml").is_file()
+            } else {
+                file.extension().and_then(|e| e.to_str()) == Some("rs")
+            };
+            if is_lesson {
+                let stem = file.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+                lessons.push(Lesson {
+                    id: format!("{}/{}", chapter_name, stem),
+                    path: file.clone(),
+                });
+            }
+        }
+    }
+    Ok(lessons)
+}
+
+// Looks a lesson up by its id (`03_ownership/A_ownership`), its file name (`A_ownership`)
+// or its name without the letter prefix (`ownership`).
+pub fn find(name: &str) -> crate::Result<Lesson> {
+    let name = name.trim_end_matches(".rs");
+    let matches: Vec<Lesson> = all()?
+        .into_iter()
+        .filter(|lesson| {
+            let short = lesson.name().split_once('_').map(|(_, rest)| rest);
+            lesson.id == name || lesson.name() == name || short == Some(name)
+        })
+        .collect();
+
+    match matches.len() {
+        0 => Err(Report::msg(format!(
+            "no lesson called `{}`; see `rust_tutor lessons`",
+            name
+        ))),
+        1 => Ok(matches.into_iter().next().unwrap()),
+        _ => {
+            let ids: Vec<&str> = matches.iter().map(|l| l.id.as_str()).collect();
+            Err(Report::msg(format!(
+                "`{}` is ambiguous: {}",
+                name,
+                ids.join(", ")
+            )))
+        }
+    }
+}
+
+// Finds one file of a lesson, given as `<lesson>/<file>` like `A_module_tree/garden.rs`. A
+// plain lesson name means its main file.
+pub fn find_file(name: &str) -> crate::Result<(Lesson, PathBuf)> {
+    if !name.ends_with(".rs") || find(name).is_ok() {
+        let lesson = find(name)?;
+        let file = lesson.main_file()?;
+        return Ok((lesson, file));
+    }
+    for (i, _) in name.match_indices('/') {
+        if let Ok(lesson) = find(&name[..i]) {
+            let file = lesson.path.join(&name[i + 1..]);
+            if lesson.layout() != Layout::File && file.is_file() {
+                return Ok((lesson, file));
+            }
+        }
+    }
+    Err(Report::msg(format!(
+        "no lesson file called `{}`; see `rust_tutor lessons`",
+        name
+    )))
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
@@ -0,0 +1,30 @@
+// The tutor itself: the pieces behind the `rust_tutor` command-line tool.
+// The lessons under `src/topics` are standalone programs and are not part of this crate.
+
+pub mod achievements;
+pub mod challenges;
+pub mod classroom;
+pub mod common;
+pub mod compare;
+pub mod compile_fail;
+pub mod compiler;
+pub mod exercises;
+pub mod explain;
+pub mod layout;
+pub mod lessons;
+pub mod memdiagram;
+pub mod playground;
+pub mod progress;
+pub mod repl;
+pub mod runner;
+pub mod sandbox;
+pub mod sha256;
+pub mod submission;
+pub mod tar;
+pub mod timeline;
+
+// Errors from the tutor are reports from `common/report.rs`, the same type the lessons use:
+// any error, plus the context it happened in.
+pub use common::report::{Context, Report};
+
+pub type Result<T> = std::result::Result<T, Report>;
@@ -1,7 +1,298 @@
+use std::env;
+use std::path::{Path, PathBuf};
+use std::process;
+
+use rust_tutor::achievements;
+use rust_tutor::classroom;
+use rust_tutor::common::{clock, explore};
+use rust_tutor::compare::View;
+use rust_tutor::progress::{self, Home, Store};
+use rust_tutor::runner;
+use rust_tutor::submission;
+use rust_tutor::{Context, Report};
+
+const USAGE: &str = "\
+Usage: rust_tutor [--profile <name>] [--verbose] <command>
+
+Commands:
+  lessons          list all lessons
+  run <lesson>     compile and run a lesson in the sandbox
+    --clock <mode>   real, fast (100x), instant, or scaled:<factor>
+    --dot <dir>      write Graphviz files of Rc graphs into <dir>
+  explore <lesson> [--preemptions <n>] [--interleavings <n>]
+                   run a lesson's concurrency models in every thread order, within the bounds
+                   (defaults: 2 preemptions, 1000 interleavings)
+  challenge list   list the borrow-checker challenges
+  challenge <start|hint|check> <name>
+                   work on a challenge: copy it into your workspace, get a hint, check your fix
+  challenge verify check that every challenge fails as described and its solution passes
+  compile-fail [lesson]
+                   check that the lessons' COMPILE_FAIL examples fail as annotated
+  exercise list    list the exercises
+  exercise <start|hint|check> <name>
+                   work on an exercise; `check` grades it with hidden tests
+  compare <name> [--view unified|side-by-side] [--width <n>]
+                   compare your solved exercise or challenge with the reference solution
+  explain <lesson>:<line>
+                   show the comments, section and glossary entries that explain a line
+  layout [type...] show how the types from the lessons are laid out in memory
+  repl             experiment with Rust statements and expressions
+  pipeline [chain] watch items go through an iterator chain, like `(1..10).filter(..).take(2)`
+  assign <lessons...> [--out <dir>]
+                   bundle the exercises of some lessons or chapters for a class (default: assignment)
+  grade <submissions> [--bundle <dir>] [--out <dir>]
+                   grade one workspace per learner; writes gradebook.csv and feedback (default: grades)
+  learner-key <learner...>
+                   print the keys learners sign submissions with ($RUST_TUTOR_COURSE_SECRET)
+  submit [--bundle <dir>] [--key <key>] [--out <file>]
+                   pack and sign your exercise solutions and progress into an archive to hand in
+  verify-submission <archive> [--bundle <dir>] [--receipt <sha256>] [--extract <dir>]
+                   check a submission's signature, and that it was made from the assignment
+  progress         show what you've completed and your achievements
+  profile list     list the learner profiles; * marks the current one
+  profile <create|switch|delete> <name>
+  profile rename <old> <new>
+                   manage profiles, each with its own progress and workspace
+  help             show this message
+
+--profile <name> (or $RUST_TUTOR_PROFILE) uses a profile for one command without switching.
+--verbose (or $RUST_LIB_BACKTRACE=1) adds a backtrace to errors.";
+
 fn main() {
-    hello();
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let profile = take_flag(&mut args, "--profile");
+    let verbose = take_switch(&mut args, "--verbose");
+    if verbose {
+        // Reports only capture a backtrace when this is set.
+        env::set_var("RUST_LIB_BACKTRACE", "1");
+    }
+
+    let result = match args.first().map(String::as_str) {
+        Some("lessons") => runner::list_command(),
+        Some("run") => with_store(profile.as_deref(), |store| run(&args[1..], store)),
+        Some("explore") => explore(&args[1..]),
+        Some("challenge") => {
+            let args: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+            with_store(profile.as_deref(), |store| {
+                rust_tutor::challenges::command(store, &args)
+            })
+        }
+        Some("compile-fail") => rust_tutor::compile_fail::command(args.get(1).map(String::as_str)),
+        Some("exercise") => {
+            let args: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+            with_store(profile.as_deref(), |store| {
+                rust_tutor::exercises::command(store, &args)
+            })
+        }
+        Some("compare") => store(profile.as_deref()).and_then(|store| compare(&args[1..], &store)),
+        Some("explain") => match args.get(1) {
+            Some(target) => rust_tutor::explain::command(target),
+            None => Err(Report::msg("usage: rust_tutor explain <lesson>:<line>")),
+        },
+        Some("layout") => layout(&args[1..]),
+        Some("assign") => {
+            let args = &args[1..];
+            let out = flag(args, "--out").unwrap_or("assignment");
+            classroom::assign_command(&positional(args), Path::new(out))
+        }
+        Some("grade") => grade(&args[1..]),
+        Some("submit") => store(profile.as_deref()).and_then(|store| {
+            let args = &args[1..];
+            submission::submit_command(
+                &store,
+                flag(args, "--bundle").map(Path::new),
+                flag(args, "--key"),
+                flag(args, "--out").map(Path::new),
+            )
+        }),
+        Some("verify-submission") => verify_submission(&args[1..]),
+        Some("learner-key") => {
+            let learners: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+            submission::learner_key_command(&learners)
+        }
+        Some("progress") => store(profile.as_deref())
+            .and_then(|store| rust_tutor::achievements::progress_command(&store)),
+        Some("profile") => {
+            let args: Vec<&str> = args[1..].iter().map(String::as_str).collect();
+            Home::open()
+                .map_err(Into::into)
+                .and_then(|home| progress::profile_command(&home, &args))
+        }
+        Some("repl") => rust_tutor::repl::run(),
+        Some("pipeline") => rust_tutor::playground::command(args.get(1).map(String::as_str)),
+        Some("help") | None => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        Some(other) => Err(Report::msg(format!(
+            "unknown command `{}`; try `rust_tutor help`",
+            other
+        ))),
+    };
+
+    if let Err(e) = result {
+        // `RUST_BACKTRACE=1` is about panics; someone who set it for their own programs
+        // shouldn't get a backtrace with every typo in a command.
+        let backtrace = verbose || env::var_os("RUST_LIB_BACKTRACE").is_some_and(|v| v != "0");
+        if backtrace {
+            eprintln!("error: {:?}", e);
+        } else {
+            eprintln!("error: {}", e);
+            let causes: Vec<String> = e.chain().skip(1).map(|c| c.to_string()).collect();
+            if !causes.is_empty() {
+                eprintln!("\nCaused by:");
+                for (n, cause) in causes.iter().enumerate() {
+                    eprintln!("    {}: {}", n, cause);
+                }
+            }
+        }
+        process::exit(1);
+    }
+}
+
+fn run(args: &[String], store: &Store) -> rust_tutor::Result<()> {
+    let mut options = runner::Options {
+        store: Some(store.clone()),
+        ..runner::Options::default()
+    };
+    if let Some(mode) = flag(args, "--clock") {
+        options.clock =
+            clock::Mode::parse(mode).with_context(|| format!("unknown clock mode `{}`", mode))?;
+    }
+    options.dot_dir = flag(args, "--dot").map(PathBuf::from);
+    match positional(args).first() {
+        Some(lesson) => runner::run_command(lesson, &options),
+        None => Err(Report::msg(
+            "usage: rust_tutor run <lesson> [--clock <mode>] [--dot <dir>]",
+        )),
+    }
+}
+
+fn explore(args: &[String]) -> rust_tutor::Result<()> {
+    let mut bounds = explore::Bounds::default();
+    if let Some(n) = flag(args, "--preemptions") {
+        bounds.preemptions = n.parse::<usize>().context("--preemptions takes a number")?;
+    }
+    if let Some(n) = flag(args, "--interleavings") {
+        bounds.interleavings = n
+            .parse::<usize>()
+            .context("--interleavings takes a number")?;
+        if bounds.interleavings == 0 {
+            return Err(Report::msg("--interleavings must be at least 1"));
+        }
+    }
+    match positional(args).first() {
+        Some(lesson) => runner::explore_command(lesson, bounds),
+        None => Err(Report::msg(
+            "usage: rust_tutor explore <lesson> [--preemptions <n>] [--interleavings <n>]",
+        )),
+    }
+}
+
+fn grade(args: &[String]) -> rust_tutor::Result<()> {
+    let out = flag(args, "--out").unwrap_or("grades");
+    match positional(args).first() {
+        Some(submissions) => classroom::grade_command(
+            Path::new(submissions),
+            flag(args, "--bundle").map(Path::new),
+            Path::new(out),
+        ),
+        None => Err(Report::msg(
+            "usage: rust_tutor grade <submissions> [--bundle <dir>] [--out <dir>]",
+        )),
+    }
+}
+
+fn verify_submission(args: &[String]) -> rust_tutor::Result<()> {
+    match positional(args).first() {
+        Some(archive) => submission::verify_command(
+            Path::new(archive),
+            flag(args, "--bundle").map(Path::new),
+            flag(args, "--receipt"),
+            flag(args, "--extract").map(Path::new),
+        ),
+        None => Err(Report::msg(
+            "usage: rust_tutor verify-submission <archive> [--bundle <dir>] \
+                     [--receipt <sha256>] [--extract <dir>]",
+        )),
+    }
+}
+
+fn compare(args: &[String], store: &Store) -> rust_tutor::Result<()> {
+    let view = match flag(args, "--view").unwrap_or("unified") {
+        "unified" => View::Unified,
+        "side-by-side" => View::SideBySide {
+            width: match flag(args, "--width") {
+                Some(width) => width.parse()?,
+                None => 120,
+            },
+        },
+        other => return Err(Report::msg(format!("unknown view `{}`", other))),
+    };
+    match positional(args).first() {
+        Some(name) => rust_tutor::compare::command(store, name, view),
+        None => Err(Report::msg(
+            "usage: rust_tutor compare <name> [--view unified|side-by-side] [--width <n>]",
+        )),
+    }
+}
+
+fn layout(args: &[String]) -> rust_tutor::Result<()> {
+    let names: Vec<&str> = args.iter().map(String::as_str).collect();
+    rust_tutor::layout::run(&names)
+}
+
+fn store(profile: Option<&str>) -> rust_tutor::Result<Store> {
+    Home::open()
+        .context("opening the tutor's home directory")?
+        .store(profile)
+}
+
+// Runs a command that may make progress, then announces any achievements it earned.
+// A failed check is progress too: it may have extended a streak.
+fn with_store(
+    profile: Option<&str>,
+    command: impl FnOnce(&Store) -> rust_tutor::Result<()>,
+) -> rust_tutor::Result<()> {
+    let store = store(profile)?;
+    let result = command(&store);
+    achievements::announce(&store)?;
+    result
+}
+
+// Removes the flag `name`, which takes no value, from `args`, returning whether it was there.
+fn take_switch(args: &mut Vec<String>, name: &str) -> bool {
+    let before = args.len();
+    args.retain(|arg| arg != name);
+    args.len() != before
+}
+
+// Removes `name` and the value following it from `args`, returning the value.
+fn take_flag(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == name)?;
+    let value = args.get(index + 1).cloned();
+    args.drain(index..(index + 2).min(args.len()));
+    value
+}
+
+// The value following `name`, as in `--clock fast`.
+fn flag<'a>(args: &'a [String], name: &str) -> Option<&'a str> {
+    let index = args.iter().position(|arg| arg == name)?;
+    args.get(index + 1).map(String::as_str)
 }
 
-fn hello() {
-    println!("Hello, world");
+// Arguments that are neither flags nor flag values.
+fn positional(args: &[String]) -> Vec<&str> {
+    let mut positional = Vec::new();
+    let mut skip_next = false;
+    for arg in args {
+        if skip_next {
+            skip_next = false;
+        } else if arg.starts_with("--") {
+            skip_next = true;
+        } else {
+            positional.push(arg.as_str());
+        }
+    }
+    positional
 }
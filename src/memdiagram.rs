@@ -0,0 +1,250 @@
+// Draws the checkpoints recorded by `common/memory.rs` as stack/heap diagrams:
+//
+//     after `let s2 = s1;`
+//     +------+----------------------+
+//     | s1   | (moved)              |
+//     +------+----------------------+
+//     | s2   | ptr 0x5a1c2e0        | ---> +---+---+---+---+---+
+//     |      | len 5                |      | h | e | l | l | o |
+//     |      | cap 5                |      +---+---+---+---+---+
+//     +------+----------------------+
+//
+// Moved variables are greyed out when the output is a terminal.
+
+use crate::timeline::unescape;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Slot {
+    String {
+        name: String,
+        address: usize,
+        ptr: usize,
+        len: usize,
+        capacity: usize,
+        contents: String,
+    },
+    Str {
+        name: String,
+        address: usize,
+        ptr: usize,
+        len: usize,
+        contents: String,
+    },
+    Reference {
+        name: String,
+        address: usize,
+        target: usize,
+    },
+    Value {
+        name: String,
+        address: usize,
+        value: String,
+    },
+    Moved {
+        name: String,
+    },
+}
+
+impl Slot {
+    fn name(&self) -> &str {
+        match self {
+            Slot::String { name, .. }
+            | Slot::Str { name, .. }
+            | Slot::Reference { name, .. }
+            | Slot::Value { name, .. }
+            | Slot::Moved { name } => name,
+        }
+    }
+
+    fn address(&self) -> Option<usize> {
+        match self {
+            Slot::String { address, .. }
+            | Slot::Str { address, .. }
+            | Slot::Reference { address, .. }
+            | Slot::Value { address, .. } => Some(*address),
+            Slot::Moved { .. } => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Checkpoint {
+    pub title: String,
+    pub slots: Vec<Slot>,
+}
+
+pub fn parse(text: &str) -> Vec<Checkpoint> {
+    let mut checkpoints: Vec<Checkpoint> = Vec::new();
+    for line in text.lines() {
+        let fields: Vec<String> = line.split('\t').map(unescape).collect();
+        if fields[0] == "checkpoint" {
+            checkpoints.push(Checkpoint {
+                title: fields.get(1).cloned().unwrap_or_default(),
+                slots: Vec::new(),
+            });
+        } else if let (Some(slot), Some(checkpoint)) = (parse_slot(&fields), checkpoints.last_mut())
+        {
+            checkpoint.slots.push(slot);
+        }
+    }
+    checkpoints
+}
+
+fn parse_slot(fields: &[String]) -> Option<Slot> {
+    let number = |i: usize| fields.get(i)?.parse::<usize>().ok();
+    let text = |i: usize| fields.get(i).cloned();
+    let slot = match fields[0].as_str() {
+        "string" => Slot::String {
+            name: text(1)?,
+            address: number(2)?,
+            ptr: number(3)?,
+            len: number(4)?,
+            capacity: number(5)?,
+            contents: text(6)?,
+        },
+        "str" => Slot::Str {
+            name: text(1)?,
+            address: number(2)?,
+            ptr: number(3)?,
+            len: number(4)?,
+            contents: text(5)?,
+        },
+        "reference" => Slot::Reference {
+            name: text(1)?,
+            address: number(2)?,
+            target: number(3)?,
+        },
+        "value" => Slot::Value {
+            name: text(1)?,
+            address: number(2)?,
+            value: text(3)?,
+        },
+        "moved" => Slot::Moved { name: text(1)? },
+        _ => return None,
+    };
+    Some(slot)
+}
+
+const CELL_WIDTH: usize = 20;
+// Longer buffers are cut off; the diagram is about the shape, not every byte.
+const MAX_BYTES: usize = 16;
+
+// One byte of a buffer: the character itself when it is printable ASCII, its hex value otherwise.
+fn byte_label(byte: u8) -> String {
+    if byte.is_ascii_graphic() || byte == b' ' {
+        format!(" {} ", byte as char)
+    } else {
+        format!("{:02x} ", byte)
+    }
+}
+
+// A buffer drawn as a row of boxes, one per byte. Spare capacity is drawn as `.`.
+fn buffer(contents: &str, capacity: usize) -> [String; 3] {
+    let mut cells: Vec<String> = contents.bytes().map(byte_label).collect();
+    while cells.len() < capacity {
+        cells.push(String::from(" . "));
+    }
+    let hidden = cells.len().saturating_sub(MAX_BYTES);
+    cells.truncate(MAX_BYTES);
+
+    let border = format!("+{}", "---+".repeat(cells.len()));
+    let mut middle = format!("|{}|", cells.join("|"));
+    if hidden > 0 {
+        middle.push_str(&format!(" (+{} more)", hidden));
+    }
+    [border.clone(), middle, border]
+}
+
+pub fn render(checkpoint: &Checkpoint, color: bool) -> String {
+    let name_width = checkpoint
+        .slots
+        .iter()
+        .map(|slot| slot.name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+    let border = format!(
+        "+{}+{}+",
+        "-".repeat(name_width + 2),
+        "-".repeat(CELL_WIDTH + 2)
+    );
+
+    let mut out = format!("{}\n{}\n", checkpoint.title, border);
+    for slot in &checkpoint.slots {
+        // The cells of this slot in the stack column, and what to draw to the right of them.
+        let (cells, right, arrow): (Vec<String>, Vec<String>, bool) = match slot {
+            Slot::String {
+                ptr,
+                len,
+                capacity,
+                contents,
+                ..
+            } => (
+                vec![
+                    format!("ptr {:#x}", ptr),
+                    format!("len {}", len),
+                    format!("cap {}", capacity),
+                ],
+                buffer(contents, *capacity).to_vec(),
+                true,
+            ),
+            Slot::Str {
+                ptr, len, contents, ..
+            } => (
+                vec![format!("ptr {:#x}", ptr), format!("len {}", len)],
+                vec![
+                    format!("{:?}", contents),
+                    String::from("(read-only, part of the program itself)"),
+                ],
+                true,
+            ),
+            Slot::Reference { target, .. } => {
+                let pointee = checkpoint
+                    .slots
+                    .iter()
+                    .find(|other| other.address() == Some(*target));
+                let description = match pointee {
+                    Some(other) => format!("{} (on the stack)", other.name()),
+                    None => String::from("(somewhere else)"),
+                };
+                (vec![format!("ptr {:#x}", target)], vec![description], true)
+            }
+            Slot::Value { value, .. } => (vec![value.clone()], Vec::new(), false),
+            Slot::Moved { .. } => (vec![String::from("(moved)")], Vec::new(), false),
+        };
+
+        for (i, cell) in cells.iter().enumerate() {
+            let name = if i == 0 { slot.name() } else { "" };
+            let mut line = format!(
+                "| {:<nw$} | {:<cw$} |",
+                name,
+                cell,
+                nw = name_width,
+                cw = CELL_WIDTH
+            );
+            if let Some(right) = right.get(i) {
+                line.push_str(if i == 0 && arrow { " ---> " } else { "      " });
+                line.push_str(right);
+            }
+            if color && matches!(slot, Slot::Moved { .. }) {
+                line = format!("\x1b[2m{}\x1b[0m", line);
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+        // A buffer is three lines tall, even if only two stack cells point at it.
+        for extra in right.iter().skip(cells.len()) {
+            out.push_str(&format!(
+                "| {:<nw$} | {:<cw$} |      {}\n",
+                "",
+                "",
+                extra,
+                nw = name_width,
+                cw = CELL_WIDTH
+            ));
+        }
+        out.push_str(&border);
+        out.push('\n');
+    }
+    out
+}
@@ -0,0 +1,336 @@
+// `rust_tutor pipeline`: a playground for iterator chains.
+//
+// A chain like `(1..10).filter(|x| x % 2 == 0).map(|x| x * x).take(2)` is split into its
+// source and its stages, and compiled into a program that wraps every stage with
+// `common/pipeline.rs`, so running it shows each item's way through the chain:
+//
+//     pipeline::header(&["(1..10)", "filter", "map", "take"]);
+//     let result = pipeline::source((1..10))
+//         .filter(|x| x % 2 == 0).trace(1)
+//         .map(|x| x * x).trace(2)
+//         .take(2).trace(3)
+//         .collect::<Vec<_>>();
+//     pipeline::finish();
+//
+// A chain that doesn't end in a consumer like `sum` or `count` is collected into a `Vec`.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use crate::compiler;
+use crate::lessons;
+use crate::repl::{self, Outcome};
+use crate::sandbox::{Limits, Sandbox};
+use crate::Report;
+
+// Methods that turn a collection or a string into an iterator. Leading calls to these are part
+// of the source rather than stages: `"a b".split_whitespace()` is where the items come from.
+const SOURCES: [&str; 18] = [
+    "iter",
+    "iter_mut",
+    "into_iter",
+    "chars",
+    "char_indices",
+    "bytes",
+    "split_whitespace",
+    "lines",
+    "split",
+    "rsplit",
+    "split_terminator",
+    "matches",
+    "keys",
+    "values",
+    "into_keys",
+    "into_values",
+    "windows",
+    "chunks",
+];
+
+// Methods that take an iterator and return another one.
+const ADAPTERS: [&str; 23] = [
+    "map",
+    "filter",
+    "filter_map",
+    "flat_map",
+    "flatten",
+    "take",
+    "skip",
+    "take_while",
+    "skip_while",
+    "map_while",
+    "step_by",
+    "enumerate",
+    "zip",
+    "chain",
+    "peekable",
+    "inspect",
+    "rev",
+    "cloned",
+    "copied",
+    "scan",
+    "fuse",
+    "cycle",
+    "by_ref",
+];
+
+#[derive(Debug, PartialEq)]
+pub struct Chain {
+    pub source: String,
+    // Method calls without the leading `.`, like `filter(|x| x % 2 == 0)`.
+    pub stages: Vec<String>,
+    pub consumer: String,
+}
+
+// The method a call like `map::<u8>(...)` or `take(2)` calls.
+pub fn method(call: &str) -> &str {
+    call.split(['(', ':']).next().unwrap_or(call).trim()
+}
+
+// Splits `code` at the `.` of every method call outside of brackets, strings and characters.
+fn calls(code: &str) -> Vec<String> {
+    let chars: Vec<char> = code.chars().collect();
+    let mut parts = vec![String::new()];
+    let mut depth = 0;
+    let mut i = 0;
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' => {
+                // Copy the whole string literal.
+                let start = i;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                parts
+                    .last_mut()
+                    .unwrap()
+                    .extend(&chars[start..=i.min(chars.len() - 1)]);
+                i += 1;
+                continue;
+            }
+            '\'' => {
+                // A character literal like `'('` or `'\n'`.
+                let end = if chars.get(i + 1) == Some(&'\\') {
+                    chars[i + 2..]
+                        .iter()
+                        .position(|&c| c == '\'')
+                        .map(|p| i + 2 + p)
+                } else {
+                    Some(i + 2).filter(|&end| chars.get(end) == Some(&'\''))
+                };
+                if let Some(end) = end {
+                    parts.last_mut().unwrap().extend(&chars[i..=end]);
+                    i = end + 1;
+                    continue;
+                }
+            }
+            '.' if depth == 0 => {
+                let after = chars.get(i + 1).copied().unwrap_or(' ');
+                let before = if i > 0 { chars[i - 1] } else { ' ' };
+                if (after.is_alphabetic() || after == '_') && before != '.' {
+                    parts.push(String::new());
+                    i += 1;
+                    continue;
+                }
+            }
+            _ => {}
+        }
+        parts.last_mut().unwrap().push(c);
+        i += 1;
+    }
+    // `.0` or a field access is part of whatever came before it.
+    let mut merged: Vec<String> = Vec::new();
+    for part in parts {
+        let part = part.trim().to_string();
+        match merged.last_mut() {
+            Some(last) if !part.contains('(') => {
+                last.push('.');
+                last.push_str(&part);
+            }
+            _ => merged.push(part),
+        }
+    }
+    merged
+}
+
+pub fn parse(code: &str) -> crate::Result<Chain> {
+    let code = code.trim().trim_end_matches(';');
+    if repl::open_brackets(code) != 0 {
+        return Err(Report::msg("the brackets in that chain don't match"));
+    }
+    let mut calls = calls(code).into_iter();
+    let mut source = calls.next().unwrap_or_default();
+    let mut stages: Vec<String> = calls.collect();
+    if source.is_empty() {
+        return Err(Report::msg(
+            "expected a chain like `(1..10).filter(|x| x % 2 == 0).map(|x| x * x)`",
+        ));
+    }
+    while !stages.is_empty() && SOURCES.contains(&method(&stages[0])) {
+        source = format!("{}.{}", source, stages.remove(0));
+    }
+    let consumer = match stages.last() {
+        Some(last) if !ADAPTERS.contains(&method(last)) => stages.pop().unwrap(),
+        _ => String::from("collect::<Vec<_>>()"),
+    };
+    Ok(Chain {
+        source,
+        stages,
+        consumer,
+    })
+}
+
+pub fn program(chain: &Chain) -> String {
+    let common = lessons::root()
+        .join("src")
+        .join("common")
+        .join("pipeline.rs");
+    let mut labels = vec![format!("{:?}", chain.source)];
+    labels.extend(chain.stages.iter().map(|s| format!("{:?}", method(s))));
+
+    let mut expression = format!("pipeline::source({})", chain.source);
+    for (n, stage) in chain.stages.iter().enumerate() {
+        expression.push_str(&format!("\n        .{}\n        .trace({})", stage, n + 1));
+    }
+    expression.push_str(&format!("\n        .{}", chain.consumer));
+
+    format!(
+        "#![allow(unused)]\n\n\
+         #[path = {:?}]\n\
+         mod pipeline;\n\n\
+         use pipeline::Trace;\n\n\
+         fn main() {{\n\
+         \x20   pipeline::header(&[{}]);\n\
+         \x20   let result = {};\n\
+         \x20   pipeline::finish();\n\
+         \x20   let shown = format!(\"{{:?}}\", result);\n\
+         \x20   if shown != \"()\" {{\n\
+         \x20       println!(\"result: {{}}\", shown);\n\
+         \x20   }}\n\
+         }}\n",
+        common.to_string_lossy(),
+        labels.join(", "),
+        expression
+    )
+}
+
+pub struct Playground {
+    sandbox: Sandbox,
+}
+
+impl Playground {
+    pub fn new() -> io::Result<Playground> {
+        Ok(Playground {
+            // An endless chain like `(1..).map(...)` fills the screen quickly.
+            sandbox: Sandbox::new(Limits {
+                output: 64 * 1024,
+                ..Limits::default()
+            })?,
+        })
+    }
+
+    pub fn run(&self, chain: &Chain) -> io::Result<Outcome> {
+        let source = self.sandbox.dir().join("pipeline.rs");
+        let binary = self.sandbox.dir().join("pipeline");
+        fs::write(&source, program(chain))?;
+
+        let compiled = compiler::compile(&source, &binary, &["-A", "warnings"])?;
+        if !compiled.success {
+            return Ok(Outcome::CompileError(compiled.stderr));
+        }
+        let run = self.sandbox.run(&compiled.binary, &[])?;
+        if run.success() {
+            Ok(Outcome::Ran {
+                stdout: run.stdout,
+                stderr: run.stderr,
+            })
+        } else {
+            Ok(Outcome::Failed {
+                stdout: run.stdout,
+                stderr: run.stderr,
+                limit: run.limit,
+            })
+        }
+    }
+
+    fn show(&self, code: &str) -> crate::Result<()> {
+        match self.run(&parse(code)?)? {
+            Outcome::Ran { stdout, stderr } => {
+                print!("{}", stdout);
+                eprint!("{}", stderr);
+            }
+            Outcome::CompileError(errors) => eprint!("{}", errors),
+            Outcome::Failed {
+                stdout,
+                stderr,
+                limit,
+            } => {
+                print!("{}", stdout);
+                eprint!("{}", stderr);
+                if let Some(limit) = limit {
+                    eprintln!("stopped: {}", self.sandbox.limits().describe(limit));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+const HELP: &str = "\
+Enter an iterator chain to see every item go through it, one row per item from the source:
+  (1..10).filter(|x| x % 2 == 0).map(|x| x * x).take(2)
+  \"one two three\".split_whitespace().map(|w| w.len()).sum::<usize>()
+A chain that doesn't end in a consumer (`sum`, `count`, `fold`, ...) is collected into a Vec.
+Commands:
+  :show <chain>   print the program a chain is run as
+  :help           show this message
+  :quit           leave the playground";
+
+// `rust_tutor pipeline [chain]`: runs one chain, or reads them from stdin until `:quit`.
+pub fn command(chain: Option<&str>) -> crate::Result<()> {
+    let playground = Playground::new()?;
+    if let Some(chain) = chain {
+        return playground.show(chain);
+    }
+
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+    println!("rust_tutor iterator playground. Type :help for help.");
+    loop {
+        print!("pipeline> ");
+        io::stdout().flush()?;
+        let mut code = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        while repl::open_brackets(&code) > 0 {
+            print!("       .. ");
+            io::stdout().flush()?;
+            match lines.next() {
+                Some(line) => {
+                    code.push('\n');
+                    code.push_str(&line?);
+                }
+                None => break,
+            }
+        }
+        let code = code.trim();
+        let result = match code.split_once(' ').map_or((code, ""), |(a, b)| (a, b)) {
+            ("", _) => Ok(()),
+            (":quit" | ":q", _) => break,
+            (":help", _) => {
+                println!("{}", HELP);
+                Ok(())
+            }
+            (":show", chain) => parse(chain).map(|chain| println!("{}", program(&chain))),
+            _ => playground.show(code),
+        };
+        if let Err(e) = result {
+            eprintln!("error: {}", e);
+        }
+    }
+    Ok(())
+}
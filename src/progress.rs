@@ -0,0 +1,339 @@
+// The learner's progress and workspace, kept in a data directory outside the lessons:
+// `$RUST_TUTOR_HOME` if it is set, `.rust_tutor` in the checkout otherwise.
+//
+// Several learners can share a checkout by using profiles. Each profile has its own directory
+// under `profiles/`, with its own progress log and workspace:
+//
+//     .rust_tutor/
+//         current_profile        the name of the profile `rust_tutor profile switch` chose
+//         profiles/default/      progress.tsv and workspace/
+//         profiles/alice/
+//
+// `--profile <name>` or `$RUST_TUTOR_PROFILE` picks a profile for a single command instead.
+//
+// Progress is an append-only log with one event per line, `<unix seconds>\t<event>\t<subject>`:
+//
+//     1760000000	started	challenge/borrowck/01_two_mutable_borrows
+//     1760000420	solved	challenge/borrowck/01_two_mutable_borrows
+//
+// Nothing is ever rewritten, so a crash can at worst lose the last line.
+
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::lessons;
+use crate::Report;
+
+pub const ENV: &str = "RUST_TUTOR_HOME";
+pub const PROFILE_ENV: &str = "RUST_TUTOR_PROFILE";
+pub const DEFAULT_PROFILE: &str = "default";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub time: u64,
+    pub kind: String,
+    pub subject: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Store {
+    dir: PathBuf,
+}
+
+impl Store {
+    pub fn at(dir: impl Into<PathBuf>) -> io::Result<Store> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Store { dir })
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    // The profile name, which is the name of the store's directory.
+    pub fn name(&self) -> String {
+        self.dir
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default()
+    }
+
+    // Where the learner's own copies of challenges and exercises live.
+    pub fn workspace(&self) -> PathBuf {
+        self.dir.join("workspace")
+    }
+
+    fn log(&self) -> PathBuf {
+        self.dir.join("progress.tsv")
+    }
+
+    pub fn record(&self, kind: &str, subject: &str) -> io::Result<()> {
+        self.record_at(now(), kind, subject)
+    }
+
+    // For events that happened earlier than they are recorded, like an achievement noticed
+    // after the fact.
+    pub fn record_at(&self, time: u64, kind: &str, subject: &str) -> io::Result<()> {
+        let mut log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log())?;
+        writeln!(log, "{}\t{}\t{}", time, kind, subject)
+    }
+
+    pub fn events(&self) -> io::Result<Vec<Event>> {
+        let text = match fs::read_to_string(self.log()) {
+            Ok(text) => text,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(text
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.splitn(3, '\t');
+                Some(Event {
+                    time: fields.next()?.parse().ok()?,
+                    kind: fields.next()?.to_string(),
+                    subject: fields.next()?.to_string(),
+                })
+            })
+            .collect())
+    }
+
+    // How many times `kind` was recorded for `subject`.
+    pub fn count(&self, kind: &str, subject: &str) -> io::Result<usize> {
+        Ok(self
+            .events()?
+            .iter()
+            .filter(|e| e.kind == kind && e.subject == subject)
+            .count())
+    }
+
+    // Reveals the next of `hints` for `subject`, numbered from 1, if there is one left.
+    pub fn next_hint(
+        &self,
+        subject: &str,
+        hints: &[String],
+    ) -> io::Result<Option<(usize, String)>> {
+        let seen = self.count("hint", subject)?;
+        match hints.get(seen) {
+            Some(hint) => {
+                self.record("hint", subject)?;
+                Ok(Some((seen + 1, hint.clone())))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+// The current Unix time, in seconds.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// The data directory, which holds every profile.
+pub struct Home {
+    dir: PathBuf,
+}
+
+impl Home {
+    pub fn open() -> io::Result<Home> {
+        let dir = match env::var_os(ENV) {
+            Some(dir) => PathBuf::from(dir),
+            None => lessons::root().join(".rust_tutor"),
+        };
+        Home::at(dir)
+    }
+
+    pub fn at(dir: impl Into<PathBuf>) -> io::Result<Home> {
+        let home = Home { dir: dir.into() };
+        fs::create_dir_all(home.profiles_dir())?;
+        home.migrate()?;
+        Ok(home)
+    }
+
+    // Before profiles existed, the progress log and workspace were directly in the data
+    // directory. They become the default profile.
+    fn migrate(&self) -> io::Result<()> {
+        let default = self.profile_dir(DEFAULT_PROFILE);
+        for name in ["progress.tsv", "workspace"] {
+            let old = self.dir.join(name);
+            if old.exists() && !default.join(name).exists() {
+                fs::create_dir_all(&default)?;
+                fs::rename(&old, default.join(name))?;
+            }
+        }
+        Ok(())
+    }
+
+    fn profiles_dir(&self) -> PathBuf {
+        self.dir.join("profiles")
+    }
+
+    fn profile_dir(&self, name: &str) -> PathBuf {
+        self.profiles_dir().join(name)
+    }
+
+    fn exists(&self, name: &str) -> bool {
+        self.profile_dir(name).is_dir()
+    }
+
+    pub fn profiles(&self) -> io::Result<Vec<String>> {
+        let mut names: Vec<String> = fs::read_dir(self.profiles_dir())?
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                entry
+                    .path()
+                    .is_dir()
+                    .then(|| entry.file_name().to_string_lossy().into_owned())
+            })
+            .collect();
+        if !names.iter().any(|n| n == DEFAULT_PROFILE) {
+            names.push(String::from(DEFAULT_PROFILE));
+        }
+        names.sort();
+        Ok(names)
+    }
+
+    // The profile chosen with `rust_tutor profile switch`.
+    pub fn current(&self) -> io::Result<String> {
+        match fs::read_to_string(self.dir.join("current_profile")) {
+            Ok(name) if !name.trim().is_empty() => Ok(name.trim().to_string()),
+            Ok(_) => Ok(String::from(DEFAULT_PROFILE)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(String::from(DEFAULT_PROFILE)),
+            Err(e) => Err(e),
+        }
+    }
+
+    // The store of `profile`, or of the profile picked by the environment or `switch`. Only
+    // the default profile is created on demand; others need `rust_tutor profile create`.
+    pub fn store(&self, profile: Option<&str>) -> crate::Result<Store> {
+        let name = match (profile, env::var(PROFILE_ENV)) {
+            (Some(name), _) => name.to_string(),
+            (None, Ok(name)) if !name.is_empty() => name,
+            _ => self.current()?,
+        };
+        validate(&name)?;
+        if name != DEFAULT_PROFILE && !self.exists(&name) {
+            return Err(Report::msg(format!(
+                "there is no profile `{}`; create it with `rust_tutor profile create {}`",
+                name, name
+            )));
+        }
+        Ok(Store::at(self.profile_dir(&name))?)
+    }
+
+    pub fn create(&self, name: &str) -> crate::Result<()> {
+        validate(name)?;
+        if self.exists(name) {
+            return Err(Report::msg(format!(
+                "the profile `{}` already exists",
+                name
+            )));
+        }
+        fs::create_dir_all(self.profile_dir(name))?;
+        Ok(())
+    }
+
+    pub fn switch(&self, name: &str) -> crate::Result<()> {
+        validate(name)?;
+        if name != DEFAULT_PROFILE && !self.exists(name) {
+            return Err(Report::msg(format!("there is no profile `{}`", name)));
+        }
+        fs::write(self.dir.join("current_profile"), format!("{}\n", name))?;
+        Ok(())
+    }
+
+    // Deletes a profile with all of its progress and solutions.
+    pub fn delete(&self, name: &str) -> crate::Result<()> {
+        validate(name)?;
+        if !self.exists(name) {
+            return Err(Report::msg(format!("there is no profile `{}`", name)));
+        }
+        if self.current()? == name {
+            return Err(Report::msg(format!(
+                "`{}` is the current profile; switch to another one first",
+                name
+            )));
+        }
+        fs::remove_dir_all(self.profile_dir(name))?;
+        Ok(())
+    }
+
+    pub fn rename(&self, old: &str, new: &str) -> crate::Result<()> {
+        validate(old)?;
+        validate(new)?;
+        if !self.exists(old) {
+            return Err(Report::msg(format!("there is no profile `{}`", old)));
+        }
+        if self.exists(new) {
+            return Err(Report::msg(format!("the profile `{}` already exists", new)));
+        }
+        fs::rename(self.profile_dir(old), self.profile_dir(new))?;
+        if self.current()? == old {
+            self.switch(new)?;
+        }
+        Ok(())
+    }
+}
+
+// Profile names become directory names, so keep them simple. Every `Home` method that takes a
+// name checks it, so that a name like `../..` can never reach outside `profiles/`.
+fn validate(name: &str) -> crate::Result<()> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(Report::msg(format!(
+            "`{}` can't be a profile name; use letters, digits, `-` and `_`",
+            name
+        )))
+    }
+}
+
+// `rust_tutor profile <list|create|switch|delete|rename>`
+pub fn profile_command(home: &Home, args: &[&str]) -> crate::Result<()> {
+    match args {
+        [] | ["list"] => {
+            let current = home.current()?;
+            for name in home.profiles()? {
+                let marker = if name == current { "*" } else { " " };
+                println!("{} {}", marker, name);
+            }
+            Ok(())
+        }
+        ["create", name] => {
+            home.create(name)?;
+            println!("Created the profile `{}`.", name);
+            Ok(())
+        }
+        ["switch", name] => {
+            home.switch(name)?;
+            println!("Switched to the profile `{}`.", name);
+            Ok(())
+        }
+        ["delete", name] => {
+            home.delete(name)?;
+            println!("Deleted the profile `{}`.", name);
+            Ok(())
+        }
+        ["rename", old, new] => {
+            home.rename(old, new)?;
+            println!("Renamed the profile `{}` to `{}`.", old, new);
+            Ok(())
+        }
+        _ => Err(Report::msg(
+            "usage: rust_tutor profile <list|create|switch|delete|rename> [name]",
+        )),
+    }
+}
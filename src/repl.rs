@@ -0,0 +1,387 @@
+// A small REPL for quick experiments.
+//
+// Every input is classified as an item (`fn`, `struct`, `use`, ...), a statement (`let x = 5;`)
+// or an expression (`"Здравствуйте"[0..4]`). Items and statements are remembered and the whole
+// session is recompiled as one program for every new input:
+//
+//     <items>
+//     fn main() {
+//         <statements>
+//         <new input>
+//     }
+//
+// If the new input does not compile or panics, it is simply dropped and the session keeps
+// its previous state.
+//
+// Since the whole program runs again, so do the earlier statements. Only what the new input
+// prints is shown, but other side effects happen again: a statement that appends to a file
+// appends once more for every later input. Expressions are shown and then forgotten; bind one
+// with `let` to keep its value.
+
+use std::fs;
+use std::io::{self, BufRead, Write};
+
+use crate::compiler;
+use crate::sandbox::{Limit, Limits, Sandbox};
+
+// Printed right before the new input runs, so we only show the output that input produced
+// rather than replaying everything the earlier statements printed.
+const MARKER: &str = "\u{1}rust_tutor-repl\u{1}";
+
+// Keywords that start an item; these go outside of `fn main`.
+const ITEM_KEYWORDS: [&str; 13] = [
+    "use",
+    "fn",
+    "pub",
+    "struct",
+    "enum",
+    "impl",
+    "trait",
+    "mod",
+    "const",
+    "static",
+    "type",
+    "extern",
+    "macro_rules!",
+];
+
+#[derive(Debug, PartialEq)]
+pub enum Input {
+    Item(String),
+    Statement(String),
+    Expression(String),
+}
+
+// Decides where a piece of code belongs in the generated program.
+pub fn classify(code: &str) -> Input {
+    let code = code.trim();
+    let first_word = code
+        .split(|c: char| c.is_whitespace() || c == '<' || c == '(' || c == '{')
+        .next()
+        .unwrap_or("");
+
+    if ITEM_KEYWORDS.contains(&first_word) || code.starts_with("#[") {
+        Input::Item(code.to_string())
+    } else if first_word == "let" {
+        // Be forgiving about a missing `;` after a `let`.
+        let mut code = code.to_string();
+        if !code.ends_with(';') {
+            code.push(';');
+        }
+        Input::Statement(code)
+    } else if code.ends_with(';') || first_word == "for" || first_word == "while" {
+        Input::Statement(code.to_string())
+    } else {
+        Input::Expression(code.to_string())
+    }
+}
+
+// Returns how many brackets are still open in `code`, ignoring string, raw string and char
+// literals and comments. The REPL keeps reading lines while this is positive.
+pub fn open_brackets(code: &str) -> i32 {
+    let mut depth = 0;
+    let mut chars = code.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            '"' => {
+                while let Some(c) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            // `'('` and `'\''` are char literals, but the `'a` in `&'a str` is a lifetime.
+            '\'' => {
+                let mut ahead = chars.clone();
+                match (ahead.next(), ahead.next()) {
+                    (Some('\\'), _) => {
+                        chars.next();
+                        chars.next();
+                        for c in chars.by_ref() {
+                            if c == '\'' {
+                                break;
+                            }
+                        }
+                    }
+                    (Some(_), Some('\'')) => {
+                        chars.next();
+                        chars.next();
+                    }
+                    _ => {}
+                }
+            }
+            // `r"{"` and `r#"{"#`: nothing ends these but a quote and the same number of `#`s.
+            'r' if raw_string_hashes(&chars).is_some() => {
+                let hashes = raw_string_hashes(&chars).unwrap_or(0);
+                chars.nth(hashes);
+                while let Some(c) = chars.next() {
+                    if c == '"'
+                        && chars.clone().take(hashes).filter(|&c| c == '#').count() == hashes
+                    {
+                        for _ in 0..hashes {
+                            chars.next();
+                        }
+                        break;
+                    }
+                }
+            }
+            // Block comments nest, unlike in C.
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut comments = 1;
+                while comments > 0 {
+                    match chars.next() {
+                        Some('/') if chars.peek() == Some(&'*') => {
+                            chars.next();
+                            comments += 1;
+                        }
+                        Some('*') if chars.peek() == Some(&'/') => {
+                            chars.next();
+                            comments -= 1;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    depth
+}
+
+// After an `r`, the number of `#`s before the opening quote of a raw string, if that's what
+// follows.
+fn raw_string_hashes(chars: &std::iter::Peekable<std::str::Chars>) -> Option<usize> {
+    let mut ahead = chars.clone();
+    let mut hashes = 0;
+    loop {
+        match ahead.next()? {
+            '#' => hashes += 1,
+            '"' => return Some(hashes),
+            _ => return None,
+        }
+    }
+}
+
+// What happened when an input was evaluated.
+pub enum Outcome {
+    // The input compiled and ran; this is what it printed.
+    Ran {
+        stdout: String,
+        stderr: String,
+    },
+    // The input did not compile; these are the compiler's messages.
+    CompileError(String),
+    // The input compiled but the program failed at runtime, possibly because it hit one of
+    // the sandbox limits.
+    Failed {
+        stdout: String,
+        stderr: String,
+        limit: Option<Limit>,
+    },
+}
+
+pub struct Session {
+    items: Vec<String>,
+    statements: Vec<String>,
+    sandbox: Sandbox,
+}
+
+impl Session {
+    pub fn new() -> io::Result<Session> {
+        Ok(Session {
+            items: Vec::new(),
+            statements: Vec::new(),
+            // Nobody wants to scroll through more than a screenful of a runaway loop.
+            sandbox: Sandbox::new(Limits {
+                output: 64 * 1024,
+                ..Limits::default()
+            })?,
+        })
+    }
+
+    pub fn reset(&mut self) {
+        self.items.clear();
+        self.statements.clear();
+    }
+
+    // Evaluates one complete input. The session only remembers it if it compiles and runs.
+    pub fn eval(&mut self, code: &str) -> io::Result<Outcome> {
+        match classify(code) {
+            Input::Item(item) => {
+                self.items.push(item);
+                let outcome = self.run("")?;
+                if !matches!(outcome, Outcome::Ran { .. }) {
+                    self.items.pop();
+                }
+                Ok(outcome)
+            }
+            Input::Statement(statement) => {
+                let outcome = self.run(&statement)?;
+                if matches!(outcome, Outcome::Ran { .. }) {
+                    self.statements.push(statement);
+                }
+                Ok(outcome)
+            }
+            Input::Expression(expr) => {
+                let show = format!("__rust_tutor_show(&({}));", expr);
+                match self.run(&show)? {
+                    // Not every value implements `Debug`; in that case just evaluate it.
+                    Outcome::CompileError(errors)
+                        if errors.contains("doesn't implement `Debug`") =>
+                    {
+                        self.run(&format!("{};", expr))
+                    }
+                    outcome => Ok(outcome),
+                }
+            }
+        }
+    }
+
+    // The program as it would be compiled right now, for `:show`.
+    pub fn source(&self) -> String {
+        self.program("")
+    }
+
+    fn program(&self, newest: &str) -> String {
+        let mut program = String::from("#![allow(unused)]\n\n");
+        for item in &self.items {
+            program.push_str(item);
+            program.push('\n');
+        }
+        program.push_str(
+            "\nfn __rust_tutor_show<T: ::std::fmt::Debug + ?Sized>(value: &T) {\n\
+             \x20   let shown = format!(\"{:?}\", value);\n\
+             \x20   if shown != \"()\" {\n\
+             \x20       println!(\"{}\", shown);\n\
+             \x20   }\n\
+             }\n\nfn main() {\n",
+        );
+        for statement in &self.statements {
+            program.push_str("    ");
+            program.push_str(statement);
+            program.push('\n');
+        }
+        program.push_str(&format!("    println!({:?});\n", MARKER));
+        program.push_str(&format!("    eprintln!({:?});\n", MARKER));
+        program.push_str("    ");
+        program.push_str(newest);
+        program.push_str("\n}\n");
+        program
+    }
+
+    fn run(&self, newest: &str) -> io::Result<Outcome> {
+        let source = self.sandbox.dir().join("repl.rs");
+        let binary = self.sandbox.dir().join("repl");
+        fs::write(&source, self.program(newest))?;
+
+        let compiled = compiler::compile(&source, &binary, &["-A", "warnings"])?;
+        if !compiled.success {
+            return Ok(Outcome::CompileError(compiled.stderr));
+        }
+
+        let run = self.sandbox.run(&compiled.binary, &[])?;
+        let stdout = after_marker(&run.stdout);
+        let stderr = after_marker(&run.stderr);
+        if run.success() {
+            Ok(Outcome::Ran { stdout, stderr })
+        } else {
+            Ok(Outcome::Failed {
+                stdout,
+                stderr,
+                limit: run.limit,
+            })
+        }
+    }
+}
+
+fn after_marker(output: &str) -> String {
+    match output.find(MARKER) {
+        Some(index) => output[index + MARKER.len()..]
+            .trim_start_matches('\n')
+            .to_string(),
+        None => output.to_string(),
+    }
+}
+
+const HELP: &str = "\
+Enter Rust statements, expressions or items. Expressions are printed with `{:?}`.
+Commands:
+  :show    print the program built so far
+  :reset   forget all items and statements
+  :help    show this message
+  :quit    leave the REPL";
+
+// Runs an interactive session on stdin/stdout until `:quit` or end of input.
+pub fn run() -> crate::Result<()> {
+    let mut session = Session::new()?;
+    let stdin = io::stdin();
+    let mut lines = stdin.lock().lines();
+
+    println!("rust_tutor REPL. Type :help for help.");
+    loop {
+        print!(">> ");
+        io::stdout().flush()?;
+
+        let mut code = match lines.next() {
+            Some(line) => line?,
+            None => break,
+        };
+        while open_brackets(&code) > 0 {
+            print!(".. ");
+            io::stdout().flush()?;
+            match lines.next() {
+                Some(line) => {
+                    code.push('\n');
+                    code.push_str(&line?);
+                }
+                None => break,
+            }
+        }
+
+        match code.trim() {
+            "" => continue,
+            ":quit" | ":q" => break,
+            ":help" => println!("{}", HELP),
+            ":show" => println!("{}", session.source()),
+            ":reset" => {
+                session.reset();
+                println!("Session cleared.");
+            }
+            code => match session.eval(code)? {
+                Outcome::Ran { stdout, stderr } => {
+                    print!("{}", stdout);
+                    eprint!("{}", stderr);
+                }
+                Outcome::CompileError(errors) => eprint!("{}", errors),
+                Outcome::Failed {
+                    stdout,
+                    stderr,
+                    limit,
+                } => {
+                    print!("{}", stdout);
+                    eprint!("{}", stderr);
+                    if let Some(limit) = limit {
+                        eprintln!("stopped: {}", session.sandbox.limits().describe(limit));
+                    }
+                    eprintln!("(input discarded)");
+                }
+            },
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,203 @@
+// Compiling and running lessons inside the sandbox.
+
+use std::env;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::common::{clock, explore, memory, rc_graph, trace};
+use crate::compiler;
+use crate::lessons::{Layout, Lesson};
+use crate::memdiagram;
+use crate::progress::Store;
+use crate::sandbox::{Limits, Run, Sandbox};
+use crate::timeline;
+use crate::Report;
+
+pub struct Options {
+    pub limits: Limits,
+    // Show the lesson's output while it runs instead of only returning it.
+    pub echo: bool,
+    // How lessons that use `common/clock.rs` should treat their sleeps.
+    pub clock: clock::Mode,
+    // Where lessons using `common/rc_graph.rs` should write their Graphviz files, if anywhere.
+    pub dot_dir: Option<PathBuf>,
+    // Where to record that the lesson was run to the end, if anywhere.
+    pub store: Option<Store>,
+    // For lessons using `common/explore.rs`: try many interleavings of their models, not one.
+    pub explore: Option<explore::Bounds>,
+}
+
+impl Default for Options {
+    fn default() -> Options {
+        Options {
+            limits: Limits::default(),
+            echo: true,
+            clock: default_clock(),
+            dot_dir: None,
+            store: None,
+            explore: None,
+        }
+    }
+}
+
+// Real time when a person is watching, no waiting at all on CI.
+fn default_clock() -> clock::Mode {
+    if env::var_os("CI").is_some() {
+        clock::Mode::Virtual
+    } else {
+        clock::Mode::Real
+    }
+}
+
+// A finished lesson run, along with whatever the lesson recorded through `common`.
+pub struct LessonRun {
+    pub run: Run,
+    pub trace: Vec<timeline::Event>,
+    pub memory: Vec<memdiagram::Checkpoint>,
+}
+
+pub fn run(lesson: &Lesson, options: &Options) -> crate::Result<LessonRun> {
+    let mut sandbox = Sandbox::new(options.limits)?;
+    let trace_file = sandbox.dir().join("trace.tsv");
+    let memory_file = sandbox.dir().join("memory.tsv");
+    sandbox
+        .echo(options.echo)
+        .env(clock::ENV, &options.clock.to_string())
+        .env(trace::ENV, &trace_file.to_string_lossy())
+        .env(memory::ENV, &memory_file.to_string_lossy());
+    if let Some(dir) = &options.dot_dir {
+        // The lesson runs in the sandbox directory, so the path must not be relative.
+        let dir = env::current_dir()?.join(dir);
+        sandbox.env(rc_graph::ENV, &dir.to_string_lossy());
+    }
+    if let Some(bounds) = options.explore {
+        sandbox.env(explore::ENV, &bounds.to_string());
+    }
+
+    if let Some(fixtures) = lesson.fixtures() {
+        sandbox.fixtures(&fixtures)?;
+    }
+
+    let binary = sandbox.dir().join(lesson.name());
+    let compiled = match lesson.layout() {
+        Layout::Workspace => {
+            compiler::cargo_build(&lesson.path, &sandbox.dir().join("target"), &binary)?
+        }
+        // rustc finds the other files of a module tree from `main.rs` by itself.
+        Layout::File | Layout::ModuleTree => {
+            let mut args = vec!["-A", "warnings"];
+            if lesson.is_test_only()? {
+                args.push("--test");
+            }
+            compiler::compile(&lesson.main_file()?, &binary, &args)?
+        }
+    };
+    if !compiled.success {
+        return Err(Report::msg(format!(
+            "{} does not compile:\n{}",
+            lesson.id, compiled.stderr
+        )));
+    }
+
+    let run = sandbox.run(&binary, &[])?;
+    let trace = timeline::parse(&fs::read_to_string(&trace_file).unwrap_or_default());
+    let memory = memdiagram::parse(&fs::read_to_string(&memory_file).unwrap_or_default());
+    Ok(LessonRun { run, trace, memory })
+}
+
+// `rust_tutor run <lesson> [--clock <mode>]`
+pub fn run_command(name: &str, options: &Options) -> crate::Result<()> {
+    let lesson = crate::lessons::find(name)?;
+    let LessonRun { run, trace, memory } = run(&lesson, options)?;
+
+    if !memory.is_empty() {
+        let color = io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none();
+        println!("\nMemory at each checkpoint:");
+        for checkpoint in &memory {
+            print!("\n{}", memdiagram::render(checkpoint, color));
+        }
+    }
+    if !trace.is_empty() {
+        println!(
+            "\nTimeline:\n{}",
+            timeline::render(&trace, terminal_width())
+        );
+    }
+    if let Some(limit) = run.limit {
+        return Err(Report::msg(format!(
+            "stopped: {} ({})",
+            options.limits.describe(limit),
+            limit
+        )));
+    }
+    // Some lessons end in a panic on purpose, so a failing lesson still counts as completed.
+    if let Some(store) = &options.store {
+        store.record("completed", &lesson.subject())?;
+    }
+    if !run.status.success() {
+        return Err(Report::msg(format!(
+            "{} exited with {}",
+            lesson.id, run.status
+        )));
+    }
+    Ok(())
+}
+
+// `rust_tutor explore <lesson> [--preemptions <n>] [--interleavings <n>]`
+pub fn explore_command(name: &str, bounds: explore::Bounds) -> crate::Result<()> {
+    let lesson = crate::lessons::find(name)?;
+    if !lesson.source()?.contains("common/explore.rs") {
+        return Err(Report::msg(format!(
+            "{} has no models: only lessons using `common/explore.rs` can be explored",
+            lesson.id
+        )));
+    }
+    // Each interleaving is a complete run of the model, so this takes longer than a run.
+    let options = Options {
+        limits: Limits {
+            cpu_time: Duration::from_secs(60),
+            wall_time: Duration::from_secs(120),
+            ..Limits::default()
+        },
+        clock: clock::Mode::Virtual,
+        explore: Some(bounds),
+        ..Options::default()
+    };
+    let run = run(&lesson, &options)?.run;
+    if let Some(limit) = run.limit {
+        return Err(Report::msg(format!(
+            "stopped: {} ({}); try lower bounds",
+            options.limits.describe(limit),
+            limit
+        )));
+    }
+    if !run.status.success() {
+        return Err(Report::msg(format!(
+            "{} exited with {}",
+            lesson.id, run.status
+        )));
+    }
+    Ok(())
+}
+
+// `rust_tutor lessons`
+pub fn list_command() -> crate::Result<()> {
+    let mut chapter = String::new();
+    for lesson in crate::lessons::all()? {
+        if lesson.chapter() != chapter {
+            chapter = lesson.chapter().to_string();
+            println!("{}", chapter);
+        }
+        println!("  {}", lesson.id);
+    }
+    Ok(())
+}
+
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(100)
+}
@@ -0,0 +1,431 @@
+// Runs learner programs with limits on CPU time, wall-clock time, memory and output size.
+//
+// Lessons, exercises and REPL inputs are arbitrary programs, and a stray `loop {}` should not
+// hang the terminal. Every program runs in its own temporary directory and its own process
+// group, so that on a timeout the whole group (including any children it spawned) is killed.
+//
+// The limits are applied with `setrlimit` in the child right before it starts. Like the `abs`
+// example in `09_advanced/C_ffi.rs`, we call the C library directly through `extern "C"`.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::compiler::{self, ScratchDir};
+
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    pub cpu_time: Duration,
+    pub wall_time: Duration,
+    // In bytes, for the whole address space of the program.
+    pub memory: u64,
+    // In bytes, stdout and stderr combined.
+    pub output: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Limits {
+        Limits {
+            cpu_time: Duration::from_secs(5),
+            wall_time: Duration::from_secs(10),
+            memory: 512 * 1024 * 1024,
+            output: 1024 * 1024,
+        }
+    }
+}
+
+impl Limits {
+    // A human-readable explanation of why a program was stopped.
+    pub fn describe(&self, limit: Limit) -> String {
+        match limit {
+            Limit::CpuTime => format!(
+                "the program used more than {}s of CPU time",
+                self.cpu_time.as_secs()
+            ),
+            Limit::WallTime => format!(
+                "the program ran for more than {}s",
+                self.wall_time.as_secs_f64()
+            ),
+            Limit::Memory => format!(
+                "the program tried to use more than {} MiB of memory",
+                self.memory / (1024 * 1024)
+            ),
+            Limit::Output => format!(
+                "the program printed more than {} KiB of output",
+                self.output / 1024
+            ),
+        }
+    }
+}
+
+// The limit that stopped a program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    CpuTime,
+    WallTime,
+    Memory,
+    Output,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Limit::CpuTime => "CPU time limit",
+            Limit::WallTime => "wall-clock limit",
+            Limit::Memory => "memory limit",
+            Limit::Output => "output limit",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+// The result of running a program in the sandbox.
+pub struct Run {
+    pub status: ExitStatus,
+    pub stdout: String,
+    pub stderr: String,
+    // Set when the program was stopped because it hit one of the limits.
+    pub limit: Option<Limit>,
+}
+
+impl Run {
+    pub fn success(&self) -> bool {
+        self.limit.is_none() && self.status.success()
+    }
+}
+
+pub struct Sandbox {
+    limits: Limits,
+    dir: ScratchDir,
+    envs: Vec<(String, String)>,
+    echo: bool,
+}
+
+impl Sandbox {
+    pub fn new(limits: Limits) -> io::Result<Sandbox> {
+        Ok(Sandbox {
+            limits,
+            dir: ScratchDir::new("sandbox")?,
+            envs: Vec::new(),
+            echo: false,
+        })
+    }
+
+    // The temporary directory programs run in. It is also a good place to compile them into.
+    pub fn dir(&self) -> &Path {
+        self.dir.path()
+    }
+
+    pub fn limits(&self) -> &Limits {
+        &self.limits
+    }
+
+    // Copies the files in `dir` into the sandbox, for programs that read files from their
+    // working directory. Programs may change their copies freely; the originals stay as
+    // they are.
+    pub fn fixtures(&self, dir: &Path) -> io::Result<()> {
+        compiler::copy_dir(dir, self.dir())
+    }
+
+    // Sets an environment variable for every program run in this sandbox.
+    pub fn env(&mut self, key: &str, value: &str) -> &mut Sandbox {
+        self.envs.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    // Also pass the program's output through to our own stdout and stderr as it arrives,
+    // for lessons that are meant to be watched while they run.
+    pub fn echo(&mut self, echo: bool) -> &mut Sandbox {
+        self.echo = echo;
+        self
+    }
+
+    pub fn run(&self, program: &Path, args: &[&str]) -> io::Result<Run> {
+        let mut command = Command::new(program);
+        command
+            .args(args)
+            .current_dir(self.dir())
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+        for (key, value) in &self.envs {
+            command.env(key, value);
+        }
+        sys::apply_limits(&mut command, &self.limits);
+
+        let started = Instant::now();
+        let mut child = command.spawn()?;
+
+        // Both pipes share one output budget. Once it is used up, the readers stop collecting
+        // and raise a flag so the program gets killed below.
+        let budget = Arc::new(AtomicUsize::new(self.limits.output));
+        let exceeded = Arc::new(AtomicBool::new(false));
+        let echo_stdout = self
+            .echo
+            .then(|| Box::new(io::stdout()) as Box<dyn Write + Send>);
+        let echo_stderr = self
+            .echo
+            .then(|| Box::new(io::stderr()) as Box<dyn Write + Send>);
+        let stdout = collect(child.stdout.take(), echo_stdout, &budget, &exceeded);
+        let stderr = collect(child.stderr.take(), echo_stderr, &budget, &exceeded);
+
+        let mut limit = None;
+        let (status, cpu_time) = loop {
+            if let Some(exited) = sys::try_wait(&mut child)? {
+                break exited;
+            }
+            if exceeded.load(Ordering::SeqCst) {
+                limit = Some(Limit::Output);
+            } else if started.elapsed() > self.limits.wall_time {
+                limit = Some(Limit::WallTime);
+            }
+            if limit.is_some() {
+                sys::kill_group(&mut child);
+                break sys::wait(&mut child)?;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+        // The program may have left children behind in its process group.
+        sys::kill_group(&mut child);
+
+        let stdout = stdout.join().unwrap_or_default();
+        let stderr = stderr.join().unwrap_or_default();
+        let stdout = String::from_utf8_lossy(&stdout).into_owned();
+        let stderr = String::from_utf8_lossy(&stderr).into_owned();
+
+        if limit.is_none() {
+            limit = sys::limit_from_status(&status, cpu_time, &self.limits, &stderr);
+        }
+        if limit.is_none() && exceeded.load(Ordering::SeqCst) {
+            limit = Some(Limit::Output);
+        }
+
+        Ok(Run {
+            status,
+            stdout,
+            stderr,
+            limit,
+        })
+    }
+}
+
+fn collect<R: Read + Send + 'static>(
+    pipe: Option<R>,
+    mut echo: Option<Box<dyn Write + Send>>,
+    budget: &Arc<AtomicUsize>,
+    exceeded: &Arc<AtomicBool>,
+) -> thread::JoinHandle<Vec<u8>> {
+    let budget = Arc::clone(budget);
+    let exceeded = Arc::clone(exceeded);
+    thread::spawn(move || {
+        let mut collected = Vec::new();
+        let mut pipe = match pipe {
+            Some(pipe) => pipe,
+            None => return collected,
+        };
+        let mut buffer = [0; 8192];
+        loop {
+            let n = match pipe.read(&mut buffer) {
+                Ok(0) | Err(_) => break,
+                Ok(n) => n,
+            };
+            let allowed = budget
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |left| {
+                    Some(left.saturating_sub(n))
+                })
+                .unwrap_or(0);
+            let chunk = &buffer[..n.min(allowed)];
+            collected.extend_from_slice(chunk);
+            if let Some(echo) = echo.as_mut() {
+                let _ = echo.write_all(chunk);
+                let _ = echo.flush();
+            }
+            if n > allowed {
+                exceeded.store(true, Ordering::SeqCst);
+                break;
+            }
+        }
+        collected
+    })
+}
+
+#[cfg(unix)]
+mod sys {
+    use std::io;
+    use std::os::raw::c_long;
+    use std::os::unix::process::{CommandExt, ExitStatusExt};
+    use std::process::{Child, Command, ExitStatus};
+    use std::time::Duration;
+
+    use super::{Limit, Limits};
+
+    #[repr(C)]
+    struct RLimit {
+        rlim_cur: u64,
+        rlim_max: u64,
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct TimeVal {
+        sec: c_long,
+        usec: c_long,
+    }
+
+    // Only the CPU times are read; the other fields are all `long`s.
+    #[repr(C)]
+    #[derive(Default)]
+    struct RUsage {
+        user: TimeVal,
+        system: TimeVal,
+        rest: [c_long; 14],
+    }
+
+    extern "C" {
+        fn setrlimit(resource: i32, rlim: *const RLimit) -> i32;
+        fn setpgid(pid: i32, pgid: i32) -> i32;
+        fn kill(pid: i32, sig: i32) -> i32;
+        fn wait4(pid: i32, status: *mut i32, options: i32, rusage: *mut RUsage) -> i32;
+    }
+
+    const RLIMIT_CPU: i32 = 0;
+    const RLIMIT_CORE: i32 = 4;
+    #[cfg(target_os = "linux")]
+    const RLIMIT_AS: i32 = 9;
+    #[cfg(not(target_os = "linux"))]
+    const RLIMIT_AS: i32 = 5;
+
+    const SIGKILL: i32 = 9;
+    const SIGXCPU: i32 = 24;
+    const WNOHANG: i32 = 1;
+
+    pub fn apply_limits(command: &mut Command, limits: &Limits) {
+        let cpu = limits.cpu_time.as_secs().max(1);
+        let memory = limits.memory;
+        // SAFETY: the closure runs in the forked child before `exec`, and only makes
+        // async-signal-safe system calls.
+        unsafe {
+            command.pre_exec(move || {
+                // A process group of its own, so we can kill everything it starts.
+                setpgid(0, 0);
+                // The soft CPU limit sends SIGXCPU; the hard limit one second later is a backstop.
+                set(RLIMIT_CPU, cpu, cpu + 1);
+                set(RLIMIT_AS, memory, memory);
+                set(RLIMIT_CORE, 0, 0);
+                Ok(())
+            });
+        }
+    }
+
+    fn set(resource: i32, soft: u64, hard: u64) {
+        let limit = RLimit {
+            rlim_cur: soft,
+            rlim_max: hard,
+        };
+        unsafe {
+            setrlimit(resource, &limit);
+        }
+    }
+
+    pub fn kill_group(child: &mut Child) {
+        let pgid = child.id() as i32;
+        unsafe {
+            kill(-pgid, SIGKILL);
+        }
+    }
+
+    // Like `Child::try_wait`, but `wait4` also tells how much CPU time the program used.
+    pub fn try_wait(child: &mut Child) -> io::Result<Option<(ExitStatus, Duration)>> {
+        reap(child, WNOHANG)
+    }
+
+    pub fn wait(child: &mut Child) -> io::Result<(ExitStatus, Duration)> {
+        loop {
+            if let Some(exited) = reap(child, 0)? {
+                return Ok(exited);
+            }
+        }
+    }
+
+    fn reap(child: &mut Child, options: i32) -> io::Result<Option<(ExitStatus, Duration)>> {
+        let mut status = 0;
+        let mut usage = RUsage::default();
+        // SAFETY: both pointers are to live values of the types `wait4` writes.
+        let pid = unsafe { wait4(child.id() as i32, &mut status, options, &mut usage) };
+        if pid == 0 {
+            return Ok(None);
+        }
+        if pid < 0 {
+            let error = io::Error::last_os_error();
+            return match error.kind() {
+                io::ErrorKind::Interrupted => Ok(None),
+                _ => Err(error),
+            };
+        }
+        let seconds = |time: &TimeVal| {
+            Duration::from_secs(time.sec as u64) + Duration::from_micros(time.usec as u64)
+        };
+        let cpu_time = seconds(&usage.user) + seconds(&usage.system);
+        Ok(Some((ExitStatus::from_raw(status), cpu_time)))
+    }
+
+    pub fn limit_from_status(
+        status: &ExitStatus,
+        cpu_time: Duration,
+        limits: &Limits,
+        stderr: &str,
+    ) -> Option<Limit> {
+        match status.signal() {
+            Some(SIGXCPU) => Some(Limit::CpuTime),
+            // The hard CPU limit is enforced with SIGKILL, but so is the OOM killer, or a
+            // `kill -9` from outside: only blame the limit if the program got that far.
+            Some(SIGKILL) if cpu_time >= limits.cpu_time => Some(Limit::CpuTime),
+            // Rust aborts when an allocation fails.
+            _ if stderr.contains("memory allocation of") => Some(Limit::Memory),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(not(unix))]
+mod sys {
+    use std::io;
+    use std::process::{Child, Command, ExitStatus};
+    use std::time::Duration;
+
+    use super::{Limit, Limits};
+
+    // Only the wall-clock and output limits are enforced here.
+    pub fn apply_limits(_command: &mut Command, _limits: &Limits) {}
+
+    pub fn kill_group(child: &mut Child) {
+        let _ = child.kill();
+    }
+
+    // CPU time isn't measured here, so it's always reported as zero.
+    pub fn try_wait(child: &mut Child) -> io::Result<Option<(ExitStatus, Duration)>> {
+        Ok(child.try_wait()?.map(|status| (status, Duration::ZERO)))
+    }
+
+    pub fn wait(child: &mut Child) -> io::Result<(ExitStatus, Duration)> {
+        Ok((child.wait()?, Duration::ZERO))
+    }
+
+    pub fn limit_from_status(
+        _status: &ExitStatus,
+        _cpu_time: Duration,
+        _limits: &Limits,
+        stderr: &str,
+    ) -> Option<Limit> {
+        if stderr.contains("memory allocation of") {
+            Some(Limit::Memory)
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,449 @@
+// `rust_tutor submit`: packs a learner's exercise solutions into one tar archive for the
+// instructor, and `rust_tutor verify-submission` checks such an archive.
+//
+// The archive holds the learner's copies of the exercises, a progress summary, and a
+// `MANIFEST.txt` naming the learner, the time, the toolchain and the curriculum version, with
+// the SHA-256 of every assigned file the learner worked from and of every other file in the
+// archive, and an HMAC-SHA256 of all that:
+//
+//     learner alice
+//     submitted 1760000000
+//     toolchain rustc 1.82.0 (f6e511eec 2024-10-15)
+//     curriculum 677fd9d1a1d6c42b
+//     assigned 5891b5b5...  exercises/03_ownership/first_word/hidden_tests.rs
+//     file 9f86d081...  exercises/03_ownership/first_word.rs
+//     mac 4b393abc...
+//
+// The HMAC key is the learner's own: the instructor keeps one course secret in
+// `$RUST_TUTOR_COURSE_SECRET`, `rust_tutor learner-key alice` derives alice's key from it, and
+// alice passes that to `submit --key`. So only alice, or the instructor, can sign as alice,
+// and `verify-submission` checks the signature with the key it derives the same way. It then
+// compares the assigned files' checksums with the assignment's MANIFEST, so a submission
+// worked against other tests or exercises than the ones handed out doesn't verify.
+//
+// Alice can still re-sign a changed archive herself. `submit` also prints the SHA-256 of the
+// manifest as a receipt: a learner who hands it in before the deadline (by mail, or in the
+// course platform) can't change the archive afterwards without the receipt no longer matching.
+
+use std::env;
+use std::fs;
+use std::path::Path;
+
+use crate::achievements;
+use crate::classroom::{self, MANIFEST};
+use crate::compiler;
+use crate::exercises::{self, Exercise};
+use crate::progress::{self, Store};
+use crate::sha256;
+use crate::tar::{self, Entry};
+use crate::{Context, Report};
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Manifest {
+    pub learner: String,
+    pub submitted: u64,
+    pub toolchain: String,
+    pub curriculum: String,
+    // Path and SHA-256 of every assigned file the learner worked from, as the assignment's
+    // manifest lists them.
+    pub assigned: Vec<(String, String)>,
+    // Path and SHA-256 of every file in the archive other than the manifest.
+    pub files: Vec<(String, String)>,
+    // HMAC-SHA256 of everything above, under the learner's key.
+    pub mac: String,
+}
+
+// Where the instructor keeps the secret that every learner's key is derived from.
+pub const SECRET_ENV: &str = "RUST_TUTOR_COURSE_SECRET";
+// Where a learner can keep their key instead of passing `--key` every time.
+pub const KEY_ENV: &str = "RUST_TUTOR_SUBMIT_KEY";
+
+// The key `learner` signs submissions with. It is derived from the course secret, so the
+// instructor doesn't need to keep a list of keys.
+pub fn learner_key(secret: &str, learner: &str) -> String {
+    let label = format!("rust_tutor learner {}", learner);
+    sha256::hmac_hex(secret.as_bytes(), label.as_bytes())
+}
+
+impl Manifest {
+    pub fn parse(text: &str) -> crate::Result<Manifest> {
+        let mut manifest = Manifest::default();
+        for line in text.lines().filter(|l| !l.trim().is_empty()) {
+            let (key, value) = line.split_once(' ').unwrap_or((line, ""));
+            match key {
+                "learner" => manifest.learner = value.to_string(),
+                "submitted" => manifest.submitted = value.parse()?,
+                "toolchain" => manifest.toolchain = value.to_string(),
+                "curriculum" => manifest.curriculum = value.to_string(),
+                "assigned" | "file" => {
+                    let (hash, path) = value
+                        .split_once("  ")
+                        .with_context(|| format!("bad manifest line `{}`", line))?;
+                    let list = if key == "assigned" {
+                        &mut manifest.assigned
+                    } else {
+                        &mut manifest.files
+                    };
+                    list.push((path.to_string(), hash.to_string()));
+                }
+                "mac" => manifest.mac = value.to_string(),
+                _ => {}
+            }
+        }
+        Ok(manifest)
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = self.signed_text();
+        if !self.mac.is_empty() {
+            out.push_str(&format!("mac {}\n", self.mac));
+        }
+        out
+    }
+
+    // Every line but the `mac` one, which is what the MAC covers.
+    fn signed_text(&self) -> String {
+        let mut out = format!(
+            "learner {}\nsubmitted {}\ntoolchain {}\ncurriculum {}\n",
+            self.learner, self.submitted, self.toolchain, self.curriculum
+        );
+        for (path, hash) in &self.assigned {
+            out.push_str(&format!("assigned {}  {}\n", hash, path));
+        }
+        for (path, hash) in &self.files {
+            out.push_str(&format!("file {}  {}\n", hash, path));
+        }
+        out
+    }
+
+    fn sign(&mut self, key: &str) {
+        self.mac = sha256::hmac_hex(key.as_bytes(), self.signed_text().as_bytes());
+    }
+}
+
+// What the learner has done, for the instructor to read.
+fn summary(store: &Store, exercises: &[Exercise]) -> crate::Result<String> {
+    let events = store.events()?;
+    let count = |kind: &str, subject: &str| {
+        events
+            .iter()
+            .filter(|e| e.kind == kind && e.subject == subject)
+            .count()
+    };
+    let mut out = String::new();
+    for exercise in exercises {
+        let subject = exercise.subject();
+        let status = if count("solved", &subject) > 0 {
+            "solved"
+        } else if count("attempted", &subject) > 0 {
+            "attempted"
+        } else if count("started", &subject) > 0 {
+            "started"
+        } else {
+            "not started"
+        };
+        out.push_str(&format!(
+            "{}: {}, {} check(s), {} hint(s)\n",
+            exercise.id,
+            status,
+            count("solved", &subject) + count("attempted", &subject),
+            count("hint", &subject)
+        ));
+    }
+    for event in events.iter().filter(|e| e.kind == "achieved") {
+        out.push_str(&format!(
+            "{} on {}\n",
+            event.subject,
+            achievements::date(event.time)
+        ));
+    }
+    Ok(out)
+}
+
+// Builds the archive, signed with `key`, returning it along with the receipt.
+pub fn pack(
+    store: &Store,
+    exercises: &[Exercise],
+    curriculum: &str,
+    key: &str,
+) -> crate::Result<(Vec<u8>, String)> {
+    let mut entries = vec![Entry {
+        path: String::from("progress.txt"),
+        data: summary(store, exercises)?.into_bytes(),
+    }];
+    for exercise in exercises {
+        let file = exercise.workspace_file(store);
+        if file.exists() {
+            entries.push(Entry {
+                path: format!("exercises/{}.rs", exercise.id),
+                data: fs::read(&file)?,
+            });
+        }
+    }
+
+    let mut manifest = Manifest {
+        learner: store.name(),
+        submitted: progress::now(),
+        toolchain: compiler::version()?,
+        curriculum: curriculum.to_string(),
+        assigned: classroom::assigned_files(exercises)?,
+        files: entries
+            .iter()
+            .map(|e| (e.path.clone(), sha256::hex(&e.data)))
+            .collect(),
+        mac: String::new(),
+    };
+    manifest.sign(key);
+    let manifest = manifest.render().into_bytes();
+    let receipt = sha256::hex(&manifest);
+    entries.insert(
+        0,
+        Entry {
+            path: String::from(MANIFEST),
+            data: manifest,
+        },
+    );
+    Ok((tar::write(&entries, progress::now())?, receipt))
+}
+
+pub struct Verified {
+    pub manifest: Manifest,
+    pub entries: Vec<Entry>,
+    pub problems: Vec<String>,
+}
+
+// What a submission is checked against, besides its own manifest. Whatever is `None` is not
+// checked.
+#[derive(Default)]
+pub struct Expected<'a> {
+    // The course secret the learner's key is derived from.
+    pub secret: Option<&'a str>,
+    // The manifest of the assignment, with its curriculum version and every file handed out.
+    pub assignment: Option<&'a classroom::Manifest>,
+    pub receipt: Option<&'a str>,
+}
+
+// Checks the archive against its manifest, and against what the instructor expects.
+pub fn verify(archive: &[u8], expected: &Expected) -> crate::Result<Verified> {
+    let entries = tar::read(archive)?;
+    let manifest_data = &entries
+        .iter()
+        .find(|e| e.path == MANIFEST)
+        .context("the archive has no MANIFEST.txt")?
+        .data;
+    let manifest = Manifest::parse(&String::from_utf8_lossy(manifest_data))?;
+
+    let mut problems = Vec::new();
+    if let Some(secret) = expected.secret {
+        let mut signed = manifest.clone();
+        signed.sign(&learner_key(secret, &manifest.learner));
+        if manifest.mac.is_empty() {
+            problems.push(String::from("the submission isn't signed"));
+        } else if signed.mac != manifest.mac {
+            problems.push(format!(
+                "the signature doesn't match: the manifest was changed, \
+                 or not signed with {}'s key",
+                manifest.learner
+            ));
+        }
+    }
+    if let Some(receipt) = expected.receipt {
+        if sha256::hex(manifest_data) != receipt.to_lowercase() {
+            problems.push(String::from(
+                "the manifest doesn't match the receipt: it was changed after submitting",
+            ));
+        }
+    }
+    if let Some(assignment) = expected.assignment {
+        problems.extend(against_assignment(&manifest, assignment));
+    }
+    for (path, hash) in &manifest.files {
+        match entries.iter().find(|e| &e.path == path) {
+            Some(entry) if sha256::hex(&entry.data) == *hash => {}
+            Some(_) => problems.push(format!("{} has been changed", path)),
+            None => problems.push(format!("{} is missing", path)),
+        }
+    }
+    for entry in &entries {
+        if entry.path != MANIFEST && !manifest.files.iter().any(|(p, _)| *p == entry.path) {
+            problems.push(format!("{} is not in the manifest", entry.path));
+        }
+    }
+    Ok(Verified {
+        manifest,
+        entries,
+        problems,
+    })
+}
+
+// How the assigned files the learner worked from differ from what `assignment` handed out.
+fn against_assignment(manifest: &Manifest, assignment: &classroom::Manifest) -> Vec<String> {
+    let mut problems = Vec::new();
+    if manifest.curriculum != assignment.curriculum {
+        problems.push(format!(
+            "submitted for curriculum {}, but {} was assigned",
+            manifest.curriculum, assignment.curriculum
+        ));
+    }
+    for (path, hash) in &manifest.assigned {
+        match assignment.files.iter().find(|(p, _)| p == path) {
+            Some((_, assigned)) if assigned == hash => {}
+            Some(_) => problems.push(format!("{} is not the assigned version", path)),
+            None => problems.push(format!("{} was not assigned", path)),
+        }
+    }
+    // Every file handed out with an exercise the learner submitted must have been accounted for.
+    for (path, _) in &manifest.files {
+        let Some(id) = path
+            .strip_prefix("exercises/")
+            .and_then(|p| p.strip_suffix(".rs"))
+        else {
+            continue;
+        };
+        let dir = format!("exercises/{}/", id);
+        for (assigned, _) in assignment.files.iter().filter(|(p, _)| p.starts_with(&dir)) {
+            if !manifest.assigned.iter().any(|(p, _)| p == assigned) {
+                problems.push(format!("{} has no checksum in the submission", assigned));
+            }
+        }
+    }
+    problems
+}
+
+// Unpacks a verified archive as `<dir>/<learner>/...`, the layout `rust_tutor grade` expects.
+pub fn extract(verified: &Verified, dir: &Path) -> crate::Result<()> {
+    let learner = &verified.manifest.learner;
+    if learner.is_empty() || learner.contains(['/', '\\']) || learner.starts_with('.') {
+        return Err(Report::msg(format!(
+            "`{}` can't be used as a directory name",
+            learner
+        )));
+    }
+    for entry in &verified.entries {
+        // Only files named in the manifest, and never outside the learner's directory.
+        let listed = verified
+            .manifest
+            .files
+            .iter()
+            .any(|(p, _)| *p == entry.path);
+        if !listed
+            || entry
+                .path
+                .split('/')
+                .any(|part| part == ".." || part.is_empty())
+        {
+            continue;
+        }
+        let target = dir.join(learner).join(&entry.path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(target, &entry.data)?;
+    }
+    Ok(())
+}
+
+// `rust_tutor submit [--bundle <dir>] [--key <key>] [--out <file>]`
+pub fn submit_command(
+    store: &Store,
+    bundle: Option<&Path>,
+    key: Option<&str>,
+    out: Option<&Path>,
+) -> crate::Result<()> {
+    let key = match key {
+        Some(key) => key.to_string(),
+        None => env::var(KEY_ENV).map_err(|_| {
+            Report::msg(format!(
+                "submissions are signed with your own key: pass the one your instructor gave you \
+                 with --key, or set ${}",
+                KEY_ENV
+            ))
+        })?,
+    };
+    let (exercises, curriculum) = match bundle {
+        Some(bundle) => {
+            let manifest = classroom::read_manifest(bundle)?;
+            (classroom::open_bundle(bundle)?, manifest.curriculum)
+        }
+        None => (exercises::all()?, classroom::curriculum_version()?),
+    };
+    let (archive, receipt) = pack(store, &exercises, &curriculum, &key)?;
+    let default_out = format!("{}-submission.tar", store.name());
+    let out = out.unwrap_or(Path::new(&default_out));
+    fs::write(out, archive)?;
+    println!("Wrote {}.", out.display());
+    println!("\nYour receipt: {}", receipt);
+    println!("Hand it in along with the archive; it shows the archive wasn't changed later.");
+    Ok(())
+}
+
+// `rust_tutor verify-submission <archive> [--bundle <dir>] [--receipt <sha256>] [--extract <dir>]`
+pub fn verify_command(
+    archive: &Path,
+    bundle: Option<&Path>,
+    receipt: Option<&str>,
+    extract_to: Option<&Path>,
+) -> crate::Result<()> {
+    let secret = course_secret()?;
+    let assignment = match bundle {
+        Some(bundle) => classroom::read_manifest(bundle)?,
+        None => {
+            let all = exercises::all()?;
+            classroom::Manifest {
+                curriculum: classroom::curriculum_version()?,
+                exercises: all.iter().map(|e| e.id.clone()).collect(),
+                files: classroom::assigned_files(&all)?,
+            }
+        }
+    };
+    let expected = Expected {
+        secret: Some(&secret),
+        assignment: Some(&assignment),
+        receipt,
+    };
+    let bytes = fs::read(archive).with_context(|| format!("reading {}", archive.display()))?;
+    let verified = verify(&bytes, &expected)
+        .with_context(|| format!("{} is not a valid submission", archive.display()))?;
+    let manifest = &verified.manifest;
+    println!("learner     {}", manifest.learner);
+    println!("submitted   {}", achievements::date(manifest.submitted));
+    println!("toolchain   {}", manifest.toolchain);
+    println!("curriculum  {}", manifest.curriculum);
+    println!("files       {}", manifest.files.len());
+    if !verified.problems.is_empty() {
+        return Err(Report::msg(format!(
+            "the submission does not verify:\n  {}",
+            verified.problems.join("\n  ")
+        )));
+    }
+    if receipt.is_none() {
+        println!("\nWithout --receipt, the learner could have re-signed it after the deadline.");
+    }
+    if let Some(dir) = extract_to {
+        extract(&verified, dir)?;
+        println!("Extracted into {}.", dir.join(&manifest.learner).display());
+    }
+    println!("OK");
+    Ok(())
+}
+
+fn course_secret() -> crate::Result<String> {
+    env::var(SECRET_ENV).map_err(|_| {
+        Report::msg(format!(
+            "set ${} to the course secret the learners' keys come from",
+            SECRET_ENV
+        ))
+    })
+}
+
+// `rust_tutor learner-key <learner...>`
+pub fn learner_key_command(learners: &[&str]) -> crate::Result<()> {
+    if learners.is_empty() {
+        return Err(Report::msg("usage: rust_tutor learner-key <learner...>"));
+    }
+    let secret = course_secret()?;
+    for learner in learners {
+        println!("{}  {}", learner, learner_key(&secret, learner));
+    }
+    Ok(())
+}
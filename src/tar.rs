@@ -0,0 +1,131 @@
+// Just enough of the ustar format to write and read learner submissions: regular files only,
+// all owned by nobody in particular. The archives open with any `tar`.
+
+use std::io;
+
+const BLOCK: usize = 512;
+
+pub struct Entry {
+    pub path: String,
+    pub data: Vec<u8>,
+}
+
+fn invalid(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+// Writes `value` as zero-padded octal followed by a NUL, filling `field`.
+fn octal(field: &mut [u8], value: u64) {
+    let digits = format!("{:0width$o}", value, width = field.len() - 1);
+    field[..digits.len()].copy_from_slice(digits.as_bytes());
+    field[digits.len()] = 0;
+}
+
+// Paths longer than 100 bytes are split at a `/` into the 155-byte prefix and the name.
+fn split_path(path: &str) -> io::Result<(&str, &str)> {
+    if path.len() <= 100 {
+        return Ok(("", path));
+    }
+    path.char_indices()
+        .filter(|&(i, c)| c == '/' && i <= 155 && path.len() - i - 1 <= 100)
+        .map(|(i, _)| (&path[..i], &path[i + 1..]))
+        .next()
+        .ok_or_else(|| invalid(format!("path too long for a tar archive: {}", path)))
+}
+
+fn header(entry: &Entry, mtime: u64) -> io::Result<[u8; BLOCK]> {
+    let mut header = [0u8; BLOCK];
+    let (prefix, name) = split_path(&entry.path)?;
+    header[..name.len()].copy_from_slice(name.as_bytes());
+    octal(&mut header[100..108], 0o644);
+    octal(&mut header[108..116], 0);
+    octal(&mut header[116..124], 0);
+    octal(&mut header[124..136], entry.data.len() as u64);
+    octal(&mut header[136..148], mtime);
+    header[156] = b'0';
+    header[257..263].copy_from_slice(b"ustar\0");
+    header[263..265].copy_from_slice(b"00");
+    header[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+
+    // The checksum is computed with its own field filled with spaces.
+    header[148..156].copy_from_slice(b"        ");
+    let sum: u64 = header.iter().map(|&b| u64::from(b)).sum();
+    let digits = format!("{:06o}\0 ", sum);
+    header[148..156].copy_from_slice(digits.as_bytes());
+    Ok(header)
+}
+
+pub fn write(entries: &[Entry], mtime: u64) -> io::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for entry in entries {
+        out.extend_from_slice(&header(entry, mtime)?);
+        out.extend_from_slice(&entry.data);
+        out.resize(out.len().next_multiple_of(BLOCK), 0);
+    }
+    // Two empty blocks mark the end of the archive.
+    out.resize(out.len() + 2 * BLOCK, 0);
+    Ok(out)
+}
+
+// A NUL-terminated string field.
+fn text(field: &[u8]) -> String {
+    let end = field.iter().position(|&b| b == 0).unwrap_or(field.len());
+    String::from_utf8_lossy(&field[..end]).into_owned()
+}
+
+fn parse_octal(field: &[u8]) -> io::Result<u64> {
+    let digits = text(field);
+    u64::from_str_radix(digits.trim(), 8)
+        .map_err(|_| invalid(format!("bad number in tar header: {:?}", digits)))
+}
+
+// Reads the regular files of an archive. Directories and other entries are skipped.
+pub fn read(archive: &[u8]) -> io::Result<Vec<Entry>> {
+    let mut entries = Vec::new();
+    let mut offset = 0;
+    while offset + BLOCK <= archive.len() {
+        let header = &archive[offset..offset + BLOCK];
+        if header.iter().all(|&b| b == 0) {
+            break;
+        }
+        let stored = parse_octal(&header[148..156])?;
+        let sum: u64 = header
+            .iter()
+            .enumerate()
+            .map(|(i, &b)| {
+                if (148..156).contains(&i) {
+                    u64::from(b' ')
+                } else {
+                    u64::from(b)
+                }
+            })
+            .sum();
+        if sum != stored {
+            return Err(invalid(format!(
+                "bad tar header checksum at byte {}",
+                offset
+            )));
+        }
+
+        let size = parse_octal(&header[124..136])? as usize;
+        let start = offset + BLOCK;
+        let data = archive
+            .get(start..start + size)
+            .ok_or_else(|| invalid(String::from("tar archive is truncated")))?;
+        if matches!(header[156], b'0' | 0) {
+            let prefix = text(&header[345..500]);
+            let name = text(&header[..100]);
+            let path = if prefix.is_empty() {
+                name
+            } else {
+                format!("{}/{}", prefix, name)
+            };
+            entries.push(Entry {
+                path,
+                data: data.to_vec(),
+            });
+        }
+        offset = start + size.next_multiple_of(BLOCK);
+    }
+    Ok(entries)
+}
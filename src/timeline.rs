@@ -0,0 +1,181 @@
+// Draws the trace events recorded by `common/trace.rs` as an ASCII swimlane timeline:
+//
+//       time | main                       | spawned
+//      0.1ms | hi number 1 from the main.. | |
+//      0.1ms | |                          | + spawned
+//      0.2ms | |                          | hi number 1 from the spa..
+//
+// Every thread gets a lane. Lanes of threads that are done get reused by later threads, so ten
+// short-lived worker threads don't need ten columns.
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    pub seq: u64,
+    pub nanos: u64,
+    pub lane: String,
+    pub kind: String,
+    pub detail: String,
+}
+
+// Reads the trace file format. Lines that don't parse are skipped: a thread may have been
+// killed halfway through writing one.
+pub fn parse(text: &str) -> Vec<Event> {
+    let mut events: Vec<Event> = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(5, '\t');
+            Some(Event {
+                seq: fields.next()?.parse().ok()?,
+                nanos: fields.next()?.parse().ok()?,
+                lane: unescape(fields.next()?),
+                kind: fields.next()?.to_string(),
+                detail: unescape(fields.next()?),
+            })
+        })
+        .collect();
+    events.sort_by_key(|e| e.seq);
+    events
+}
+
+// Undoes the escaping of tabs, newlines and backslashes done by the `common` recorders.
+pub(crate) fn unescape(text: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c == '\\' {
+            match chars.next() {
+                Some('t') => unescaped.push('\t'),
+                Some('n') => unescaped.push('\n'),
+                Some(other) => unescaped.push(other),
+                None => {}
+            }
+        } else {
+            unescaped.push(c);
+        }
+    }
+    unescaped
+}
+
+struct Lane {
+    name: String,
+    // Index of the first and last event of this lane.
+    first: usize,
+    last: usize,
+    column: usize,
+}
+
+// Assigns every lane a column, reusing the columns of lanes that have finished.
+// The main thread always gets the first column.
+fn lanes(events: &[Event]) -> Vec<Lane> {
+    let mut lanes: Vec<Lane> = Vec::new();
+    for (i, event) in events.iter().enumerate() {
+        match lanes.iter_mut().find(|lane| lane.name == event.lane) {
+            Some(lane) => lane.last = i,
+            None => lanes.push(Lane {
+                name: event.lane.clone(),
+                first: i,
+                last: i,
+                column: 0,
+            }),
+        }
+    }
+    // The main thread is alive for the whole program, even while it is not doing anything.
+    if let Some(main) = lanes.iter().position(|lane| lane.name == "main") {
+        let mut main = lanes.remove(main);
+        main.first = 0;
+        main.last = events.len() - 1;
+        lanes.insert(0, main);
+    }
+
+    // For every column, the index of the last event of the lane that currently uses it.
+    let mut busy_until: Vec<usize> = Vec::new();
+    for lane in lanes.iter_mut() {
+        lane.column = match busy_until.iter().position(|&until| until < lane.first) {
+            Some(column) => column,
+            None => {
+                busy_until.push(0);
+                busy_until.len() - 1
+            }
+        };
+        busy_until[lane.column] = lane.last;
+    }
+    lanes.sort_by_key(|lane| lane.first);
+    lanes
+}
+
+fn describe(event: &Event) -> String {
+    let (channel, value) = event
+        .detail
+        .split_once(' ')
+        .unwrap_or((event.detail.as_str(), ""));
+    match event.kind.as_str() {
+        "spawned" => format!("+ {}", event.detail),
+        "joined" => format!("joined {}", event.detail),
+        "sent" => format!("{}.send({})", channel, value),
+        "received" => format!("{} got {}", channel, value),
+        "locked" => format!("[locked {}]", event.detail),
+        "unlocked" => format!("[unlocked {}]", event.detail),
+        _ => event.detail.clone(),
+    }
+}
+
+// Pads or cuts `text` to exactly `width` characters. Tabs and newlines from a traced message
+// would break the row, so they become spaces.
+fn fit(text: &str, width: usize) -> String {
+    let text: String = text
+        .chars()
+        .map(|c| if c.is_whitespace() { ' ' } else { c })
+        .collect();
+    let length = text.chars().count();
+    if length <= width {
+        format!("{}{}", text, " ".repeat(width - length))
+    } else {
+        let cut: String = text.chars().take(width.saturating_sub(2)).collect();
+        format!("{}..", cut)
+    }
+}
+
+// Renders the timeline to fit into roughly `width` columns of text.
+pub fn render(events: &[Event], width: usize) -> String {
+    let lanes = lanes(events);
+    let columns = lanes.iter().map(|l| l.column + 1).max().unwrap_or(0);
+    if columns == 0 {
+        return String::new();
+    }
+    const TIME_WIDTH: usize = 9;
+    let cell = (width.saturating_sub(TIME_WIDTH) / columns)
+        .saturating_sub(3)
+        .max(12);
+
+    let mut out = String::new();
+    let mut header = format!("{:>w$}", "time", w = TIME_WIDTH);
+    for column in 0..columns {
+        let first = lanes.iter().find(|l| l.column == column).unwrap();
+        header.push_str(" | ");
+        header.push_str(&fit(&first.name, cell));
+    }
+    out.push_str(header.trim_end());
+    out.push('\n');
+    out.push_str(&"-".repeat(header.trim_end().len()));
+    out.push('\n');
+
+    for (i, event) in events.iter().enumerate() {
+        let millis = event.nanos as f64 / 1_000_000.0;
+        let mut row = format!("{:>w$.1}ms", millis, w = TIME_WIDTH - 2);
+        for column in 0..columns {
+            let occupant = lanes
+                .iter()
+                .find(|l| l.column == column && l.first <= i && i <= l.last);
+            let text = match occupant {
+                Some(lane) if lane.name == event.lane => describe(event),
+                Some(_) => String::from("|"),
+                None => String::new(),
+            };
+            row.push_str(" | ");
+            row.push_str(&fit(&text, cell));
+        }
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
@@ -1,6 +1,12 @@
 // Ownership is Rust's most unique feature.
 // It enables Rust to make memory safety guarantees without needing a garbage collector.
 
+// `memory::Checkpoint` captures where things live at a given point in the program.
+// Run this lesson with `rust_tutor run A_ownership` to see the stack and heap drawn out.
+#[path = "../../common/memory.rs"]
+#[allow(dead_code)]
+mod memory;
+
 fn main() {
     // VARIABLE SCOPE
     // A scope is the range within a program for which an item is valid.
@@ -8,6 +14,9 @@ fn main() {
         let s = "hello"; // s is valid from this point forward
         // do stuff with s
         println!("s is: {}", s);
+        memory::Checkpoint::new("VARIABLE SCOPE: a string literal is baked into the program")
+            .str("s", &s)
+            .emit();
     } // this scope is now over, and s is no longer valid
 
 
@@ -16,6 +25,9 @@ fn main() {
     let mut s = String::from("hello"); // `s` is valid from this point forward.
     s.push_str(", world!"); // push_str() appends a literal to a String
     println!("{}", s);
+    memory::Checkpoint::new("THE `String` TYPE: the text lives on the heap and can grow")
+        .string("s", &s)
+        .emit();
     // The memory is automatically returned once the variable that owns it goes out of scope.
 
 
@@ -23,8 +35,16 @@ fn main() {
     // When we assign a heap-allocated value (like a String) to another variable,
     // the ownership is moved.
     let s1 = String::from("hello");
+    memory::Checkpoint::new("MOVE: before `let s2 = s1;`")
+        .string("s1", &s1)
+        .emit();
     let s2 = s1; // s1 is moved to s2. s1 is no longer valid.
+    memory::Checkpoint::new("MOVE: after `let s2 = s1;` the same heap buffer now belongs to s2")
+        .moved("s1")
+        .string("s2", &s2)
+        .emit();
 
+    // COMPILE_FAIL(E0382)
     // println!("s1 is {}, s2 is {}", s1, s2); // This line would cause a compile error!
     println!("s2 is {}", s2);
 
@@ -34,6 +54,10 @@ fn main() {
     let s1 = String::from("hello");
     let s2 = s1.clone();
     println!("s1 = {}, s2 = {}", s1, s2);
+    memory::Checkpoint::new("CLONE: `s1.clone()` copies the heap data into a second buffer")
+        .string("s1", &s1)
+        .string("s2", &s2)
+        .emit();
 
 
     // OWNERSHIP AND FUNCTIONS
@@ -46,6 +70,10 @@ fn main() {
                                     // but i32 is Copy, so it's okay to still
                                     // use x afterward
     println!("x is still valid: {}", x);
+    memory::Checkpoint::new("OWNERSHIP AND FUNCTIONS: `s` was moved into the function, `x` was copied")
+        .moved("s")
+        .value("x", &x)
+        .emit();
 
 } // Here, x goes out of scope, then s. But because s's value was moved, nothing
   // special happens.
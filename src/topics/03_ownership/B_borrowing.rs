@@ -2,6 +2,11 @@
 // back to the calling function so we can still use it. This can be tedious.
 // Rust has a feature for using a value without transferring ownership, called references.
 
+// Run this lesson with `rust_tutor run B_borrowing` to see references drawn as pointers.
+#[path = "../../common/memory.rs"]
+#[allow(dead_code)]
+mod memory;
+
 fn main() {
     // REFERENCES AND BORROWING
     // A reference is like a pointer in that it’s an address we can follow to access data.
@@ -13,6 +18,13 @@ fn main() {
     // will not be dropped when the reference goes out of scope.
     let len = calculate_length(&s1);
 
+    // A reference is a pointer to `s1` itself, which in turn points to the heap.
+    let r = &s1;
+    memory::Checkpoint::new("REFERENCES: `&s1` points to s1, not to the heap data")
+        .string("s1", &s1)
+        .reference("r", &r)
+        .emit();
+
     println!("The length of '{}' is {}.", s1, len);
 
 
@@ -21,16 +33,21 @@ fn main() {
     let mut s = String::from("hello");
     change(&mut s);
     println!("Modified string: {}", s);
+    memory::Checkpoint::new("MUTABLE REFERENCES: `change` grew the string through `&mut s`")
+        .string("s", &s)
+        .emit();
 
     // A key rule of mutable references:
     // You can have only ONE mutable reference to a particular piece of data in a particular scope.
     // This prevents data races at compile time.
     let r1 = &mut s;
+    // COMPILE_FAIL(E0499)
     // let r2 = &mut s; // This would be a compile error!
     // println!("{}, {}", r1, r2);
 
     // We also cannot have a mutable reference while we have an immutable one.
     let r_immut = &s;
+    // COMPILE_FAIL(E0502)
     // let r_mut = &mut s; // COMPILE ERROR
     // println!("{}, {}", r_immut, r_mut);
 }
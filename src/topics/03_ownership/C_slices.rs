@@ -18,6 +18,7 @@ fn main() {
     // A better `first_word` using slices
     let word = first_word_slice(&s);
 
+    // COMPILE_FAIL(E0502)
     // s.clear(); // This would cause a compile error because `word` is an immutable borrow.
 
     println!("The first word is: {}", word);
@@ -25,6 +25,8 @@ fn main() {
     let s2 = String::from("world!");
     let s3 = s1 + &s2; // note s1 has been moved here and can no longer be used
     println!("Concatenated string: {}", s3);
+    // COMPILE_FAIL(E0382)
+    // println!("{}", s1); // s1 was moved by `+`
 
     let t1 = String::from("tic");
     let t2 = String::from("tac");
@@ -0,0 +1,140 @@
+// `Vec`, `String` and `HashMap` cover most programs, but `std::collections` has a few more,
+// each for a job the first three do badly: keeping keys in order, keeping values unique,
+// adding and removing at both ends, and always taking the largest value first.
+
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashSet, VecDeque};
+
+fn main() {
+    // BTREEMAP: A MAP IN KEY ORDER
+    // `HashMap` places its keys by their hash, so `println!("Scores: {:?}", scores)` in
+    // `C_hash_maps.rs` can print them in a different order on every run. A `BTreeMap` keeps its
+    // keys sorted, so iterating and printing always give the same order. It has the same
+    // `insert`, `get` and `entry` methods.
+    let mut scores = BTreeMap::new();
+    scores.insert(String::from("Yellow"), 50);
+    scores.insert(String::from("Blue"), 10);
+    scores.insert(String::from("Red"), 100);
+    println!("Scores: {:?}", scores);
+
+    let text = "hello world wonderful world";
+    let mut counts = BTreeMap::new();
+    for word in text.split_whitespace() {
+        *counts.entry(word).or_insert(0) += 1;
+    }
+    println!("Word count: {:?}", counts);
+
+    // Because the keys are in order, a `BTreeMap` can also answer questions about them.
+    let mut temperatures = BTreeMap::new();
+    for (hour, degrees) in [(6, 11), (9, 15), (12, 21), (15, 23), (18, 19), (21, 14)] {
+        temperatures.insert(hour, degrees);
+    }
+    println!("First reading: {:?}", temperatures.first_key_value());
+    println!("Last reading: {:?}", temperatures.last_key_value());
+    let afternoon: Vec<_> = temperatures.range(12..18).collect();
+    println!("Between 12 and 18: {:?}", afternoon);
+    // The last reading before 10 o'clock.
+    println!("Before 10: {:?}", temperatures.range(..10).next_back());
+
+
+    // HASHSET: UNIQUE VALUES
+    // A `HashSet<T>` is a `HashMap<T, ()>`: it only remembers whether a value is there.
+    // `insert` returns `false` when the value was already in the set.
+    let mut seen = HashSet::new();
+    for word in text.split_whitespace() {
+        if !seen.insert(word) {
+            println!("Seen before: {}", word);
+        }
+    }
+    println!("{} different words", seen.len());
+
+    // Sets combine with `union`, `intersection` and `difference`. These return iterators,
+    // in hash order again, so they are collected into a `BTreeSet`, which keeps them sorted.
+    let rust: HashSet<&str> = ["ana", "ben", "chen", "dara"].into_iter().collect();
+    let go: HashSet<&str> = ["ben", "dara", "eli"].into_iter().collect();
+    let both: BTreeSet<_> = rust.intersection(&go).collect();
+    let either: BTreeSet<_> = rust.union(&go).collect();
+    let only_rust: BTreeSet<_> = rust.difference(&go).collect();
+    println!("Both: {:?}", both);
+    println!("Either: {:?}", either);
+    println!("Only Rust: {:?}", only_rust);
+    println!("Is ben learning Go? {}", go.contains("ben"));
+
+    // `collect` into a set is also the quickest way to drop duplicates.
+    let letters: BTreeSet<char> = "mississippi".chars().collect();
+    println!("Letters in mississippi: {:?}", letters);
+
+
+    // VECDEQUE: A QUEUE WITH TWO ENDS
+    // `Vec::remove(0)` moves every other item down by one. A `VecDeque` is a ring buffer:
+    // pushing and popping at either end is cheap, which makes it the type for queues.
+    let mut queue = VecDeque::new();
+    queue.push_back("first customer");
+    queue.push_back("second customer");
+    queue.push_front("someone with an appointment");
+    println!("Queue: {:?}", queue);
+    while let Some(next) = queue.pop_front() {
+        println!("Serving {}", next);
+    }
+
+    // Keeping the last few items: push at the back, drop from the front when it's full.
+    let mut recent = VecDeque::with_capacity(3);
+    for page in ["home", "lessons", "exercises", "progress", "help"] {
+        if recent.len() == 3 {
+            recent.pop_front();
+        }
+        recent.push_back(page);
+    }
+    println!("Last 3 pages: {:?}", recent);
+    // It can still be indexed, and rotated.
+    recent.rotate_left(1);
+    println!("Rotated: {:?}, first is {}", recent, recent[0]);
+
+
+    // BINARYHEAP: LARGEST FIRST
+    // A `BinaryHeap` is a priority queue: `pop` always returns the largest item left, however
+    // they were pushed. Only the top is kept in order; the rest are in no particular order.
+    let mut heap = BinaryHeap::new();
+    for n in [3, 1, 4, 1, 5, 9, 2, 6] {
+        heap.push(n);
+    }
+    println!("Largest: {:?}", heap.peek());
+    let mut in_order = Vec::new();
+    while let Some(n) = heap.pop() {
+        in_order.push(n);
+    }
+    println!("Popped: {:?}", in_order);
+
+    // Tuples compare field by field, so `(priority, task)` pops the highest priority first.
+    // Wrapping values in `Reverse` flips the order, turning it into a min-heap.
+    let mut tasks = BinaryHeap::new();
+    tasks.push((2, "write the report"));
+    tasks.push((5, "fix the build"));
+    tasks.push((1, "water the plants"));
+    while let Some((priority, task)) = tasks.pop() {
+        println!("[{}] {}", priority, task);
+    }
+    let mut deadlines = BinaryHeap::new();
+    for day in [14, 3, 9] {
+        deadlines.push(Reverse(day));
+    }
+    if let Some(Reverse(day)) = deadlines.pop() {
+        println!("Soonest deadline: day {}", day);
+    }
+
+
+    // CHOOSING A COLLECTION
+    // - `Vec`: a list in the order you built it. The default.
+    // - `HashMap` / `HashSet`: fast lookup when the order doesn't matter.
+    // - `BTreeMap` / `BTreeSet`: lookup with the keys kept sorted, and ranges over them.
+    // - `VecDeque`: adding and removing at both ends.
+    // - `BinaryHeap`: always taking out the largest (or, with `Reverse`, smallest) item.
+    // Converting between them is a `collect` away.
+    let sorted: Vec<i32> = BinaryHeap::from(vec![5, 2, 8]).into_sorted_vec();
+    let deduped: Vec<i32> = [3, 1, 3, 2, 1]
+        .into_iter()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    println!("{:?} {:?}", sorted, deduped);
+}
@@ -0,0 +1,128 @@
+// `A_result_and_option.rs` returned `io::Error` from `read_username_from_file`. But reading a
+// username can go wrong in ways that aren't I/O errors at all: the file may be empty, or hold
+// something that isn't a valid name. A custom error type lets the function say exactly what
+// went wrong, and lets its callers decide what to do about each case.
+//
+// `rust_tutor run B_custom_errors` runs this lesson next to a few prepared files, from
+// `fixtures/B_custom_errors/`: `username.txt`, `empty.txt`, `invalid.txt` and `age.txt`.
+
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+
+// A CUSTOM ERROR ENUM
+// One variant per way the function can fail. A variant can carry the details: the underlying
+// `io::Error`, or the text that wasn't a valid name.
+#[derive(Debug)]
+enum UsernameError {
+    Io(io::Error),
+    Empty,
+    Invalid(String),
+}
+
+// DISPLAY
+// `Debug` is for programmers; `Display` is the message for the people using the program.
+// Every error type should have both, and `Error` requires them.
+impl fmt::Display for UsernameError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UsernameError::Io(_) => write!(f, "couldn't read the username file"),
+            UsernameError::Empty => write!(f, "the username file is empty"),
+            UsernameError::Invalid(name) => write!(f, "{:?} is not a valid username", name),
+        }
+    }
+}
+
+// THE ERROR TRAIT
+// Implementing `std::error::Error` makes this a proper error, usable anywhere errors are
+// expected, like in a `Box<dyn Error>`. Its `source` method returns the lower-level error that
+// caused this one, if there is one. The message above doesn't repeat the `io::Error`: whoever
+// reports the error can follow `source` to print the whole chain.
+impl Error for UsernameError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            UsernameError::Io(error) => Some(error),
+            UsernameError::Empty | UsernameError::Invalid(_) => None,
+        }
+    }
+}
+
+// FROM CONVERSIONS
+// `?` doesn't return the error it finds as is: it passes it through `From::from` first. With
+// this impl, `?` on an `io::Error` in a function returning `UsernameError` wraps it in `Io`.
+impl From<io::Error> for UsernameError {
+    fn from(error: io::Error) -> UsernameError {
+        UsernameError::Io(error)
+    }
+}
+
+// A username is 1 to 16 letters, digits or underscores.
+fn read_username_from_file(path: &str) -> Result<String, UsernameError> {
+    let text = fs::read_to_string(path)?;
+    let name = text.trim();
+    if name.is_empty() {
+        return Err(UsernameError::Empty);
+    }
+    if name.len() > 16 || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        return Err(UsernameError::Invalid(name.to_string()));
+    }
+    Ok(name.to_string())
+}
+
+// Prints an error followed by its sources, one per line.
+fn report(error: &dyn Error) {
+    println!("error: {}", error);
+    let mut source = error.source();
+    while let Some(cause) = source {
+        println!("  caused by: {}", cause);
+        source = cause.source();
+    }
+}
+
+// BOX<DYN ERROR>
+// A function that can fail in several unrelated ways can return `Box<dyn Error>`: any error
+// type converts into it with `?`. It is convenient for applications, but callers can no longer
+// `match` on what went wrong, only print it or look at its `source`.
+fn read_age(path: &str) -> Result<u8, Box<dyn Error>> {
+    let text = fs::read_to_string(path)?;
+    let age: u8 = text.trim().parse()?;
+    Ok(age)
+}
+
+// MAIN CAN RETURN RESULT
+// If `main` returns an `Err`, the program prints it with `Debug` and exits with a non-zero
+// status, so `?` works in `main` too.
+fn main() -> Result<(), Box<dyn Error>> {
+    // Matching on the enum handles each failure differently.
+    for path in ["username.txt", "empty.txt", "invalid.txt", "missing.txt"] {
+        match read_username_from_file(path) {
+            Ok(name) => println!("{}: hello, {}!", path, name),
+            Err(UsernameError::Empty) => println!("{}: no name yet, using \"guest\"", path),
+            Err(error) => {
+                print!("{}: ", path);
+                report(&error);
+            }
+        }
+    }
+
+    // `age.txt` holds `forty-two`, so `parse` fails with a `ParseIntError`, which `?` boxed.
+    // `downcast_ref` asks whether a boxed error is of a particular type.
+    match read_age("age.txt") {
+        Ok(age) => println!("age: {}", age),
+        Err(error) => {
+            let kind = if error.downcast_ref::<ParseIntError>().is_some() {
+                "not a number"
+            } else {
+                "something else"
+            };
+            println!("age.txt: {} ({})", error, kind);
+        }
+    }
+
+    // `?` converts a `UsernameError` into a `Box<dyn Error>` for `main`'s return type.
+    let name = read_username_from_file("username.txt")?;
+    println!("logged in as {}", name);
+    Ok(())
+}
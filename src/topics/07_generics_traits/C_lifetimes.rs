@@ -10,6 +10,7 @@ fn main() {
     println!("The longest string is {}", result);
 
     // Example of a dangling reference that the compiler will prevent:
+    // COMPILE_FAIL(E0597)
     // let r;
     // {
     //     let x = 5;
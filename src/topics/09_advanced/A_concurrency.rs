@@ -5,24 +5,40 @@ use std::thread;
 use std::time::Duration;
 use std::sync::{mpsc, Mutex, Arc};
 
+// `clock::sleep` works just like `thread::sleep`, but lets the tutor speed the lesson up
+// (`rust_tutor run A_concurrency --clock fast`) so you don't have to wait for every second.
+// Threads that sleep are started with `clock::spawn`, which is `thread::spawn` that also tells
+// the new thread what time it is.
+#[path = "../../common/clock.rs"]
+#[allow(dead_code)]
+mod clock;
+
+// `trace` records what each thread does (`trace::say` prints a line just like `println!`).
+// `rust_tutor run A_concurrency` draws these events as a timeline with one lane per thread.
+#[path = "../../common/trace.rs"]
+#[allow(dead_code)]
+mod trace;
+
 fn main() {
     // SECTION: CREATING A NEW THREAD WITH `spawn`
     // The `thread::spawn` function takes a closure, which is the code to be run in the new thread.
     let handle = thread::spawn(|| {
+        trace::spawned("spawned");
         for i in 1..10 {
-            println!("hi number {} from the spawned thread!", i);
-            thread::sleep(Duration::from_millis(1));
+            trace::say(&format!("hi number {} from the spawned thread!", i));
+            clock::sleep(Duration::from_millis(1));
         }
     });
 
     // The main thread will continue its execution.
     for i in 1..5 {
-        println!("hi number {} from the main thread!", i);
-        thread::sleep(Duration::from_millis(1));
+        trace::say(&format!("hi number {} from the main thread!", i));
+        clock::sleep(Duration::from_millis(1));
     }
 
     // The `join` method on the handle waits for the spawned thread to finish.
     handle.join().unwrap();
+    trace::joined("spawned");
 
 
     // SECTION: USING `move` CLOSURES WITH THREADS
@@ -31,10 +47,12 @@ fn main() {
     let v = vec![1, 2, 3];
 
     let handle2 = thread::spawn(move || {
-        println!("Here's a vector: {:?}", v);
+        trace::spawned("vector");
+        trace::say(&format!("Here's a vector: {:?}", v));
     });
 
     handle2.join().unwrap();
+    trace::joined("vector");
 
 
     // SECTION: MESSAGE PASSING WITH CHANNELS
@@ -44,7 +62,8 @@ fn main() {
 
     let tx2 = tx.clone(); // Clone the transmitter to have multiple producers
 
-    thread::spawn(move || {
+    clock::spawn(move || {
+        trace::spawned("producer 1");
         let vals = vec![
             String::from("hi"),
             String::from("from"),
@@ -52,12 +71,14 @@ fn main() {
             String::from("thread"),
         ];
         for val in vals {
+            trace::sent("tx", &val);
             tx.send(val).unwrap();
-            thread::sleep(Duration::from_secs(1));
+            clock::sleep(Duration::from_secs(1));
         }
     });
 
-    thread::spawn(move || {
+    clock::spawn(move || {
+        trace::spawned("producer 2");
         let vals = vec![
             String::from("more"),
             String::from("messages"),
@@ -65,14 +86,16 @@ fn main() {
             String::from("you"),
         ];
         for val in vals {
+            trace::sent("tx2", &val);
             tx2.send(val).unwrap();
-            thread::sleep(Duration::from_secs(1));
+            clock::sleep(Duration::from_secs(1));
         }
     });
 
     // The `recv` method on the receiver will block the main thread's execution
     // and wait until a value is sent down the channel.
     for received in rx {
+        trace::received("rx", &received);
         println!("Got: {}", received);
     }
 
@@ -86,11 +109,15 @@ fn main() {
     let counter = Arc::new(Mutex::new(0));
     let mut handles = vec![];
 
-    for _ in 0..10 {
+    for i in 0..10 {
         let counter = Arc::clone(&counter);
         let handle = thread::spawn(move || {
+            trace::spawned(&format!("counter {}", i));
             let mut num = counter.lock().unwrap();
+            trace::locked("counter");
             *num += 1;
+            drop(num); // Release the lock before saying so; otherwise `num` holds it until the end
+            trace::unlocked("counter");
         });
         handles.push(handle);
     }
@@ -4,6 +4,12 @@ use std::ops::Deref;
 use std::rc::{Rc, Weak};
 use std::cell::RefCell;
 
+// `rc_graph` draws the pointers between `Node`s along with their strong and weak counts.
+// Run `rust_tutor run B_smart_pointers --dot <dir>` to also get Graphviz files of the graphs.
+#[path = "../../common/rc_graph.rs"]
+#[allow(dead_code)]
+mod rc_graph;
+
 // SECTION: `Box<T>` for allocating values on the heap
 // Boxes allow you to store data on the heap rather than the stack.
 
@@ -61,6 +67,26 @@ struct Node {
     children: RefCell<Vec<Rc<Node>>>,
 }
 
+// Tells `rc_graph` which pointers a `Node` holds: its children strongly, its parent weakly.
+impl rc_graph::Inspect for Node {
+    fn label(&self) -> String {
+        format!("Node {}", self.value)
+    }
+
+    fn strong(&self) -> Vec<(String, Rc<Node>)> {
+        self.children
+            .borrow()
+            .iter()
+            .enumerate()
+            .map(|(i, child)| (format!("children[{}]", i), Rc::clone(child)))
+            .collect()
+    }
+
+    fn weak(&self) -> Vec<(String, Weak<Node>)> {
+        vec![(String::from("parent"), self.parent.borrow().clone())]
+    }
+}
+
 
 fn main() {
     // Using a Box<T>
@@ -120,6 +146,8 @@ fn main() {
             Rc::strong_count(&leaf),
             Rc::weak_count(&leaf),
         );
+
+        rc_graph::walk(&[("leaf", &leaf), ("branch", &branch)]).emit("tree");
     }
 
     println!("leaf parent = {:?}", leaf.parent.borrow().upgrade());
@@ -128,4 +156,28 @@ fn main() {
         Rc::strong_count(&leaf),
         Rc::weak_count(&leaf),
     );
+    rc_graph::walk(&[("leaf", &leaf)]).emit("after_branch_dropped");
+
+
+    // SECTION: What goes wrong without `Weak<T>`
+    // If children also held their parent through an `Rc`, each would keep the other alive.
+    // Once `a` and `b` go out of scope the counts never reach zero, and the memory leaks.
+    {
+        let a = Rc::new(Node {
+            value: 1,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![]),
+        });
+        let b = Rc::new(Node {
+            value: 2,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![Rc::clone(&a)]),
+        });
+        a.children.borrow_mut().push(Rc::clone(&b)); // a owns b, and b owns a
+
+        rc_graph::walk(&[("a", &a), ("b", &b)]).emit("cycle");
+
+        // Breaking the cycle by hand lets both nodes be dropped after all.
+        a.children.borrow_mut().clear();
+    }
 }
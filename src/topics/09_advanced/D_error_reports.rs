@@ -0,0 +1,150 @@
+// Error reports: what an application does with errors it can't handle, only explain.
+//
+// The `UsernameError` enum in `06_error_handling/B_custom_errors.rs` lets callers `match` on
+// what went wrong, which is what a library should offer. But most errors in an application end
+// up printed for a person, who needs to know what the program was doing at the time and why it
+// failed, all the way down. `common/report.rs` is a small type for that, and it is what
+// `rust_tutor` itself uses for its own errors. It's about 200 lines: read it alongside this.
+//
+// `rust_tutor run D_error_reports` runs this lesson next to the config files in
+// `fixtures/D_error_reports/`.
+
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+use std::str::FromStr;
+
+// THE REPORT TYPE
+// A `Report` holds any error as a `Box<dyn Error + Send + Sync>`. It implements `From` for
+// every error type, so `?` turns whatever error it meets into a report, with no enum listing
+// them all: below, `ParseIntError` and `io::Error` both become reports.
+#[path = "../../common/report.rs"]
+#[allow(dead_code)]
+mod report;
+
+use report::{Context, Report};
+
+#[derive(Debug)]
+struct Config {
+    name: String,
+    port: u16,
+    workers: usize,
+}
+
+// ADDING CONTEXT
+// "invalid digit found in string" alone doesn't say which file or which line. The `Context`
+// trait adds `.context(...)` to results: on an error, it wraps the error in a new one carrying
+// the message, with the original as its `source`. `with_context` takes a closure instead, so
+// the `format!` only runs when there is an error. On an `Option`, `None` becomes a report with
+// the message.
+fn parse_config(text: &str) -> Result<Config, Report> {
+    let (mut name, mut port, mut workers) = (None, None, None);
+    for (n, line) in text.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (key, value) = line
+            .split_once('=')
+            .with_context(|| format!("line {}: expected `key = value`", n + 1))?;
+        let value = value.trim();
+        match key.trim() {
+            "name" => name = Some(value.to_string()),
+            "port" => port = Some(number(value, "port", n + 1)?),
+            "workers" => workers = Some(number(value, "workers", n + 1)?),
+            other => {
+                let message = format!("line {}: unknown setting `{}`", n + 1, other);
+                return Err(Report::msg(message));
+            }
+        }
+    }
+    Ok(Config {
+        name: name.context("there is no `name` setting")?,
+        port: port.context("there is no `port` setting")?,
+        workers: workers.unwrap_or(1),
+    })
+}
+
+fn number<T>(value: &str, key: &str, line: usize) -> Result<T, Report>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    value
+        .parse::<T>()
+        .with_context(|| format!("line {}: `{}` is not a number", line, key))
+}
+
+// Context stacks: each function says what it was doing, and the report collects all of it.
+fn load_config(path: &str) -> Result<Config, Report> {
+    let text = fs::read_to_string(path).with_context(|| format!("reading {}", path))?;
+    parse_config(&text).with_context(|| format!("loading the config from {}", path))
+}
+
+// MAIN CAN RETURN A REPORT
+// When `main` returns an `Err`, Rust prints it with `{:?}`, which for a report is the whole
+// chain. Try changing "server.conf" at the end to "broken.conf".
+fn main() -> Result<(), Report> {
+    for path in ["server.conf", "incomplete.conf", "missing.conf"] {
+        match load_config(path) {
+            Ok(config) => println!("{}: {:?}", path, config),
+            Err(report) => println!("{}: {:#}", path, report),
+        }
+    }
+
+
+    // PRINTING A REPORT
+    // `{}` shows only the outermost message, `{:#}` the whole chain on one line, and `{:?}`
+    // one cause per line, the way `main` prints it.
+    let report = load_config("broken.conf").unwrap_err();
+    println!("\n{{}}:   {}", report);
+    println!("{{:#}}:  {:#}", report);
+    println!("{{:?}}:\n{:?}", report);
+
+
+    // THE CHAIN OF CAUSES
+    // Every error's `source` is the error that caused it; `chain` follows them from the top.
+    println!();
+    for (depth, error) in report.chain().enumerate() {
+        println!("{}{}", "  ".repeat(depth), error);
+    }
+    println!("root cause: {}", report.root_cause());
+
+    // The errors in the chain are still their original types, so a program can look for one
+    // it knows how to deal with, however deep it is.
+    if let Some(error) = report.downcast_ref::<ParseIntError>() {
+        println!("a number didn't parse: {:?}", error.kind());
+    }
+    let missing = load_config("missing.conf").unwrap_err();
+    if let Some(error) = missing.downcast_ref::<io::Error>() {
+        if error.kind() == io::ErrorKind::NotFound {
+            println!("missing.conf doesn't exist; the defaults would do");
+        }
+    }
+
+
+    // BACKTRACES
+    // A report also remembers where it was made, as a `std::backtrace::Backtrace`. Capturing
+    // one is slow, so `Backtrace::capture` only does it when the `RUST_BACKTRACE` or
+    // `RUST_LIB_BACKTRACE` environment variable is set; `{:?}` then prints it after the causes.
+    println!("\nbacktrace: {:?}", report.backtrace().status());
+
+
+    // WHY REPORT ISN'T AN ERROR
+    // `Report` implements `From<E>` for every `E: Error`. If it implemented `Error` too, that
+    // would include `From<Report> for Report`, which the standard library already provides
+    // for every type. So a report can't be passed where a `dyn Error` is expected.
+    fn describe(error: &dyn Error) -> String {
+        error.to_string()
+    }
+    println!("{}", describe(report.root_cause()));
+    // COMPILE_FAIL(E0277)
+    // println!("{}", describe(&report));
+
+    let config = load_config("server.conf")?;
+    println!(
+        "\nserving {} on port {} with {} workers",
+        config.name, config.port, config.workers
+    );
+    Ok(())
+}
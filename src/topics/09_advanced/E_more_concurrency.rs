@@ -0,0 +1,243 @@
+// More tools for sharing between threads. `A_concurrency.rs` used `Arc<Mutex<_>>` for shared
+// state and `mpsc::channel` for messages; `std::sync` has more specialised tools, each doing
+// one job better: atomics for counters, `RwLock` for data that's read far more than written,
+// `Condvar` for waiting until something changes, `Barrier` for threads that work in phases,
+// and `thread::scope` for threads that borrow instead of owning.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, TrySendError};
+use std::sync::{Arc, Barrier, Condvar, Mutex, RwLock};
+use std::thread;
+use std::time::Duration;
+
+// `rust_tutor run E_more_concurrency` draws the traced events as a timeline, as in
+// `A_concurrency.rs`.
+#[path = "../../common/clock.rs"]
+#[allow(dead_code)]
+mod clock;
+
+#[path = "../../common/trace.rs"]
+#[allow(dead_code)]
+mod trace;
+
+// A queue that holds at most `capacity` items. `push` waits while it is full, and `pop` waits
+// while it is empty. See CONDVAR below.
+struct BoundedQueue<T> {
+    items: Mutex<VecDeque<T>>,
+    capacity: usize,
+    not_empty: Condvar,
+    not_full: Condvar,
+}
+
+impl<T> BoundedQueue<T> {
+    fn new(capacity: usize) -> BoundedQueue<T> {
+        BoundedQueue {
+            items: Mutex::new(VecDeque::new()),
+            capacity,
+            not_empty: Condvar::new(),
+            not_full: Condvar::new(),
+        }
+    }
+
+    fn push(&self, item: T) {
+        let mut items = self.items.lock().unwrap();
+        // `wait` unlocks the mutex while it sleeps and locks it again before returning. It can
+        // also wake up for no reason, so the condition is checked again in a loop.
+        while items.len() == self.capacity {
+            items = self.not_full.wait(items).unwrap();
+        }
+        items.push_back(item);
+        self.not_empty.notify_one();
+    }
+
+    fn pop(&self) -> T {
+        let mut items = self.items.lock().unwrap();
+        loop {
+            if let Some(item) = items.pop_front() {
+                self.not_full.notify_one();
+                return item;
+            }
+            items = self.not_empty.wait(items).unwrap();
+        }
+    }
+}
+
+fn main() {
+    // ATOMICS
+    // The mutex counter in `A_concurrency.rs` locks, adds one and unlocks. For a plain number
+    // there is a cheaper way: an atomic type, whose operations the processor itself carries
+    // out as one indivisible step. `fetch_add` adds and returns the old value, and no other
+    // thread can see the counter halfway through. There's no lock and no guard, and an
+    // atomic only needs a `&` reference to change, so an `Arc` is all it takes to share it.
+    let atomic = Arc::new(AtomicUsize::new(0));
+    let locked = Arc::new(Mutex::new(0));
+    let mut handles = vec![];
+    for _ in 0..10 {
+        let atomic = Arc::clone(&atomic);
+        let locked = Arc::clone(&locked);
+        handles.push(thread::spawn(move || {
+            for _ in 0..1000 {
+                atomic.fetch_add(1, Ordering::Relaxed);
+                *locked.lock().unwrap() += 1;
+            }
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    println!("Atomic counter: {}", atomic.load(Ordering::Relaxed));
+    println!("Mutex counter: {}", *locked.lock().unwrap());
+
+    // The `Ordering` says what else other threads are guaranteed to see. `Relaxed` only
+    // promises that the counter itself is right, which is all a counter needs. When an atomic
+    // flag announces that other data is ready, the writer uses `Release` and the reader
+    // `Acquire`, so the reader that sees the flag also sees the data. When unsure, `SeqCst`
+    // is the strictest.
+    //
+    // An atomic only helps when the whole update is one operation. Anything bigger, like
+    // changing two numbers together, still needs a mutex. `compare_exchange` is the building
+    // block for the rest: it stores a new value only if the current one is what you expect.
+    let slot = AtomicUsize::new(0);
+    println!(
+        "Claim a free slot: {:?}",
+        slot.compare_exchange(0, 7, Ordering::SeqCst, Ordering::SeqCst)
+    );
+    println!(
+        "Claim it again: {:?}",
+        slot.compare_exchange(0, 9, Ordering::SeqCst, Ordering::SeqCst)
+    );
+
+
+    // RWLOCK
+    // A `Mutex` lets one thread in at a time, even when every thread only wants to read.
+    // An `RwLock` allows any number of readers at once, or a single writer.
+    let config = RwLock::new(vec![String::from("verbose = false")]);
+    {
+        let first = config.read().unwrap();
+        let second = config.read().unwrap();
+        println!("Two readers: {} and {} lines", first.len(), second.len());
+        // While anyone is reading, a writer has to wait. `try_write` doesn't wait; it fails.
+        println!("Can we write now? {}", config.try_write().is_ok());
+    }
+    config.write().unwrap().push(String::from("threads = 4"));
+    println!("After writing: {:?}", *config.read().unwrap());
+
+
+    // CONDVAR
+    // A thread that must wait for something, like an item in an empty queue, could lock,
+    // check and unlock in a loop, but that wastes a processor on checking. A condition
+    // variable (`Condvar`) lets it sleep until another thread calls `notify_one` or
+    // `notify_all`. It always goes with a mutex: see `BoundedQueue` above.
+    let queue = Arc::new(BoundedQueue::new(2));
+    let producer = {
+        let queue = Arc::clone(&queue);
+        thread::spawn(move || {
+            trace::spawned("producer");
+            for job in 1..=5 {
+                trace::say(&format!("queueing job {}", job));
+                // With room for only 2 jobs, this waits until the consumer catches up.
+                queue.push(job);
+            }
+        })
+    };
+    for _ in 1..=5 {
+        let job = queue.pop();
+        trace::say(&format!("working on job {}", job));
+        clock::sleep(Duration::from_millis(10));
+    }
+    producer.join().unwrap();
+    trace::joined("producer");
+
+
+    // BARRIER
+    // A `Barrier` for `n` threads makes each thread's `wait` block until all `n` have called
+    // it. Here, no worker starts checking until every worker has finished loading.
+    let workers = 4;
+    let barrier = Arc::new(Barrier::new(workers));
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let mut handles = vec![];
+    for id in 0..workers {
+        let barrier = Arc::clone(&barrier);
+        let log = Arc::clone(&log);
+        handles.push(thread::spawn(move || {
+            log.lock().unwrap().push(format!("worker {} loaded", id));
+            // Exactly one of the threads is told it is the leader, for work done only once.
+            if barrier.wait().is_leader() {
+                log.lock().unwrap().push(String::from("all workers are ready"));
+            }
+            log.lock().unwrap().push(format!("worker {} checked", id));
+        }));
+    }
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    let log = log.lock().unwrap();
+    let first_check = log.iter().position(|line| line.ends_with("checked")).unwrap();
+    let loaded = log[..first_check]
+        .iter()
+        .filter(|line| line.ends_with("loaded"))
+        .count();
+    println!("Loaded before the first check: {} of {} workers", loaded, workers);
+
+
+    // SCOPED THREADS
+    // `thread::spawn` needs a `'static` closure: the thread might outlive the function that
+    // started it, so it can't borrow that function's variables. Hence all the `Arc`s and
+    // `move`s above.
+    let numbers: Vec<u64> = (1..=1000).collect();
+    // COMPILE_FAIL(E0373)
+    // thread::spawn(|| println!("{}", numbers.len()));
+
+    // `thread::scope` promises that every thread spawned inside it is joined before it
+    // returns, so those threads can borrow local variables, even mutably.
+    let mut sums = vec![0; 4];
+    thread::scope(|s| {
+        for (chunk, sum) in numbers.chunks(250).zip(sums.iter_mut()) {
+            s.spawn(move || *sum = chunk.iter().sum());
+        }
+    });
+    println!("Sums of each quarter: {:?}", sums);
+    println!("Total: {}", sums.iter().sum::<u64>());
+
+    // `spawn` in a scope still returns a handle, whose `join` gives the closure's result.
+    let evens = thread::scope(|s| {
+        let counter = s.spawn(|| numbers.iter().filter(|n| *n % 2 == 0).count());
+        counter.join().unwrap()
+    });
+    println!("Even numbers: {}", evens);
+
+
+    // SYNC_CHANNEL: BACKPRESSURE
+    // `mpsc::channel` never makes the sender wait: a fast producer can fill memory with
+    // messages the receiver hasn't got to. `mpsc::sync_channel(n)` holds at most `n`; once
+    // it's full, `send` blocks until the receiver takes one.
+    let (tx, rx) = mpsc::sync_channel(2);
+    tx.send("one").unwrap();
+    tx.send("two").unwrap();
+    // `try_send` shows what `send` would wait for.
+    match tx.try_send("three") {
+        Err(TrySendError::Full(message)) => println!("Full, so {:?} has to wait", message),
+        other => println!("Unexpected: {:?}", other),
+    }
+    println!("Received {:?}", rx.recv().unwrap());
+    println!("Room again: {}", tx.try_send("three").is_ok());
+    drop(tx);
+    println!("The rest: {:?}", rx.iter().collect::<Vec<_>>());
+
+    // With a bound of 0, every `send` waits until a receiver takes the message: a handover.
+    let (tx, rx) = mpsc::sync_channel(0);
+    let sender = thread::spawn(move || {
+        trace::spawned("sender");
+        for n in 1..=3 {
+            trace::sent("tx", &n);
+            tx.send(n).unwrap();
+        }
+    });
+    for n in rx {
+        trace::received("rx", &n);
+        clock::sleep(Duration::from_millis(10));
+    }
+    sender.join().unwrap();
+    trace::joined("sender");
+}
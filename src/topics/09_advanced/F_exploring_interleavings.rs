@@ -0,0 +1,122 @@
+// A concurrency bug may only show up when the threads happen to run in one particular order,
+// which is why a program can pass every test and still fail in production. Rather than hoping
+// to hit that order, this lesson hands the order to the tutor, which tries them all.
+//
+// `common/explore.rs` has look-alikes of `Arc`, `Mutex`, the atomics, channels and
+// `thread::spawn`. Inside `explore::model`, only one thread runs at a time, and every lock,
+// atomic operation or message is a point where the tutor can switch to another thread.
+// `rust_tutor run F_exploring_interleavings` runs each model once; `rust_tutor explore
+// F_exploring_interleavings` runs them in every order it can, and prints the steps of any
+// order that fails.
+
+#[path = "../../common/explore.rs"]
+#[allow(dead_code)]
+mod explore;
+
+// The same paths as `std`: only these `use` lines differ from ordinary threaded code.
+use explore::sync::atomic::{AtomicUsize, Ordering};
+use explore::sync::{mpsc, Arc, Mutex};
+use explore::thread;
+
+fn main() {
+    // THE MUTEX COUNTER
+    // The counter from `A_concurrency.rs`, ten threads each adding one. Whichever thread gets
+    // the lock first, and wherever the others are, the lock makes each `+= 1` happen whole.
+    explore::model("mutex counter", || {
+        let counter = Arc::new(Mutex::new(0));
+        let mut handles = vec![];
+        for _ in 0..10 {
+            let counter = Arc::clone(&counter);
+            let handle = thread::spawn(move || {
+                let mut num = counter.lock().unwrap();
+                *num += 1;
+            });
+            handles.push(handle);
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*counter.lock().unwrap(), 10);
+    });
+
+
+    // A COUNTER WITHOUT THE LOCK
+    // Here each thread reads the counter and writes back one more, as two separate steps.
+    // Run once, it looks fine: each thread happens to do both steps before the next starts.
+    // `rust_tutor explore` finds the order that loses an update: two threads both load the
+    // same value before either stores, and one `+ 1` disappears.
+    explore::model("racy counter", || {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let mut handles = vec![];
+        for _ in 0..10 {
+            let counter = Arc::clone(&counter);
+            handles.push(thread::spawn(move || {
+                let value = counter.load(Ordering::SeqCst);
+                counter.store(value + 1, Ordering::SeqCst);
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 10);
+    });
+
+    // The fix is to make reading and writing one step, with `fetch_add` (or a mutex).
+    explore::model("fetch_add counter", || {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let mut handles = vec![];
+        for _ in 0..10 {
+            let counter = Arc::clone(&counter);
+            handles.push(thread::spawn(move || {
+                counter.fetch_add(1, Ordering::SeqCst);
+            }));
+        }
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 10);
+    });
+
+
+    // LOCKING IN TWO ORDERS
+    // Two threads each need both mutexes, but take them in opposite orders. If each gets its
+    // first lock before the other gets its second, both wait forever: a deadlock. The explorer
+    // reports an order in which every thread is waiting for another. Outside a model,
+    // `common/tracked_mutex.rs` catches the same mistake from a single ordinary run: it notes
+    // the order each thread takes its locks in and reports two opposite ones. The
+    // `two_accounts` exercise uses it.
+    explore::model("two locks", || {
+        let accounts = Arc::new(Mutex::new(100));
+        let audit_log = Arc::new(Mutex::new(Vec::new()));
+        let auditor = {
+            let accounts = Arc::clone(&accounts);
+            let audit_log = Arc::clone(&audit_log);
+            thread::spawn(move || {
+                let mut log = audit_log.lock().unwrap();
+                let balance = accounts.lock().unwrap();
+                log.push(*balance);
+            })
+        };
+        {
+            let mut balance = accounts.lock().unwrap();
+            *balance -= 30;
+            audit_log.lock().unwrap().push(*balance);
+        }
+        auditor.join().unwrap();
+    });
+
+
+    // MESSAGES FROM TWO THREADS
+    // A channel keeps each sender's messages in order, but says nothing about the order
+    // between senders. This model assumes the first thread's message always arrives first.
+    explore::model("message order", || {
+        let (tx, rx) = mpsc::channel();
+        for name in ["first", "second"] {
+            let tx = tx.clone();
+            thread::spawn(move || tx.send(name).unwrap());
+        }
+        drop(tx);
+        let received: Vec<&str> = rx.iter().collect();
+        assert_eq!(received, ["first", "second"]);
+    });
+}
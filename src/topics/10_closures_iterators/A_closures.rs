@@ -0,0 +1,109 @@
+// Closures are anonymous functions you can save in a variable or pass to other functions.
+// Unlike functions, closures can capture values from the scope in which they're defined.
+
+use std::thread;
+
+fn main() {
+    // CLOSURE SYNTAX
+    // The parameters go between pipes, and the body can be a single expression.
+    // Types are usually inferred, but they can be written out like a function's.
+    let add_one_v1 = |x: u32| -> u32 { x + 1 };
+    let add_one_v2 = |x| x + 1;
+    println!("{} {}", add_one_v1(1), add_one_v2(2));
+
+    // Once a closure has been called, its inferred types are fixed:
+    // calling `add_one_v2(String::from("hi"))` now would be a type error.
+
+
+    // CAPTURING THE ENVIRONMENT
+    // A closure captures each value it uses in the least demanding way that works:
+    // by shared reference, by mutable reference, or by taking ownership.
+
+    // `only_borrows` only reads `list`, so it captures `&list`.
+    // Other shared references to `list` are fine while the closure exists.
+    let list = vec![1, 2, 3];
+    let only_borrows = || println!("From closure: {:?}", list);
+    println!("Before calling the closure: {:?}", list);
+    only_borrows();
+
+    // `borrows_mutably` pushes to `list`, so it captures `&mut list`.
+    // Until its last use, nothing else may touch `list`, not even to print it.
+    let mut list = vec![1, 2, 3];
+    let mut borrows_mutably = || list.push(7);
+    borrows_mutably();
+    println!("After calling the closure: {:?}", list);
+
+    // `move` makes a closure take ownership of everything it captures, even if it only reads it.
+    // This is needed when the closure outlives the current scope, like on another thread.
+    let list = vec![1, 2, 3];
+    thread::spawn(move || println!("From thread: {:?}", list))
+        .join()
+        .unwrap();
+    // `list` now belongs to the closure; using it here would be a compile error.
+
+
+    // FN, FNMUT AND FNONCE
+    // What a closure does with its captured values decides which traits it implements:
+    // - `FnOnce`: it can be called once. Every closure implements this.
+    // - `FnMut`: it can be called more than once, and may change what it captured.
+    // - `Fn`: it can be called more than once, and doesn't change what it captured.
+    // Functions that take closures say which of these they need.
+
+    // `sort_by_key` calls the closure once per comparison, so it needs `FnMut`.
+    // A closure that counts its calls is fine.
+    let mut rectangles = [(10, 1), (3, 5), (7, 12)];
+    let mut calls = 0;
+    rectangles.sort_by_key(|r| {
+        calls += 1;
+        r.0
+    });
+    println!("{:?}, sorted in {} calls", rectangles, calls);
+
+    // A closure that gives away a captured value can only be called once: it is `FnOnce`.
+    let name = String::from("ferris");
+    let consume = move || name;
+    let owned = consume();
+    println!("The closure gave away {}", owned);
+    // COMPILE_FAIL(E0382)
+    // let again = consume(); // `consume` was moved by the first call
+
+    println!("{}", call_twice(|| String::from("twice")));
+    let mut counter = 0;
+    call_until(3, || {
+        counter += 1;
+        counter
+    });
+    println!("counter: {}", counter);
+
+
+    // RETURNING CLOSURES
+    // Every closure has its own anonymous type, so functions return them as `impl Fn`,
+    // or as a `Box<dyn Fn>` when different closures can come back.
+    let add_five = make_adder(5);
+    println!("add_five(10) = {}", add_five(10));
+    for op in ["double", "negate"] {
+        println!("{}(21) = {}", op, make_operation(op)(21));
+    }
+}
+
+// `Fn` is the most demanding bound for the closure, and the most flexible for the caller.
+fn call_twice<F: Fn() -> String>(f: F) -> String {
+    format!("{} {}", f(), f())
+}
+
+// `FnMut` lets the closure change its captured state between calls.
+fn call_until<F: FnMut() -> i32>(limit: i32, mut f: F) {
+    while f() < limit {}
+}
+
+// `move` is needed: `n` is a parameter, and would be gone when the closure runs.
+fn make_adder(n: i32) -> impl Fn(i32) -> i32 {
+    move |x| x + n
+}
+
+fn make_operation(name: &str) -> Box<dyn Fn(i32) -> i32> {
+    match name {
+        "double" => Box::new(|x| x * 2),
+        _ => Box::new(|x| -x),
+    }
+}
@@ -0,0 +1,132 @@
+// An iterator produces a sequence of items, one at a time, and knows when it's done.
+// Iterators are lazy: they do nothing until something consumes them.
+
+// Run this lesson with `rust_tutor run B_iterators` to watch each item pass through a chain
+// of adapters. `rust_tutor pipeline` lets you try your own chains the same way.
+#[path = "../../common/pipeline.rs"]
+#[allow(dead_code)]
+mod pipeline;
+
+use pipeline::Trace;
+
+// IMPLEMENTING ITERATOR
+// Implementing the `Iterator` trait only takes one method: `next`, which returns
+// `Some(item)` while there are items and `None` after that.
+struct Counter {
+    count: u32,
+}
+
+impl Counter {
+    fn new() -> Counter {
+        Counter { count: 0 }
+    }
+}
+
+impl Iterator for Counter {
+    // The type of the items this iterator produces.
+    type Item = u32;
+
+    fn next(&mut self) -> Option<u32> {
+        if self.count < 5 {
+            self.count += 1;
+            Some(self.count)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Shoe {
+    size: u32,
+    style: String,
+}
+
+fn main() {
+    // THE ITERATOR TRAIT
+    // Calling `next` by hand shows what a `for` loop does behind the scenes.
+    let v = vec![1, 2, 3];
+    let mut iter = v.iter();
+    println!("{:?} {:?} {:?} {:?}", iter.next(), iter.next(), iter.next(), iter.next());
+
+    // There are three ways to iterate over a collection:
+    // `iter()` borrows each item, `iter_mut()` borrows them mutably, and `into_iter()` takes them.
+    let mut v = vec![1, 2, 3];
+    for x in v.iter_mut() {
+        *x *= 10;
+    }
+    let owned: Vec<i32> = v.into_iter().collect();
+    println!("{:?}", owned);
+
+    // Once `next` is written, every other iterator method comes for free.
+    let counter: Vec<u32> = Counter::new().collect();
+    println!("Counter: {:?}", counter);
+    let sum: u32 = Counter::new()
+        .zip(Counter::new().skip(1))
+        .map(|(a, b)| a * b)
+        .filter(|x| x % 3 == 0)
+        .sum();
+    println!("Sum of the products divisible by 3: {}", sum);
+
+
+    // ADAPTERS AND CONSUMERS
+    // Adapters like `map` and `filter` turn an iterator into another iterator.
+    // Consumers like `collect`, `sum` and `count` call `next` until the end and produce a value.
+    let words = "the quick brown fox jumps over the lazy dog";
+    let long: Vec<&str> = words.split_whitespace().filter(|w| w.len() > 4).collect();
+    println!("Long words: {:?}", long);
+    for (i, word) in words.split_whitespace().enumerate().skip(6) {
+        println!("word {}: {}", i, word);
+    }
+
+    // The closures passed to adapters can capture their environment.
+    let shoes = vec![
+        Shoe { size: 10, style: String::from("sneaker") },
+        Shoe { size: 13, style: String::from("sandal") },
+        Shoe { size: 10, style: String::from("boot") },
+    ];
+    let shoe_size = 10;
+    let in_my_size: Vec<Shoe> = shoes.into_iter().filter(|s| s.size == shoe_size).collect();
+    println!("In my size: {:?}", in_my_size);
+
+
+    // LAZINESS
+    // Creating a chain of adapters does no work at all. This `map` never runs,
+    // and the compiler warns that the iterator is unused.
+    let _unused = v_numbers().iter().map(|x| {
+        println!("this is never printed");
+        x * 2
+    });
+
+    // Consuming the chain pulls one item at a time through every stage, rather than
+    // running each stage over the whole collection. Each row below is one item: it
+    // stops where `filter` drops it, and `take` stops asking once it has two items,
+    // so the source is never asked for 5 or anything after it.
+    pipeline::header(&["1..10", "filter even", "square", "take(2)"]);
+    let squares: Vec<i32> = pipeline::source(1..10)
+        .filter(|x| x % 2 == 0)
+        .trace(1)
+        .map(|x| x * x)
+        .trace(2)
+        .take(2)
+        .trace(3)
+        .collect();
+    pipeline::finish();
+    println!("Squares: {:?}", squares);
+
+    // `rev` pulls items from the back instead of the front. That only works for iterators
+    // that implement `DoubleEndedIterator`, like ranges and the iterators of a `Vec`.
+    pipeline::header(&["1..=4", "rev", "map x10"]);
+    let reversed: Vec<i32> = pipeline::source(1..=4)
+        .rev()
+        .trace(1)
+        .map(|x| x * 10)
+        .trace(2)
+        .collect();
+    pipeline::finish();
+    println!("Reversed: {:?}", reversed);
+}
+
+fn v_numbers() -> Vec<i32> {
+    vec![1, 2, 3]
+}
@@ -0,0 +1,19 @@
+// The `garden` module. `mod garden;` in main.rs loads this file, and the modules it declares
+// are in the `garden/` directory next to it.
+
+pub mod vegetables;
+
+// Re-export `harvest`, so code outside can write `garden::harvest`.
+pub use self::vegetables::harvest;
+
+use self::vegetables::{Asparagus, Season};
+
+// Visible everywhere in the crate, but not outside of it.
+pub(crate) fn water(plant: &mut Asparagus) {
+    // `watered` is `pub(super)` in `vegetables`, which makes it visible here, in its parent.
+    plant.watered = true;
+}
+
+pub fn in_season(season: Season) -> bool {
+    matches!(season, Season::Spring)
+}
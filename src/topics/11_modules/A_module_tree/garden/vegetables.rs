@@ -0,0 +1,40 @@
+// The `garden::vegetables` module, a child of `garden`.
+
+#[derive(Debug)]
+pub struct Asparagus {
+    pub height_cm: u32,
+    // Visible in this module and its parent, `garden`.
+    pub(super) watered: bool,
+    // Private: only code in this module can read or set it.
+    planted_on: u32,
+}
+
+impl Asparagus {
+    pub fn new(height_cm: u32, planted_on: u32) -> Asparagus {
+        Asparagus {
+            height_cm,
+            watered: false,
+            planted_on,
+        }
+    }
+
+    pub fn age(&self, today: u32) -> u32 {
+        today - self.planted_on
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Season {
+    Spring,
+    Autumn,
+}
+
+pub fn harvest(plant: Asparagus) -> String {
+    let state = if plant.watered { "watered" } else { "dry" };
+    format!("harvested {} cm of {} asparagus", plant.height_cm, state)
+}
+
+// Being a descendant of the crate root doesn't give access to the private items of other
+// modules: `kitchen::season` is private to `kitchen`, and this module is not inside it.
+// COMPILE_FAIL(E0603)
+// use crate::kitchen::season;
@@ -0,0 +1,98 @@
+// Modules organise the code of a crate: they group related items, and decide which of them the
+// rest of the crate may use. This lesson is a directory rather than a single file, laid out the
+// way a crate's modules usually are:
+//
+//     main.rs                 the crate root, where the module tree starts
+//     garden.rs               mod garden
+//     garden/vegetables.rs    mod garden::vegetables
+//     pantry/mod.rs           mod pantry, in the older layout
+//
+// `rustc main.rs` builds all of it: the compiler follows the `mod` declarations to the files.
+
+// MODULES
+// `mod garden;` declares a module whose body is in another file. The compiler looks for it in
+// `garden.rs`, or in `garden/mod.rs`, next to this file.
+mod garden;
+mod pantry;
+
+// A module can also be written inline, with its body between braces.
+mod kitchen {
+    // Everything in a module is private to it unless marked `pub`.
+    pub fn cook(vegetable: &str) -> String {
+        format!("{} soup", season(vegetable))
+    }
+
+    fn season(food: &str) -> String {
+        format!("salted {}", food)
+    }
+
+    pub mod oven {
+        pub fn bake(food: &str) -> String {
+            // `super` is the parent module. A module can use everything its ancestors define,
+            // private or not: privacy hides a module's insides from the outside, not from
+            // the modules nested within it.
+            format!("baked {}", super::season(food))
+        }
+    }
+}
+
+
+// PATHS AND USE
+// An item is named by its path through the module tree. `use` brings a path into scope, so it
+// doesn't have to be written out every time.
+use garden::vegetables::{Asparagus, Season};
+// `self` in a list imports the module itself along with items from it.
+use kitchen::oven::{self, bake};
+// `as` renames what is imported, to avoid a clash or to give it a clearer name.
+use pantry::Jar as PantryJar;
+
+fn main() {
+    // An absolute path starts from the crate root with `crate`; a relative path starts from
+    // the current module. Both of these name the same function.
+    let soup = crate::kitchen::cook("leek");
+    let same_soup = kitchen::cook("leek");
+    println!("{} / {}", soup, same_soup);
+
+    println!("{}", oven::bake("carrot"));
+    println!("{}", bake("potato"));
+
+
+    // VISIBILITY
+    // `season` is private to `kitchen`, so `main` can't call it, even though `oven` can.
+    // COMPILE_FAIL(E0603)
+    // println!("{}", kitchen::season("leek"));
+
+    // A `pub` struct keeps its fields private unless they are marked `pub` as well. `Asparagus`
+    // has a private field, so it can only be made through its constructor.
+    let mut plant = Asparagus::new(12, 3);
+    println!("{:?} is {} cm tall", plant, plant.height_cm);
+    // COMPILE_FAIL(E0451)
+    // let copy = Asparagus { height_cm: 12, watered: false, planted_on: 3 };
+
+    // Private fields can't be read from outside their module either; methods can expose them.
+    println!("planted {} days ago", plant.age(10));
+    // COMPILE_FAIL(E0616)
+    // println!("planted on day {}", plant.planted_on);
+
+    // `pub(crate)` makes an item visible to the whole crate, but no further: if this crate
+    // were a library, `water` would not be part of its API.
+    garden::water(&mut plant);
+
+    // The variants of a `pub` enum are public too, unlike the fields of a `pub` struct.
+    for season in [Season::Spring, Season::Autumn] {
+        println!("harvest in {:?}? {}", season, garden::in_season(season));
+    }
+
+    // `pub` items inside a private module are only reachable from where the module is.
+    let jar = PantryJar::filled("pickles");
+    println!("{} on the {} shelf", jar.contents, jar.shelf());
+    // COMPILE_FAIL(E0603)
+    // println!("{}", pantry::shelf::top());
+
+
+    // RE-EXPORTS
+    // `harvest` is defined in `garden::vegetables`, but `garden` re-exports it with `pub use`,
+    // so it can be reached as `garden::harvest`. Re-exports let a crate present a simpler
+    // structure than the one it is built from.
+    println!("{}", garden::harvest(plant));
+}
@@ -0,0 +1,26 @@
+// The `pantry` module, in the layout from before the 2018 edition: a directory with a `mod.rs`
+// inside, rather than `pantry.rs` next to a `pantry/` directory. Both still work, but a crate
+// full of files called `mod.rs` is hard to find your way around in an editor.
+
+pub struct Jar {
+    pub contents: String,
+}
+
+impl Jar {
+    pub fn filled(contents: &str) -> Jar {
+        Jar {
+            contents: contents.to_string(),
+        }
+    }
+
+    pub fn shelf(&self) -> &'static str {
+        shelf::top()
+    }
+}
+
+// A private module: its `pub` items can be used here in `pantry`, but not from outside.
+mod shelf {
+    pub fn top() -> &'static str {
+        "top"
+    }
+}
@@ -0,0 +1,41 @@
+// A package can hold crates of two kinds. A binary crate like this one has a `main` and builds
+// into a program; a library crate like `greeter` has a `src/lib.rs` and is used by other crates.
+// This lesson is a small Cargo workspace holding one of each:
+//
+//     Cargo.toml              the workspace, listing its members
+//     greeter/src/lib.rs      the library crate `greeter`
+//     app/src/main.rs         this binary crate, which depends on `greeter`
+//
+// `cargo run` in the lesson's directory builds both and runs this one.
+
+// CRATES AND DEPENDENCIES
+// A dependency listed in Cargo.toml is in scope under its crate name, like `std` is: paths into
+// it start with `greeter::`. `use` works the same as for this crate's own modules.
+use greeter::{Greeter, Style};
+
+fn main() {
+    let greeter = Greeter::new("Ferris", Style::Plain);
+    println!("{}", greeter.greet("world"));
+    let loud = Greeter::new("Ferris", Style::Loud);
+    println!("{}", loud.greet("workspace"));
+
+    // A crate that isn't listed as a dependency can't be used, even if it is on the machine.
+    // COMPILE_FAIL(E0432)
+    // use rand::Rng;
+
+
+    // VISIBILITY ACROSS CRATES
+    // Only the library's public API can be used here. `pub(crate)` items are private to
+    // `greeter`, just like items without `pub`.
+    // COMPILE_FAIL(E0603)
+    // println!("{}", greeter::punctuation());
+
+    // `formatting` is a private module; `Style` can only be reached through its re-export.
+    // COMPILE_FAIL(E0603)
+    // let style = greeter::formatting::Style::Plain;
+
+    // Public fields can be read, private ones can't, the same as within one crate.
+    println!("{} greeted twice", greeter.name);
+    // COMPILE_FAIL(E0616)
+    // println!("{:?}", greeter.style);
+}
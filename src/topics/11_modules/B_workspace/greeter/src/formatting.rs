@@ -0,0 +1,16 @@
+// `mod formatting;` in lib.rs loads this file: a library's module tree works the same way as
+// a binary's.
+
+#[derive(Debug, Clone, Copy)]
+pub enum Style {
+    Plain,
+    Loud,
+}
+
+pub fn apply(style: Style, who: &str) -> String {
+    let greeting = format!("Hello, {}{}", who, crate::punctuation());
+    match style {
+        Style::Plain => greeting,
+        Style::Loud => greeting.to_uppercase(),
+    }
+}
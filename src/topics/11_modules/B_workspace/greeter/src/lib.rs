@@ -0,0 +1,45 @@
+// The `greeter` library crate. Its public API is every `pub` item that can be reached from
+// this file, the crate root of the library. Other crates name them as `greeter::...`.
+
+// The module itself is private, so `greeter::formatting` doesn't exist for other crates...
+mod formatting;
+
+// ...but this re-export makes its `Style` available as `greeter::Style`. The crate's users see
+// a flat API, and the library is free to move `Style` to another module later.
+pub use formatting::Style;
+
+pub struct Greeter {
+    pub name: String,
+    style: Style,
+}
+
+impl Greeter {
+    pub fn new(name: &str, style: Style) -> Greeter {
+        Greeter {
+            name: name.to_string(),
+            style,
+        }
+    }
+
+    pub fn greet(&self, who: &str) -> String {
+        format!("{}, says {}", formatting::apply(self.style, who), self.name)
+    }
+}
+
+// `pub(crate)` is visible everywhere in `greeter`, and nowhere else. Within one crate it is
+// like `pub`; across crates it is like private.
+pub(crate) fn punctuation() -> &'static str {
+    "!"
+}
+
+// A library can't be run, but it can have tests: `cargo test -p greeter`.
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn greets_loudly() {
+        let greeter = Greeter::new("Ferris", Style::Loud);
+        assert_eq!(greeter.greet("crab"), "HELLO, CRAB!, says Ferris");
+    }
+}
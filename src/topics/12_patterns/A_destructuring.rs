@@ -0,0 +1,185 @@
+// Patterns describe the shape of a value. `match`, `if let`, `let` and function parameters all
+// use them, both to check that a value has a certain shape and to take it apart into its pieces.
+// This lesson goes through what a pattern can say; `B_refutability.rs` covers where they appear.
+
+#[derive(Debug)]
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+// The `Message` and `Coin` types from `04_structs_enums/B_enums.rs`, with `ChangeColor` now
+// holding a nested enum.
+#[derive(Debug)]
+enum Color {
+    Rgb(u8, u8, u8),
+    Hsv(u16, u8, u8),
+}
+
+#[derive(Debug)]
+enum Message {
+    Quit,
+    Move { x: i32, y: i32 },
+    Write(String),
+    ChangeColor(Color),
+}
+
+#[derive(Debug, PartialEq)]
+enum UsState {
+    Alabama,
+    Alaska,
+}
+
+#[derive(Debug)]
+enum Coin {
+    Penny,
+    Nickel,
+    Dime,
+    Quarter(UsState),
+}
+
+fn main() {
+    // DESTRUCTURING STRUCTS
+    // A struct pattern names the fields to take out. `Point { x, y }` is short for
+    // `Point { x: x, y: y }`; write `x: a` to bind the field to another name.
+    let p = Point { x: 0, y: 7 };
+    let Point { x: a, y: b } = p;
+    println!("a = {}, b = {}", a, b);
+
+    // A literal in a pattern only matches that value, so this `match` sorts points by whether
+    // they lie on an axis.
+    for p in [
+        Point { x: 0, y: 7 },
+        Point { x: 3, y: 0 },
+        Point { x: 1, y: 1 },
+    ] {
+        match p {
+            Point { x, y: 0 } => println!("on the x axis at {}", x),
+            Point { x: 0, y } => println!("on the y axis at {}", y),
+            Point { x, y } => println!("on neither axis: ({}, {})", x, y),
+        }
+    }
+
+
+    // DESTRUCTURING ENUMS
+    // A pattern for an enum names the variant, then takes its data apart in the same shape it
+    // was declared with: braces for struct-like variants, parentheses for tuple-like ones.
+    // Patterns nest: `Message::ChangeColor(Color::Rgb(r, g, b))` looks two enums deep at once.
+    let messages = [
+        Message::Move { x: 10, y: 20 },
+        Message::Write(String::from("hello")),
+        Message::ChangeColor(Color::Rgb(0, 160, 255)),
+        Message::ChangeColor(Color::Hsv(200, 100, 100)),
+        Message::Quit,
+    ];
+    for message in &messages {
+        describe(message);
+    }
+
+    // The quarter's state is bound to `state`, or matched against a value directly.
+    for coin in [
+        Coin::Quarter(UsState::Alaska),
+        Coin::Quarter(UsState::Alabama),
+        Coin::Penny,
+        Coin::Nickel,
+        Coin::Dime,
+    ] {
+        match coin {
+            Coin::Quarter(UsState::Alaska) => println!("a quarter from Alaska, the big one"),
+            Coin::Quarter(state) => println!("a quarter from {:?}", state),
+            Coin::Penny | Coin::Nickel | Coin::Dime => println!("not a quarter"),
+        }
+    }
+
+
+    // IGNORING VALUES
+    // `_` matches anything without binding it, and `..` skips the rest of a struct or tuple.
+    let numbers = (2, 4, 8, 16, 32);
+    let (first, .., last) = numbers;
+    println!("first {}, last {}", first, last);
+    let (_, second, _, fourth, _) = numbers;
+    println!("second {}, fourth {}", second, fourth);
+    let origin = Point { x: 0, y: 0 };
+    let Point { x, .. } = origin;
+    println!("x is {}", x);
+
+
+
+    // RANGES, ALTERNATIVES AND GUARDS
+    // `|` matches either pattern, and `..=` any value in a range of numbers or characters.
+    for n in [1, 4, 7, 12] {
+        let size = match n {
+            1 | 2 => "one or two",
+            3..=9 => "a few",
+            _ => "lots",
+        };
+        println!("{}: {}", n, size);
+    }
+    for c in ['c', 'K', '7'] {
+        let kind = match c {
+            'a'..='z' => "lowercase",
+            'A'..='Z' => "uppercase",
+            _ => "something else",
+        };
+        println!("{:?} is {}", c, kind);
+    }
+
+    // A guard is an extra `if` condition on an arm. It can use the variables the pattern bound,
+    // which a pattern alone can't do: `Some(x) if x % 2 == 0`.
+    for value in [Some(4), Some(5), None] {
+        match value {
+            Some(x) if x % 2 == 0 => println!("{} is even", x),
+            Some(x) => println!("{} is odd", x),
+            None => println!("nothing"),
+        }
+    }
+
+    // The guard applies to the whole arm: this is `(4 | 5 | 6) if y`, not `4 | 5 | (6 if y)`.
+    let (x, y) = (4, false);
+    match x {
+        4 | 5 | 6 if y => println!("yes"),
+        _ => println!("no: the guard is false for 4 too"),
+    }
+
+
+    // @ BINDINGS
+    // `name @ pattern` tests a value against a pattern and keeps it in a variable as well,
+    // so the arm knows both that the id is in range and which id it is.
+    for id in [5, 11, 42] {
+        match id {
+            small @ 3..=7 => println!("found a small id: {}", small),
+            10..=12 => println!("found an id between 10 and 12, but not which one"),
+            other => println!("found some other id: {}", other),
+        }
+    }
+
+
+    // EXHAUSTIVENESS
+    // A `match` must handle every possible value. The compiler checks this, so when a variant
+    // is added to an enum, every `match` that forgets it stops compiling. A wildcard `_` arm
+    // switches that check off for the variants it swallows; without one, the compiler points
+    // at each place the new variant needs handling.
+    // COMPILE_FAIL(E0004)
+    // match Coin::Dime {
+    //     Coin::Penny => println!("penny"),
+    //     Coin::Nickel | Coin::Dime => println!("nickel or dime"),
+    // }
+}
+
+fn describe(message: &Message) {
+    match message {
+        Message::Quit => println!("quit"),
+        Message::Move { x: 0, y } => println!("move vertically by {}", y),
+        Message::Move { x, y } => println!("move to ({}, {})", x, y),
+        Message::Write(text) => println!("write {:?}", text),
+        Message::ChangeColor(Color::Rgb(r, g, b)) => {
+            println!("change the color to red {}, green {}, blue {}", r, g, b)
+        }
+        Message::ChangeColor(Color::Hsv(h, s, v)) => {
+            println!(
+                "change the color to hue {}, saturation {}, value {}",
+                h, s, v
+            )
+        }
+    }
+}
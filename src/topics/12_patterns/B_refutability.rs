@@ -0,0 +1,108 @@
+// Where patterns appear, and which patterns each place accepts. A pattern that can fail to
+// match, like `Some(x)`, is refutable; one that matches anything, like `(a, b)` for a pair,
+// is irrefutable. `let`, function parameters and `for` loops only take irrefutable patterns;
+// `if let`, `while let` and `let else` are the forms that handle a pattern failing.
+
+fn main() {
+    // IF LET
+    // `if let` runs its block when the pattern matches. Several can be chained with `else`,
+    // mixed with plain conditions, which a single `match` can't do.
+    let favorite_color: Option<&str> = None;
+    let is_tuesday = false;
+    let age: Result<u8, _> = "34".parse::<u8>();
+
+    if let Some(color) = favorite_color {
+        println!("using your favorite color, {}", color);
+    } else if is_tuesday {
+        println!("Tuesday is green day");
+    } else if let Ok(age) = age {
+        if age > 30 {
+            println!("using purple");
+        } else {
+            println!("using orange");
+        }
+    } else {
+        println!("using blue");
+    }
+
+
+    // WHILE LET
+    // `while let` loops for as long as the pattern matches. `pop` returns `None` once the
+    // stack is empty, which ends the loop.
+    let mut stack = vec![1, 2, 3];
+    while let Some(top) = stack.pop() {
+        println!("popped {}", top);
+    }
+
+
+    // LET ELSE
+    // `let ... else` binds the pattern's variables for the rest of the block, or runs the
+    // `else` block when it doesn't match. That block must leave: `return`, `break`,
+    // `continue` or panic. It keeps the code that handles the expected case unindented.
+    for line in ["apples 3", "pears", "plums x"] {
+        match parse_line(line) {
+            Some((fruit, count)) => println!("{} x {}", count, fruit),
+            None => println!("can't read {:?}", line),
+        }
+    }
+
+
+    // SLICE PATTERNS
+    // A slice pattern matches on the length of a slice and its elements. `..` stands for any
+    // number of elements, and `rest @ ..` keeps them as a slice.
+    let slices: [&[i32]; 4] = [&[], &[7], &[1, 2], &[1, 2, 3, 4]];
+    for slice in slices {
+        let text = match slice {
+            [] => String::from("empty"),
+            [only] => format!("just {}", only),
+            [first, second] => format!("a pair: {} and {}", first, second),
+            [first, rest @ ..] => format!("{} followed by {} more", first, rest.len()),
+        };
+        println!("{:?}: {}", slice, text);
+    }
+    println!("sum of [1, 2, 3, 4] is {}", sum(&[1, 2, 3, 4]));
+
+
+    // IRREFUTABLE PATTERNS
+    // `let` takes apart tuples and structs, because those patterns always match...
+    let (x, y, z) = (1, 2, 3);
+    println!("x {}, y {}, z {}", x, y, z);
+
+    // ...and so do function parameters and `for` loops.
+    let point = (3, 5);
+    print_coordinates(&point);
+    for (index, value) in ['a', 'b', 'c'].iter().enumerate() {
+        println!("{} is at index {}", value, index);
+    }
+
+    // A refutable pattern needs a way to handle the values it doesn't match, so a plain `let`
+    // won't take it. This one doesn't say what happens when `some_value` is `None`.
+    let some_value: Option<i32> = Some(5);
+    // COMPILE_FAIL(E0005)
+    // let Some(x) = some_value;
+    if let Some(x) = some_value {
+        println!("some_value holds {}", x);
+    }
+}
+
+fn parse_line(line: &str) -> Option<(&str, u32)> {
+    let Some((fruit, count)) = line.split_once(' ') else {
+        return None;
+    };
+    let Ok(count) = count.parse() else {
+        return None;
+    };
+    Some((fruit, count))
+}
+
+// Recursion on a slice pattern: the first element, plus the sum of the rest.
+fn sum(numbers: &[i32]) -> i32 {
+    match numbers {
+        [] => 0,
+        [first, rest @ ..] => first + sum(rest),
+    }
+}
+
+fn print_coordinates(&(x, y): &(i32, i32)) {
+    println!("current location: ({}, {})", x, y);
+}
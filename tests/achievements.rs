@@ -0,0 +1,109 @@
+// Achievement rules, evaluated against hand-written progress logs.
+
+use rust_tutor::achievements::{self, Rule};
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::progress::{Event, Store};
+
+const DAY: u64 = 24 * 60 * 60;
+
+fn event(day: u64, kind: &str, subject: &str) -> Event {
+    Event {
+        time: 1_760_000_000 + day * DAY,
+        kind: kind.to_string(),
+        subject: subject.to_string(),
+    }
+}
+
+#[test]
+fn the_definitions_file_parses() {
+    let definitions = achievements::definitions().unwrap();
+    assert!(definitions.iter().any(|a| a.id == "ownership"));
+    assert!(definitions
+        .iter()
+        .any(|a| a.rule == Rule::Streak { days: 7 }));
+}
+
+#[test]
+fn finishing_a_chapter_needs_every_lesson() {
+    let curriculum = achievements::curriculum().unwrap();
+    let rule = Rule::All {
+        kind: String::from("completed"),
+        subjects: String::from("lesson/03_ownership/*"),
+    };
+    let mut events = vec![
+        event(0, "completed", "lesson/03_ownership/A_ownership"),
+        event(0, "completed", "lesson/03_ownership/B_borrowing"),
+    ];
+    assert!(!achievements::satisfied(&rule, &events, &curriculum));
+    events.push(event(1, "completed", "lesson/03_ownership/C_slices"));
+    assert!(achievements::satisfied(&rule, &events, &curriculum));
+}
+
+#[test]
+fn a_hint_before_solving_is_not_clean() {
+    let curriculum = achievements::curriculum().unwrap();
+    let rule = Rule::Clean {
+        subjects: String::from("challenge/borrowck/*"),
+    };
+    let first = "challenge/borrowck/01_two_mutable_borrows";
+    let hinted = vec![event(0, "hint", first), event(0, "solved", first)];
+    assert!(!achievements::satisfied(&rule, &hinted, &curriculum));
+    let clean = vec![event(0, "attempted", first), event(0, "solved", first)];
+    assert!(achievements::satisfied(&rule, &clean, &curriculum));
+}
+
+#[test]
+fn streaks_count_consecutive_days() {
+    let subject = "exercise/03_ownership/first_word";
+    let events: Vec<Event> = [0, 1, 2, 4, 5, 5, 6]
+        .iter()
+        .map(|&day| event(day, "attempted", subject))
+        .collect();
+    let today = events[0].time / DAY + 7;
+    assert_eq!(achievements::streaks(&events, today), (3, 3));
+    assert_eq!(achievements::streaks(&events, today + 5), (3, 0));
+}
+
+#[test]
+fn an_achievement_is_dated_by_the_event_that_earned_it() {
+    let curriculum = achievements::curriculum().unwrap();
+    let subject = "exercise/03_ownership/first_word";
+    let rule = Rule::Streak { days: 3 };
+    let mut events: Vec<Event> = [0, 2, 3, 4, 4, 9]
+        .iter()
+        .map(|&day| event(day, "attempted", subject))
+        .collect();
+    assert_eq!(
+        achievements::earned_at(&rule, &events, &curriculum),
+        Some(events[3].time)
+    );
+    events.truncate(3);
+    assert_eq!(achievements::earned_at(&rule, &events, &curriculum), None);
+
+    // Noticed days later, but recorded with the time of the lesson that earned it.
+    let home = ScratchDir::new("achievement-test").unwrap();
+    let store = Store::at(home.path()).unwrap();
+    let completed = event(0, "completed", "lesson/01_getting_started/A_hello_world");
+    store
+        .record_at(completed.time, &completed.kind, &completed.subject)
+        .unwrap();
+    let earned = achievements::update(&store).unwrap();
+    assert!(earned.iter().any(|a| a.id == "first_steps"));
+    let achieved = store
+        .events()
+        .unwrap()
+        .into_iter()
+        .find(|e| e.subject == "achievement/first_steps")
+        .unwrap();
+    assert_eq!(achieved.time, completed.time);
+}
+
+#[test]
+fn dates_are_formatted_in_utc() {
+    assert_eq!(achievements::date(0), "1970-01-01");
+    assert_eq!(achievements::date(951_782_400), "2000-02-29");
+    assert!(achievements::matches(
+        "lesson/*/A_*",
+        "lesson/03_ownership/A_ownership"
+    ));
+}
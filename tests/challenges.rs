@@ -0,0 +1,66 @@
+// Every challenge must fail with the error it names, and its solution must pass.
+
+use std::fs;
+use std::thread;
+
+use rust_tutor::challenges::{self, Outcome};
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::progress::Store;
+
+#[test]
+fn every_challenge_fails_as_described_and_its_solution_passes() {
+    let all = challenges::all().unwrap();
+    assert!(all.len() >= 30, "only {} challenges", all.len());
+
+    // Each check runs rustc twice, so spread them over a few threads.
+    let problems: Vec<String> = thread::scope(|scope| {
+        let workers: Vec<_> = all
+            .chunks(all.len().div_ceil(4))
+            .map(|chunk| {
+                scope.spawn(move || {
+                    let mut problems = Vec::new();
+                    for challenge in chunk {
+                        for problem in challenges::verify(challenge).unwrap() {
+                            problems.push(format!("{}: {}", challenge.id, problem));
+                        }
+                    }
+                    problems
+                })
+            })
+            .collect();
+        workers
+            .into_iter()
+            .flat_map(|w| w.join().unwrap())
+            .collect()
+    });
+    assert!(problems.is_empty(), "{}", problems.join("\n"));
+}
+
+#[test]
+fn a_challenge_is_solved_once_the_workspace_copy_is_fixed() {
+    let home = ScratchDir::new("challenge-test").unwrap();
+    let store = Store::at(home.path()).unwrap();
+    let challenge = challenges::find("two_mutable_borrows").unwrap();
+
+    let file = challenges::start(&store, &challenge).unwrap();
+    assert!(matches!(
+        challenges::check(&store, &challenge).unwrap(),
+        Outcome::DoesNotCompile(_)
+    ));
+
+    let (number, _) = challenges::hint(&store, &challenge).unwrap().unwrap();
+    assert_eq!(number, 1);
+
+    fs::copy(challenge.solution_path(), &file).unwrap();
+    assert_eq!(
+        challenges::check(&store, &challenge).unwrap(),
+        Outcome::Solved
+    );
+    let kinds: Vec<String> = store
+        .events()
+        .unwrap()
+        .into_iter()
+        .map(|e| e.kind)
+        .collect();
+    assert_eq!(kinds, ["started", "attempted", "hint", "solved"]);
+}
@@ -0,0 +1,124 @@
+// Assignment bundles and batch grading.
+
+use std::fs;
+
+use rust_tutor::classroom;
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::sha256;
+
+#[test]
+fn sha256_matches_the_standard_test_vectors() {
+    assert_eq!(
+        sha256::hex(b""),
+        "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+    );
+    assert_eq!(
+        sha256::hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    assert_eq!(
+        sha256::hex(b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"),
+        "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+    );
+    assert_eq!(
+        sha256::hex(&vec![b'a'; 1_000_000]),
+        "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+    );
+}
+
+#[test]
+fn a_bundle_is_graded_for_every_learner() {
+    let dir = ScratchDir::new("classroom-test").unwrap();
+    let bundle = dir.path().join("bundle");
+    let exercises = classroom::select(&["03_ownership/A_ownership"]).unwrap();
+    assert_eq!(exercises.len(), 1);
+    classroom::assign(&exercises, &bundle).unwrap();
+    assert!(!bundle
+        .join("exercises/03_ownership/first_word/solution.rs")
+        .exists());
+    let exercises = classroom::open_bundle(&bundle).unwrap();
+
+    // One learner hands in the reference solution, one the untouched exercise, one nothing.
+    let submissions = dir.path().join("submissions");
+    for (learner, file) in [("ada", "solution.rs"), ("bob", "exercise.rs")] {
+        let target = submissions.join(learner).join("exercises/03_ownership");
+        fs::create_dir_all(&target).unwrap();
+        let source = rust_tutor::exercises::find("first_word")
+            .unwrap()
+            .dir
+            .join(file);
+        fs::copy(source, target.join("first_word.rs")).unwrap();
+    }
+    fs::create_dir_all(submissions.join("cyd")).unwrap();
+
+    let gradebook = classroom::grade(&submissions, &exercises, 3).unwrap();
+    let csv = gradebook.to_csv();
+    let rows: Vec<&str> = csv.lines().collect();
+    assert_eq!(rows[0], "learner,03_ownership/first_word,passed");
+    assert_eq!(rows[1], "ada,7/7,1/1");
+    assert_eq!(rows[2], "bob,0/7,0/1");
+    assert_eq!(rows[3], "cyd,missing,0/1");
+    assert!(gradebook.feedback("bob").contains("FAILED  no_spaces"));
+
+    // Changing a hidden test after the fact is noticed.
+    fs::write(
+        bundle.join("exercises/03_ownership/first_word/hidden_tests.rs"),
+        "",
+    )
+    .unwrap();
+    assert!(classroom::open_bundle(&bundle).is_err());
+}
+
+#[test]
+fn bundles_carry_fixtures_for_grading() {
+    let dir = ScratchDir::new("classroom-fixtures-test").unwrap();
+    let bundle = dir.path().join("bundle");
+    let exercises = classroom::select(&["username"]).unwrap();
+    let manifest = classroom::assign(&exercises, &bundle).unwrap();
+    assert!(manifest
+        .files
+        .iter()
+        .any(|(path, _)| path == "exercises/06_error_handling/username/fixtures/valid.txt"));
+
+    let exercises = classroom::open_bundle(&bundle).unwrap();
+    let solution = rust_tutor::exercises::find("username")
+        .unwrap()
+        .solution_path();
+    let grade = rust_tutor::exercises::grade_file(&exercises[0], &solution).unwrap();
+    assert!(grade.passed(), "{:?}", grade);
+}
+
+#[test]
+fn bundles_carry_the_common_modules_their_exercises_use() {
+    let dir = ScratchDir::new("classroom-common-test").unwrap();
+    let bundle = dir.path().join("bundle");
+    let exercises = classroom::select(&["two_accounts"]).unwrap();
+    let manifest = classroom::assign(&exercises, &bundle).unwrap();
+    let module = "src/common/tracked_mutex.rs";
+    let checkout = rust_tutor::lessons::root().join(module);
+    assert!(manifest
+        .files
+        .iter()
+        .any(|(path, hash)| path == module && *hash == sha256::hex(&fs::read(&checkout).unwrap())));
+
+    // Graded with the bundle's copy of the module, not the checkout's.
+    let exercises = classroom::open_bundle(&bundle).unwrap();
+    assert_eq!(
+        exercises[0].common_modules().unwrap(),
+        [bundle.join(module)]
+    );
+    let submissions = dir.path().join("submissions");
+    let target = submissions.join("ada").join("exercises/09_advanced");
+    fs::create_dir_all(&target).unwrap();
+    let solution = rust_tutor::exercises::find("two_accounts")
+        .unwrap()
+        .solution_path();
+    fs::copy(solution, target.join("two_accounts.rs")).unwrap();
+    let gradebook = classroom::grade(&submissions, &exercises, 1).unwrap();
+    let csv = gradebook.to_csv();
+    assert!(csv.lines().nth(1).unwrap().ends_with(",1/1"), "{}", csv);
+
+    // And the module is checked like every other file.
+    fs::write(bundle.join(module), "").unwrap();
+    assert!(classroom::open_bundle(&bundle).is_err());
+}
@@ -0,0 +1,30 @@
+// The lesson clock in virtual time. The mode is read once per process, so this is one test
+// in a binary of its own.
+
+use std::thread;
+use std::time::Duration;
+
+use rust_tutor::common::clock::{self, Mode};
+
+#[test]
+fn virtual_time_follows_each_thread() {
+    std::env::set_var(clock::ENV, "virtual");
+    assert_eq!(clock::mode(), Mode::Virtual);
+
+    clock::sleep(Duration::from_millis(50));
+    assert_eq!(clock::elapsed(), Duration::from_millis(50));
+
+    // Two threads sleeping side by side take as long as the longer one, counted from when
+    // they were spawned.
+    let short = clock::spawn(|| clock::sleep(Duration::from_millis(10)));
+    let long = clock::spawn(|| clock::sleep(Duration::from_millis(20)));
+    short.join().unwrap();
+    long.join().unwrap();
+    assert_eq!(clock::elapsed(), Duration::from_millis(70));
+
+    // A plain thread knows nothing of its parent's clock, and starts at zero.
+    thread::spawn(|| clock::sleep(Duration::from_millis(10)))
+        .join()
+        .unwrap();
+    assert_eq!(clock::elapsed(), Duration::from_millis(70));
+}
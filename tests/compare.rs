@@ -0,0 +1,110 @@
+// `rust_tutor compare`: diffing a learner's solution against the reference.
+
+use std::fs;
+
+use rust_tutor::compare::{self, Row};
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::exercises;
+use rust_tutor::progress::Store;
+
+#[test]
+fn formatting_differences_disappear() {
+    let messy = "fn main(){\n  let x=1;   \n\n\n  println!(\"{}\",x);}\n";
+    let tidy = "fn main() {\n    let x = 1;\n\n    println!(\"{}\", x);\n}\n";
+    let rows = compare::diff(&compare::normalize(messy), &compare::normalize(tidy));
+    assert!(rows.iter().all(|row| matches!(row, Row::Same(_))));
+}
+
+#[test]
+fn changed_lines_are_highlighted_token_by_token() {
+    let old = "fn f() {\n    let s = s.trim_start();\n    helper();\n}\n";
+    let new = "fn f() {\n    let s = s.trim_start_matches(' ');\n}\n";
+    let rows = compare::diff(old, new);
+    assert_eq!(rows.len(), 4);
+    assert!(matches!(rows[2], Row::Removed(_)));
+
+    let unified = compare::unified(&rows, "yours", "reference", false);
+    assert_eq!(
+        unified,
+        "--- yours\n+++ reference\n@@ -1,4 +1,3 @@\n fn f() {\n\
+         -    let s = s.[-trim_start-]();\n\
+         -    helper();\n\
+         +    let s = s.{+trim_start_matches+}({+' '+});\n }\n"
+    );
+
+    let side = compare::side_by_side(&rows, "yours", "reference", 80, false);
+    let lines: Vec<&str> = side.lines().collect();
+    assert!(lines[3].contains(" | "));
+    assert!(lines[4].trim_end().ends_with('<'));
+}
+
+#[test]
+fn only_distant_context_is_left_out() {
+    let old: String = (1..=20).map(|n| format!("line {}\n", n)).collect();
+    let new = old.replace("line 10\n", "line ten\n");
+    let unified = compare::unified(&compare::diff(&old, &new), "a", "b", false);
+    assert!(unified.contains("@@ -7,7 +7,7 @@\n line 7\n"));
+    assert!(!unified.contains("line 6\n"));
+    assert!(!unified.contains("line 14\n"));
+}
+
+#[test]
+fn only_the_function_bodies_differ_from_a_fresh_copy() {
+    let dir = ScratchDir::new("compare-test").unwrap();
+    let store = Store::at(dir.path()).unwrap();
+    let exercise = exercises::find("median_mode").unwrap();
+    let file = exercises::start(&store, &exercise).unwrap();
+    let yours = fs::read_to_string(file).unwrap();
+    let reference = fs::read_to_string(exercise.solution_path()).unwrap();
+    assert!(yours.starts_with("// Median and mode\n//\n// Given"));
+
+    let rows = compare::diff(
+        &compare::normalize(compare::without_header(&yours)),
+        &compare::normalize(compare::without_header(&reference)),
+    );
+    assert_eq!(
+        rows[0],
+        Row::Same(String::from("use std::collections::HashMap;"))
+    );
+    // Every line outside the bodies of `median` and `mode` is the same on both sides.
+    let same: Vec<&str> = rows
+        .iter()
+        .filter_map(|row| match row {
+            Row::Same(line) => Some(line.as_str()),
+            _ => None,
+        })
+        .collect();
+    let main = &reference[reference.find("fn main() {").unwrap()..];
+    for line in [
+        "fn median(numbers: &[i32]) -> Option<f64> {",
+        "fn mode(numbers: &[i32]) -> Option<i32> {",
+    ]
+    .into_iter()
+    .chain(main.lines())
+    {
+        assert!(same.contains(&line), "{:?} differs", line);
+    }
+    assert!(!rows.iter().any(|row| match row {
+        Row::Removed(segments) | Row::Changed(segments, _) =>
+            segments[0].0.trim_start().starts_with("//"),
+        _ => false,
+    }));
+}
+
+#[test]
+fn the_reference_is_shown_after_solving_or_every_hint() {
+    let dir = ScratchDir::new("compare-test").unwrap();
+    let store = Store::at(dir.path()).unwrap();
+    let exercise = exercises::find("first_word").unwrap();
+    let hints = exercise.hints().unwrap();
+    let subject = exercise.subject();
+
+    assert!(!compare::unlocked(&store, &subject, hints.len()).unwrap());
+    while store.next_hint(&subject, &hints).unwrap().is_some() {}
+    assert!(compare::unlocked(&store, &subject, hints.len()).unwrap());
+
+    let other = ScratchDir::new("compare-test").unwrap();
+    let store = Store::at(other.path()).unwrap();
+    store.record("solved", &subject).unwrap();
+    assert!(compare::unlocked(&store, &subject, hints.len()).unwrap());
+}
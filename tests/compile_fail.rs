@@ -0,0 +1,52 @@
+// Every commented-out example annotated with `// COMPILE_FAIL(<code>)` must still fail to
+// compile with that error.
+
+use std::fs;
+
+use rust_tutor::compile_fail::{self, Verdict};
+use rust_tutor::lessons;
+
+#[test]
+fn annotated_examples_fail_with_their_error_code() {
+    let mut checked = 0;
+    for lesson in lessons::all().unwrap() {
+        for file in lesson.files().unwrap() {
+            let source = fs::read_to_string(&file).unwrap();
+            for example in compile_fail::examples(&source) {
+                let verdict = compile_fail::check(&lesson, &file, &example).unwrap();
+                assert_eq!(
+                    verdict,
+                    Verdict::Failed,
+                    "{}:{} should fail with {}",
+                    lesson.file_id(&file),
+                    example.line,
+                    example.code
+                );
+                checked += 1;
+            }
+        }
+    }
+    assert!(checked >= 6, "only {} examples found", checked);
+}
+
+#[test]
+fn an_extra_error_makes_an_example_stale() {
+    let dir = rust_tutor::compiler::ScratchDir::new("compile-fail-stale").unwrap();
+    let file = dir.path().join("stale.rs");
+    let moved = "fn moved() {\n    let v = vec![1];\n    let w = v;\n    println!(\"{:?} {:?}\", v, w);\n}\n";
+    fs::write(&file, format!("{}fn main() {{}}\n", moved)).unwrap();
+    assert_eq!(
+        compile_fail::expect_error(&file, "E0382", false).unwrap(),
+        Verdict::Failed
+    );
+
+    fs::write(
+        &file,
+        format!("{}fn wrong() -> i32 {{ \"one\" }}\nfn main() {{}}\n", moved),
+    )
+    .unwrap();
+    assert_eq!(
+        compile_fail::expect_error(&file, "E0382", false).unwrap(),
+        Verdict::WrongError(vec![String::from("E0308"), String::from("E0382")])
+    );
+}
@@ -0,0 +1,140 @@
+// The reference solutions must pass every hidden test, and the starting points must not.
+
+use std::fs;
+
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::exercises::{self, Grade};
+use rust_tutor::progress::Store;
+
+#[test]
+fn every_solution_passes_its_hidden_tests() {
+    for exercise in exercises::all().unwrap() {
+        let grade = exercises::grade_file(&exercise, &exercise.solution_path()).unwrap();
+        assert!(grade.passed(), "{}: {:?}", exercise.id, grade);
+    }
+}
+
+#[test]
+fn every_starting_point_compiles_but_fails() {
+    for exercise in exercises::all().unwrap() {
+        let grade = exercises::grade_file(&exercise, &exercise.path()).unwrap();
+        assert!(
+            matches!(&grade, Grade::Tested(results) if results.iter().any(|r| !r.passed)),
+            "{}: {:?}",
+            exercise.id,
+            grade
+        );
+    }
+}
+
+#[test]
+fn a_changed_signature_does_not_leak_the_hidden_tests() {
+    let home = ScratchDir::new("exercise-test").unwrap();
+    let store = Store::at(home.path()).unwrap();
+    let exercise = exercises::find("first_word").unwrap();
+
+    let file = exercises::start(&store, &exercise).unwrap();
+    let renamed = fs::read_to_string(&file)
+        .unwrap()
+        .replace("first_word", "first");
+    fs::write(&file, renamed).unwrap();
+    assert_eq!(
+        exercises::check(&store, &exercise).unwrap(),
+        Grade::Incompatible
+    );
+}
+
+#[test]
+fn a_crash_is_reported_as_one_rather_than_as_failed_tests() {
+    let exercise = exercises::find("first_word").unwrap();
+    let scratch = ScratchDir::new("crash-test").unwrap();
+    let file = scratch.path().join("first_word.rs");
+    let solution = fs::read_to_string(exercise.solution_path()).unwrap();
+
+    // Aborting takes the whole test binary down in the middle of `empty_string`.
+    let aborts = solution.replace(
+        "    let s = s.trim_start_matches(' ');",
+        "    if s.is_empty() {\n        std::process::abort();\n    }\n    let s = s.trim_start_matches(' ');",
+    );
+    assert_ne!(aborts, solution);
+    fs::write(&file, aborts).unwrap();
+    match exercises::grade_file(&exercise, &file).unwrap() {
+        Grade::Crashed { status, during } => {
+            assert_eq!(during.as_deref(), Some("empty_string"));
+            assert!(status.contains("SIGABRT"), "{}", status);
+        }
+        grade => panic!("should have crashed: {:?}", grade),
+    }
+
+    // Exiting successfully before the tests are done is no better.
+    let exits = solution.replace("None => s,", "None => std::process::exit(0),");
+    assert_ne!(exits, solution);
+    fs::write(&file, exits).unwrap();
+    assert!(matches!(
+        exercises::grade_file(&exercise, &file).unwrap(),
+        Grade::Crashed {
+            during: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn a_wildcard_arm_fails_the_exhaustiveness_check() {
+    let exercise = exercises::find("next_state").unwrap();
+    let scratch = ScratchDir::new("exhaustive-test").unwrap();
+    let file = scratch.path().join("next_state.rs");
+    let solution = fs::read_to_string(exercise.solution_path()).unwrap();
+
+    // Every hidden test still passes; only the added check notices the catch-all arm.
+    let catch_all = solution.replace(
+        "State::Idle | State::Running { .. } | State::Paused { .. } | State::Done { .. }",
+        "_",
+    );
+    assert_ne!(catch_all, solution);
+    fs::write(&file, catch_all).unwrap();
+    let Grade::Tested(results) = exercises::grade_file(&exercise, &file).unwrap() else {
+        panic!("the catch-all version should be tested");
+    };
+    for result in &results {
+        assert_eq!(
+            result.passed,
+            result.name != exercises::EXHAUSTIVE,
+            "{:?}",
+            result
+        );
+    }
+    assert!(results.iter().any(|r| r.name == exercises::EXHAUSTIVE));
+
+    // An arm after every case is covered can never run, which is an error for these exercises.
+    let unreachable = solution.replace(
+        "Event::Stop) => State::Done { ticks },",
+        "Event::Stop) => State::Done { ticks },\n        _ => state,",
+    );
+    fs::write(&file, unreachable).unwrap();
+    assert!(matches!(
+        exercises::grade_file(&exercise, &file).unwrap(),
+        Grade::DoesNotCompile(errors) if errors.contains("unreachable pattern")
+    ));
+}
+
+#[test]
+fn probe_variant_is_added_to_the_named_enum() {
+    let source = "enum Color {\n    Red,\n}\n\npub enum Message {\n    Quit,\n}\n";
+    assert_eq!(
+        exercises::with_variant(source, "Message", "Resize { width: u32 }").unwrap(),
+        "enum Color {\n    Red,\n}\n\npub enum Message {\n    Resize { width: u32 },\n    Quit,\n}\n"
+    );
+    assert_eq!(exercises::with_variant(source, "Mess", "Resize"), None);
+
+    // Any visibility, and enums written on one line.
+    let source = "pub(crate) enum State { On, Off }\nfn main() {}\n";
+    assert_eq!(
+        exercises::with_variant(source, "State", "Dimmed(u8)").unwrap(),
+        "pub(crate) enum State { Dimmed(u8), On, Off }\nfn main() {}\n"
+    );
+    assert_eq!(
+        exercises::with_variant("pub(super) enum State {}\n", "State", "On").unwrap(),
+        "pub(super) enum State { On,}\n"
+    );
+}
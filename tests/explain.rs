@@ -0,0 +1,99 @@
+// `rust_tutor explain`: finding the prose for a line of a lesson.
+
+use rust_tutor::explain::{self, Explanation};
+use rust_tutor::lessons;
+
+const SOURCE: &str = "\
+fn main() {
+    // SHADOWING
+    // A new variable can reuse an old name.
+    let x = 5;
+    let x = x + 1; // the old `x` is shadowed
+
+    // SECTION: REFERENCES
+    let s = String::from(\"hi\");
+    // COMPILE_FAIL(E0499)
+    // let a = &mut s; // two mutable borrows
+    // let b = &mut s;
+    println!(\"{}\", s.len());
+}
+
+fn takes(s: &String) { // s is borrowed
+    s.len();
+} // nothing is dropped here
+  // because nothing was owned
+";
+
+fn at(line: usize) -> Explanation {
+    explain::explain(SOURCE, line, &explain::glossary().unwrap()).unwrap()
+}
+
+fn terms(explanation: &Explanation) -> Vec<&str> {
+    explanation
+        .entries
+        .iter()
+        .map(|e| e.term.as_str())
+        .collect()
+}
+
+#[test]
+fn a_line_gets_its_comment_block_section_and_inline_comment() {
+    let line = at(5);
+    assert_eq!(line.code, "let x = x + 1; // the old `x` is shadowed");
+    assert_eq!(line.section.as_deref(), Some("SHADOWING"));
+    assert_eq!(line.prose, ["A new variable can reuse an old name."]);
+    assert_eq!(line.inline, ["the old `x` is shadowed"]);
+
+    let line = at(8);
+    assert_eq!(line.section.as_deref(), Some("REFERENCES"));
+    assert!(line.prose.is_empty());
+    assert_eq!(terms(&line), ["String::from"]);
+}
+
+#[test]
+fn compile_fail_examples_are_explained_as_code() {
+    let line = at(10);
+    assert_eq!(line.compile_fail.as_deref(), Some("E0499"));
+    assert_eq!(line.inline, ["two mutable borrows"]);
+    assert_eq!(terms(&line), ["mutable reference"]);
+    // The prose comes from above the example, which has none but the section heading.
+    assert!(line.prose.is_empty());
+
+    // The line after the example is ordinary code again.
+    let line = at(12);
+    assert_eq!(line.compile_fail, None);
+    assert_eq!(terms(&line), ["macro", "len"]);
+}
+
+#[test]
+fn sections_end_with_the_function_they_are_in() {
+    let line = at(16);
+    assert_eq!(line.section, None);
+    assert_eq!(line.prose, ["s is borrowed"]);
+
+    // A comment continued on the next line is read as a whole, from either line.
+    let whole = ["nothing is dropped here", "because nothing was owned"];
+    assert_eq!(at(17).inline, whole);
+    assert_eq!(at(18).inline, whole);
+}
+
+#[test]
+fn lessons_are_explained_by_name_and_line() {
+    let lesson = lessons::find("B_borrowing").unwrap();
+    let source = lesson.source().unwrap();
+    let line = source
+        .lines()
+        .position(|l| l.contains("let len = calculate_length(&s1);"))
+        .unwrap()
+        + 1;
+    let explanation = explain::explain(&source, line, &explain::glossary().unwrap()).unwrap();
+    assert_eq!(
+        explanation.section.as_deref(),
+        Some("REFERENCES AND BORROWING")
+    );
+    assert!(explanation.prose[0].starts_with("The `&s1` syntax"));
+    assert_eq!(terms(&explanation), ["reference"]);
+
+    assert!(explain::explain(&source, 0, &[]).is_none());
+    assert!(explain::explain(&source, source.lines().count() + 1, &[]).is_none());
+}
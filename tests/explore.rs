@@ -0,0 +1,160 @@
+// The interleaving explorer in `common/explore.rs`, run directly rather than through a lesson.
+
+use rust_tutor::common::explore::sync::atomic::{AtomicUsize, Ordering};
+use rust_tutor::common::explore::sync::{mpsc, Arc, Mutex};
+use rust_tutor::common::explore::{self, thread, Bounds};
+
+fn bounds(preemptions: usize) -> Bounds {
+    Bounds {
+        preemptions,
+        interleavings: 5000,
+    }
+}
+
+#[test]
+fn bounds_round_trip_through_the_environment_format() {
+    let bounds = Bounds {
+        preemptions: 3,
+        interleavings: 100,
+    };
+    assert_eq!(Bounds::parse(&bounds.to_string()), Some(bounds));
+    assert_eq!(Bounds::parse(""), Some(Bounds::default()));
+    assert_eq!(Bounds::parse("preemptions:x"), None);
+    assert_eq!(Bounds::parse("interleavings:0"), None);
+}
+
+#[test]
+fn bounds_that_do_not_parse_are_an_error_rather_than_one_interleaving() {
+    std::env::remove_var(explore::ENV);
+    assert_eq!(Bounds::from_env(), Ok(None));
+    std::env::set_var(explore::ENV, "preemptions:1,interleavings:7");
+    assert_eq!(
+        Bounds::from_env(),
+        Ok(Some(Bounds {
+            preemptions: 1,
+            interleavings: 7
+        }))
+    );
+    for bad in ["interleavings:0", "preemptions:two", "rounds:3"] {
+        std::env::set_var(explore::ENV, bad);
+        let error = Bounds::from_env().unwrap_err();
+        assert!(error.contains(bad), "{}", error);
+    }
+    std::env::remove_var(explore::ENV);
+}
+
+#[test]
+fn mutex_counter_passes_every_interleaving() {
+    let outcome = explore::check(bounds(2), || {
+        let counter = Arc::new(Mutex::new(0));
+        let handles: Vec<_> = (0..3)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || *counter.lock().unwrap() += 1)
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(*counter.lock().unwrap(), 3);
+    });
+    assert_eq!(outcome.failure, None);
+    assert!(outcome.complete);
+    assert!(outcome.interleavings > 1);
+}
+
+#[test]
+fn lost_update_is_found_with_one_preemption() {
+    let racy = || {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let handles: Vec<_> = (0..2)
+            .map(|_| {
+                let counter = Arc::clone(&counter);
+                thread::spawn(move || {
+                    let value = counter.load(Ordering::SeqCst);
+                    counter.store(value + 1, Ordering::SeqCst);
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        assert_eq!(counter.load(Ordering::SeqCst), 2);
+    };
+    // Without preemptions each thread runs its load and store together.
+    assert!(explore::check(bounds(0), racy).failure.is_none());
+
+    let failure = explore::check(bounds(1), racy).failure.unwrap();
+    assert!(
+        failure.message.starts_with("main panicked at explore.rs:"),
+        "{}",
+        failure.message
+    );
+    let loads = failure
+        .steps
+        .iter()
+        .filter(|s| s.ends_with("load 0 from atomic 1"))
+        .count();
+    assert_eq!(loads, 2, "{:#?}", failure.steps);
+}
+
+#[test]
+fn opposite_lock_order_deadlocks() {
+    let outcome = explore::check(bounds(2), || {
+        let a = Arc::new(Mutex::new(()));
+        let b = Arc::new(Mutex::new(()));
+        let other = {
+            let (a, b) = (Arc::clone(&a), Arc::clone(&b));
+            thread::spawn(move || {
+                let _b = b.lock().unwrap();
+                let _a = a.lock().unwrap();
+            })
+        };
+        {
+            let _a = a.lock().unwrap();
+            let _b = b.lock().unwrap();
+        }
+        other.join().unwrap();
+    });
+    let failure = outcome.failure.unwrap();
+    assert!(
+        failure.message.starts_with("deadlock: "),
+        "{}",
+        failure.message
+    );
+    assert!(failure.message.contains("main waits for mutex 2"));
+    assert!(failure.message.contains("thread 1 waits for mutex 1"));
+}
+
+#[test]
+fn channels_deliver_in_any_order_between_senders() {
+    let outcome = explore::check(bounds(2), || {
+        let (tx, rx) = mpsc::channel();
+        for n in 0..2 {
+            let tx = tx.clone();
+            thread::spawn(move || tx.send(n).unwrap());
+        }
+        drop(tx);
+        let received: Vec<i32> = rx.into_iter().collect();
+        assert_eq!(received.len(), 2);
+        assert_eq!(received[0], 0, "thread 2's message came first");
+    });
+    let failure = outcome.failure.unwrap();
+    assert!(failure.message.contains("thread 2's message came first"));
+}
+
+#[test]
+fn shims_work_outside_a_model() {
+    let counter = Arc::new(Mutex::new(0));
+    let (tx, rx) = mpsc::channel();
+    let handle = {
+        let counter = Arc::clone(&counter);
+        thread::spawn(move || {
+            *counter.lock().unwrap() += 1;
+            tx.send("done").unwrap();
+        })
+    };
+    assert_eq!(rx.recv(), Ok("done"));
+    handle.join().unwrap();
+    assert_eq!(*counter.lock().unwrap(), 1);
+}
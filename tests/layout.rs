@@ -0,0 +1,94 @@
+use rust_tutor::layout::{self, Kind};
+use rust_tutor::lessons;
+
+#[test]
+fn smart_pointer_types_are_found_without_the_generic_ones() {
+    let lesson = lessons::find("09_advanced/B_smart_pointers").unwrap();
+    let types = layout::extract(&lesson.source().unwrap(), &lesson.id);
+    let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["List", "RcList", "Node"]);
+
+    let Kind::Enum(variants) = &types[0].kind else {
+        panic!("List should be an enum");
+    };
+    assert_eq!(variants[0].name, "Cons");
+    assert_eq!(variants[0].fields, ["i32", "Box<List>"]);
+    assert!(variants[1].fields.is_empty());
+}
+
+#[test]
+fn visibility_is_stripped_from_types_and_fields() {
+    let source = "pub(crate) struct Plot {\n    pub(super) watered: bool,\n    pub(in crate::garden) rows: u32,\n    pub name: String,\n}\n\
+                  pub struct Pair(pub(crate) u8, pub u16);\n";
+    let types = layout::extract(source, "test/visibility");
+    let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+    assert_eq!(names, ["Plot", "Pair"]);
+    for def in &types {
+        assert!(!def.source.contains("pub"), "{}", def.source);
+    }
+    let Kind::Struct(fields) = &types[0].kind else {
+        panic!("Plot should be a struct");
+    };
+    let fields: Vec<&str> = fields.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(fields, ["watered", "rows", "name"]);
+    let Kind::Struct(fields) = &types[1].kind else {
+        panic!("Pair should be a struct");
+    };
+    assert_eq!(fields[0], (String::from("0"), String::from("u8")));
+
+    let m = layout::measure(&types, &[]).unwrap();
+    assert!(m.dropped.is_empty());
+    assert!(m.offsets.contains_key(&(
+        String::from("test/visibility"),
+        String::from("Plot"),
+        String::from("watered")
+    )));
+}
+
+#[test]
+fn types_that_do_not_compile_are_left_out() {
+    let mut types = layout::extract("struct Fine { a: u8, b: u32 }", "test/fine");
+    types.extend(layout::extract(
+        "struct Broken { a: Missing }",
+        "test/broken",
+    ));
+    let m = layout::measure(&types, &["Option<Fine>"]).unwrap();
+    assert_eq!(m.dropped, ["test/broken"]);
+    assert_eq!(m.size("test/fine", "Fine"), Some((8, 4)));
+    assert!(m.compared.contains_key("Option<Fine>"));
+    assert_eq!(m.size("test/broken", "Broken"), None);
+}
+
+#[test]
+fn types_with_the_same_name_in_two_lessons_are_measured_apart() {
+    let mut types = layout::extract("enum Coin { Penny, Nickel }", "test/early");
+    types.extend(layout::extract(
+        "enum Coin { Penny, Quarter(u64) }\nstruct Purse { coin: Coin }",
+        "test/later",
+    ));
+    let m = layout::measure(&types, &["Option<Coin>"]).unwrap();
+    assert!(m.dropped.is_empty());
+    assert_eq!(m.size("test/early", "Coin"), Some((1, 1)));
+    assert_eq!(m.size("test/later", "Coin"), Some((16, 8)));
+    assert_eq!(m.size("test/later", "Purse"), Some((16, 8)));
+    // Next to the first lesson that defines a `Coin`.
+    assert_eq!(m.compared["Option<Coin>"], (1, 1));
+}
+
+#[test]
+fn every_lesson_type_is_measured_together() {
+    let types = layout::lesson_types().unwrap();
+    assert!(types.iter().any(|t| t.name == "Asparagus"));
+    let m = layout::measure(&types, &[]).unwrap();
+    assert_eq!(m.dropped, Vec::<String>::new());
+    for def in &types {
+        assert!(
+            m.size(&def.lesson, &def.name).is_some(),
+            "{} from {} wasn't measured",
+            def.name,
+            def.lesson
+        );
+    }
+    layout::run(&[]).unwrap();
+    layout::run(&["List"]).unwrap();
+}
@@ -0,0 +1,192 @@
+// Every lesson should compile and run to completion. The clock runs in virtual time, so lessons
+// that sleep (like `09_advanced/A_concurrency.rs`) finish instantly.
+
+use rust_tutor::common::{clock, explore};
+use rust_tutor::lessons::{self, Layout};
+use rust_tutor::memdiagram::Slot;
+use rust_tutor::runner::{self, Options};
+
+fn quiet() -> Options {
+    Options {
+        echo: false,
+        clock: clock::Mode::Virtual,
+        ..Options::default()
+    }
+}
+
+// These lessons demonstrate a panic on purpose, e.g. opening a `hello.txt` that isn't there.
+const PANICS_ON_PURPOSE: [&str; 1] = ["06_error_handling/A_result_and_option"];
+
+#[test]
+fn every_lesson_runs() {
+    for lesson in lessons::all().unwrap() {
+        let run = runner::run(&lesson, &quiet()).unwrap().run;
+        assert_eq!(run.limit, None, "{} hit a sandbox limit", lesson.id);
+        if PANICS_ON_PURPOSE.contains(&lesson.id.as_str()) {
+            assert!(
+                run.stderr.contains("panicked"),
+                "{} should panic",
+                lesson.id
+            );
+        } else {
+            assert!(run.success(), "{} failed:\n{}", lesson.id, run.stderr);
+        }
+    }
+}
+
+#[test]
+fn concurrency_lesson_receives_every_message() {
+    let lesson = lessons::find("09_advanced/A_concurrency").unwrap();
+    let runner::LessonRun { run, trace, .. } = runner::run(&lesson, &quiet()).unwrap();
+
+    let mut received: Vec<&str> = run
+        .stdout
+        .lines()
+        .filter_map(|line| line.strip_prefix("Got: "))
+        .collect();
+    received.sort();
+    assert_eq!(
+        received,
+        ["for", "from", "hi", "messages", "more", "the", "thread", "you"]
+    );
+    assert!(run.stdout.contains("Result of mutex counter: 10"));
+
+    let count = |kind: &str| trace.iter().filter(|e| e.kind == kind).count();
+    assert_eq!(count("sent"), 8);
+    assert_eq!(count("received"), 8);
+    assert_eq!(count("locked"), 10);
+    assert!(trace.iter().any(|e| e.lane == "producer 2"));
+}
+
+#[test]
+fn more_concurrency_lesson_is_deterministic() {
+    let lesson = lessons::find("09_advanced/E_more_concurrency").unwrap();
+    let runner::LessonRun { run, trace, .. } = runner::run(&lesson, &quiet()).unwrap();
+    assert!(run.success(), "{}", run.stderr);
+    for line in [
+        "Atomic counter: 10000",
+        "Mutex counter: 10000",
+        "Can we write now? false",
+        "Loaded before the first check: 4 of 4 workers",
+        "Total: 500500",
+        "Full, so \"three\" has to wait",
+    ] {
+        assert!(run.stdout.contains(line), "missing {:?}", line);
+    }
+    let working: Vec<&str> = run
+        .stdout
+        .lines()
+        .filter(|line| line.starts_with("working on job"))
+        .collect();
+    assert_eq!(working.len(), 5);
+    assert!(working.windows(2).all(|w| w[0] < w[1]));
+
+    let count = |kind: &str| trace.iter().filter(|e| e.kind == kind).count();
+    assert_eq!(count("sent"), 3);
+    assert_eq!(count("received"), 3);
+}
+
+#[test]
+fn exploring_finds_the_bugs_a_single_run_misses() {
+    let lesson = lessons::find("09_advanced/F_exploring_interleavings").unwrap();
+    let once = runner::run(&lesson, &quiet()).unwrap().run;
+    assert!(once.success(), "{}", once.stderr);
+    assert_eq!(once.stdout.matches("ok in one interleaving").count(), 5);
+
+    let options = Options {
+        explore: Some(explore::Bounds {
+            preemptions: 1,
+            interleavings: 200,
+        }),
+        ..quiet()
+    };
+    let explored = runner::run(&lesson, &options).unwrap().run;
+    assert!(explored.success(), "{}", explored.stderr);
+    let line = |model: &str| {
+        let prefix = format!("{}: ", model);
+        explored
+            .stdout
+            .lines()
+            .find_map(|line| line.strip_prefix(&prefix))
+            .unwrap_or_default()
+            .to_string()
+    };
+    assert!(line("mutex counter").starts_with("the first 200 interleavings pass"));
+    assert!(line("fetch_add counter").starts_with("the first 200 interleavings pass"));
+    assert!(line("racy counter").contains("fails: main panicked"));
+    assert!(line("two locks").contains("fails: deadlock: "));
+    assert!(line("message order").contains("fails: main panicked"));
+}
+
+#[test]
+fn ownership_lesson_moves_the_same_heap_buffer() {
+    let lesson = lessons::find("03_ownership/A_ownership").unwrap();
+    let memory = runner::run(&lesson, &quiet()).unwrap().memory;
+
+    let heap_pointer = |title: &str, name: &str| {
+        let checkpoint = memory.iter().find(|c| c.title.starts_with(title)).unwrap();
+        checkpoint.slots.iter().find_map(|slot| match slot {
+            Slot::String { name: n, ptr, .. } if n == name => Some(*ptr),
+            _ => None,
+        })
+    };
+    let before = heap_pointer("MOVE: before", "s1").unwrap();
+    let after = heap_pointer("MOVE: after", "s2").unwrap();
+    assert_eq!(before, after);
+    assert_eq!(heap_pointer("MOVE: after", "s1"), None);
+
+    let original = heap_pointer("CLONE", "s1").unwrap();
+    let clone = heap_pointer("CLONE", "s2").unwrap();
+    assert_ne!(original, clone);
+}
+
+#[test]
+fn multi_file_lessons_have_their_files_in_order() {
+    let tree = lessons::find("11_modules/A_module_tree").unwrap();
+    assert_eq!(tree.layout(), Layout::ModuleTree);
+    let files: Vec<String> = tree
+        .files()
+        .unwrap()
+        .iter()
+        .map(|file| tree.file_id(file))
+        .collect();
+    assert_eq!(
+        files,
+        [
+            "11_modules/A_module_tree/main.rs",
+            "11_modules/A_module_tree/garden.rs",
+            "11_modules/A_module_tree/garden/vegetables.rs",
+            "11_modules/A_module_tree/pantry/mod.rs",
+        ]
+    );
+
+    let workspace = lessons::find("B_workspace").unwrap();
+    assert_eq!(workspace.layout(), Layout::Workspace);
+    assert!(workspace.main_file().unwrap().ends_with("app/src/main.rs"));
+    assert!(!workspace.is_test_only().unwrap());
+
+    let (lesson, file) = lessons::find_file("A_module_tree/garden/vegetables.rs").unwrap();
+    assert_eq!(lesson.id, tree.id);
+    assert!(file.ends_with("garden/vegetables.rs"));
+    assert!(lessons::find_file("A_module_tree/missing.rs").is_err());
+}
+
+#[test]
+fn workspace_lesson_uses_its_library_crate() {
+    let lesson = lessons::find("11_modules/B_workspace").unwrap();
+    let run = runner::run(&lesson, &quiet()).unwrap().run;
+    assert!(run.success(), "{}", run.stderr);
+    assert!(run.stdout.contains("HELLO, WORKSPACE!, says Ferris"));
+}
+
+#[test]
+fn custom_errors_lesson_reads_its_fixtures() {
+    let lesson = lessons::find("06_error_handling/B_custom_errors").unwrap();
+    assert!(lesson.fixtures().unwrap().join("username.txt").is_file());
+    let run = runner::run(&lesson, &quiet()).unwrap().run;
+    assert!(run.success(), "{}", run.stderr);
+    assert!(run.stdout.contains("username.txt: hello, ferris!"));
+    assert!(run.stdout.contains("empty.txt: no name yet"));
+    assert!(run.stdout.contains("is not a valid username"));
+    assert!(run.stdout.contains("  caused by: "));
+}
@@ -0,0 +1,73 @@
+// Stack/heap diagrams drawn from a fixed checkpoint dump, as `common/memory.rs` writes it.
+
+use rust_tutor::memdiagram::{self, Slot};
+
+const DUMP: &str = "\
+checkpoint\tafter `let s2 = s1;`
+moved\ts1
+string\ts2\t4096\t65536\t5\t8\thello
+checkpoint\tborrowing
+value\tn\t8192\t42
+reference\tr\t8200\t8192
+str\tgreeting\t8208\t1024\t2\thi
+";
+
+#[test]
+fn a_dump_is_parsed_into_checkpoints() {
+    let checkpoints = memdiagram::parse(DUMP);
+    assert_eq!(checkpoints.len(), 2);
+    assert_eq!(checkpoints[0].title, "after `let s2 = s1;`");
+    assert_eq!(
+        checkpoints[0].slots[0],
+        Slot::Moved {
+            name: String::from("s1")
+        }
+    );
+    assert_eq!(checkpoints[1].slots.len(), 3);
+}
+
+#[test]
+fn a_moved_binding_and_a_heap_buffer() {
+    let checkpoint = &memdiagram::parse(DUMP)[0];
+    let expected = [
+        "after `let s2 = s1;`",
+        "+------+----------------------+",
+        "| s1   | (moved)              |",
+        "+------+----------------------+",
+        "| s2   | ptr 0x10000          | ---> +---+---+---+---+---+---+---+---+",
+        "|      | len 5                |      | h | e | l | l | o | . | . | . |",
+        "|      | cap 8                |      +---+---+---+---+---+---+---+---+",
+        "+------+----------------------+",
+    ];
+    let plain = memdiagram::render(checkpoint, false);
+    assert_eq!(plain.lines().collect::<Vec<_>>(), expected, "\n{}", plain);
+
+    // In a terminal, only the moved binding's line is dimmed.
+    let colored = memdiagram::render(checkpoint, true);
+    let lines: Vec<&str> = colored.lines().collect();
+    assert_eq!(lines[2], format!("\x1b[2m{}\x1b[0m", expected[2]));
+    assert_eq!(lines[4], expected[4]);
+}
+
+#[test]
+fn references_point_at_the_stack_and_str_at_the_program() {
+    let checkpoint = &memdiagram::parse(DUMP)[1];
+    let expected = [
+        "borrowing",
+        "+----------+----------------------+",
+        "| n        | 42                   |",
+        "+----------+----------------------+",
+        "| r        | ptr 0x2000           | ---> n (on the stack)",
+        "+----------+----------------------+",
+        "| greeting | ptr 0x400            | ---> \"hi\"",
+        "|          | len 2                |      (read-only, part of the program itself)",
+        "+----------+----------------------+",
+    ];
+    let rendered = memdiagram::render(checkpoint, true);
+    assert_eq!(
+        rendered.lines().collect::<Vec<_>>(),
+        expected,
+        "\n{}",
+        rendered
+    );
+}
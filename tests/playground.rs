@@ -0,0 +1,61 @@
+// The iterator playground behind `rust_tutor pipeline`.
+
+use rust_tutor::playground::{self, Chain};
+use rust_tutor::repl::Outcome;
+
+#[test]
+fn chains_are_split_into_source_stages_and_consumer() {
+    let chain = playground::parse("(1..10).filter(|x| x % 2 == 0).map(|x| x * x).take(2)").unwrap();
+    assert_eq!(
+        chain,
+        Chain {
+            source: String::from("(1..10)"),
+            stages: vec![
+                String::from("filter(|x| x % 2 == 0)"),
+                String::from("map(|x| x * x)"),
+                String::from("take(2)"),
+            ],
+            consumer: String::from("collect::<Vec<_>>()"),
+        }
+    );
+
+    // Calls that create the iterator belong to the source, and the last call may consume it.
+    // Dots inside closures, strings and characters don't split anything.
+    let chain = playground::parse(
+        "\"a.b c\".split(' ').map(|s| s.len()).filter(|&n| n != '.' as usize).sum::<usize>();",
+    )
+    .unwrap();
+    assert_eq!(chain.source, "\"a.b c\".split(' ')");
+    assert_eq!(chain.stages.len(), 2);
+    assert_eq!(chain.consumer, "sum::<usize>()");
+
+    assert!(playground::parse("(1..10).map(|x| x").is_err());
+}
+
+#[test]
+fn every_item_is_shown_on_its_way_through_the_chain() {
+    let playground = playground::Playground::new().unwrap();
+    let chain = playground::parse("(1..10).filter(|x| x % 2 == 0).map(|x| x * x).take(2)").unwrap();
+    let Outcome::Ran { stdout, .. } = playground.run(&chain).unwrap() else {
+        panic!("the chain should run");
+    };
+    let rows: Vec<&str> = stdout.lines().collect();
+    assert_eq!(
+        rows,
+        [
+            "(1..10)       filter        map           take",
+            "1",
+            "2          -> 2          -> 4          -> 4",
+            "3",
+            "4          -> 4          -> 16         -> 16",
+            "items handed on by each stage: 4, 2, 2, 2",
+            "result: [4, 16]",
+        ]
+    );
+
+    let chain = playground::parse("(1..3).frobnicate()").unwrap();
+    assert!(matches!(
+        playground.run(&chain).unwrap(),
+        Outcome::CompileError(_)
+    ));
+}
@@ -0,0 +1,70 @@
+// Profiles keep learners sharing a checkout apart.
+
+use std::fs;
+
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::progress::Home;
+
+#[test]
+fn profiles_have_separate_progress_and_workspaces() {
+    let dir = ScratchDir::new("profiles-test").unwrap();
+    let home = Home::at(dir.path()).unwrap();
+    home.create("alice").unwrap();
+    home.create("bob").unwrap();
+    assert!(home.create("alice").is_err());
+    assert!(home.create("../eve").is_err());
+
+    let alice = home.store(Some("alice")).unwrap();
+    let bob = home.store(Some("bob")).unwrap();
+    alice
+        .record("solved", "exercise/03_ownership/first_word")
+        .unwrap();
+    assert_eq!(alice.events().unwrap().len(), 1);
+    assert!(bob.events().unwrap().is_empty());
+    assert_ne!(alice.workspace(), bob.workspace());
+    assert!(home.store(Some("carol")).is_err());
+
+    home.switch("alice").unwrap();
+    assert!(
+        home.delete("alice").is_err(),
+        "the current profile can't be deleted"
+    );
+    home.rename("alice", "alice2").unwrap();
+    assert_eq!(home.current().unwrap(), "alice2");
+    let renamed = home.store(Some("alice2")).unwrap();
+    assert_eq!(renamed.events().unwrap().len(), 1);
+
+    home.delete("bob").unwrap();
+    assert_eq!(home.profiles().unwrap(), ["alice2", "default"]);
+}
+
+#[test]
+fn progress_from_before_profiles_becomes_the_default_profile() {
+    let dir = ScratchDir::new("profiles-migrate").unwrap();
+    fs::write(
+        dir.path().join("progress.tsv"),
+        "1760000000\tsolved\tchallenge/borrowck/01_two_mutable_borrows\n",
+    )
+    .unwrap();
+    let home = Home::at(dir.path()).unwrap();
+    let store = home.store(Some("default")).unwrap();
+    assert_eq!(store.events().unwrap().len(), 1);
+    assert!(!dir.path().join("progress.tsv").exists());
+}
+
+#[test]
+fn names_outside_the_profiles_directory_are_rejected() {
+    let dir = ScratchDir::new("profiles-escape").unwrap();
+    let home = Home::at(dir.path()).unwrap();
+    // `profiles/../x` would be this directory, right next to `profiles/`.
+    let outside = dir.path().join("x");
+    fs::create_dir_all(&outside).unwrap();
+    fs::write(outside.join("keep.txt"), "still here").unwrap();
+
+    assert!(home.delete("../x").is_err());
+    assert!(home.switch("../x").is_err());
+    assert!(home.store(Some("../x")).is_err());
+    assert!(home.rename("../x", "y").is_err());
+    assert!(outside.join("keep.txt").exists());
+    assert_eq!(home.current().unwrap(), "default");
+}
@@ -0,0 +1,163 @@
+// The `Rc` graphs drawn by `common/rc_graph.rs`, built directly rather than through a lesson.
+
+use std::cell::RefCell;
+use std::rc::{Rc, Weak};
+
+use rust_tutor::common::rc_graph::{self, Inspect};
+
+struct Node {
+    name: &'static str,
+    next: RefCell<Vec<Rc<Node>>>,
+    back: RefCell<Vec<Weak<Node>>>,
+}
+
+fn node(name: &'static str) -> Rc<Node> {
+    Rc::new(Node {
+        name,
+        next: RefCell::new(Vec::new()),
+        back: RefCell::new(Vec::new()),
+    })
+}
+
+impl Inspect for Node {
+    fn label(&self) -> String {
+        self.name.to_string()
+    }
+
+    fn strong(&self) -> Vec<(String, Rc<Node>)> {
+        let next = self.next.borrow();
+        (0..next.len())
+            .map(|i| (format!("next[{}]", i), Rc::clone(&next[i])))
+            .collect()
+    }
+
+    fn weak(&self) -> Vec<(String, Weak<Node>)> {
+        let back = self.back.borrow();
+        (0..back.len())
+            .map(|i| (format!("back[{}]", i), back[i].clone()))
+            .collect()
+    }
+}
+
+#[test]
+fn a_strong_cycle_is_reported_as_a_leak() {
+    let a = node("a");
+    let b = node("b");
+    a.next.borrow_mut().push(Rc::clone(&b));
+    b.next.borrow_mut().push(Rc::clone(&a));
+
+    let graph = rc_graph::walk(&[("a", &a)]);
+    assert_eq!(graph.nodes.len(), 2);
+    // `a` is held by the variable and by `b`, and the walk's own clones aren't counted.
+    assert_eq!(graph.nodes[0].strong_count, 2);
+    assert_eq!(graph.leaks(), [vec![0, 1]]);
+
+    let ascii = graph.to_ascii();
+    assert!(
+        ascii.contains("a ==> [a] strong = 2, weak = 0\n"),
+        "{}",
+        ascii
+    );
+    assert!(ascii.contains(
+        "LEAK: a and b hold each other through strong pointers, and can never be dropped"
+    ));
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph rc {\n"));
+    assert!(
+        dot.contains("n0 -> n1 [label=\"next[0]\", style=solid];"),
+        "{}",
+        dot
+    );
+    assert!(dot.contains("n1 -> n0 [label=\"next[0]\", style=solid];"));
+    assert!(dot.contains("n0 [label=\"a\\nstrong = 2, weak = 0\", color=red];"));
+    assert!(dot.contains("root0 -> n0;"));
+
+    // Break the cycle, or the test itself would leak.
+    b.next.borrow_mut().clear();
+}
+
+#[test]
+fn every_node_on_a_strong_cycle_is_a_leak_even_when_reached_twice() {
+    // a -> b -> a, and a -> c -> b: `c` is on the cycle a -> c -> b -> a even though `b` is
+    // reached before it. `d` hangs off the cycle but is not on it, and `e` holds itself.
+    let a = node("a");
+    let b = node("b");
+    let c = node("c");
+    let d = node("d");
+    let e = node("e");
+    a.next.borrow_mut().push(Rc::clone(&b));
+    b.next.borrow_mut().push(Rc::clone(&a));
+    a.next.borrow_mut().push(Rc::clone(&c));
+    c.next.borrow_mut().push(Rc::clone(&b));
+    c.next.borrow_mut().push(Rc::clone(&d));
+    e.next.borrow_mut().push(Rc::clone(&e));
+
+    let graph = rc_graph::walk(&[("a", &a), ("e", &e)]);
+    let labels = |group: &Vec<usize>| -> Vec<&str> {
+        group
+            .iter()
+            .map(|&n| graph.nodes[n].label.as_str())
+            .collect()
+    };
+    // Groups and their members come in the order the walk reached them; sort them to compare.
+    let mut leaks: Vec<Vec<&str>> = graph.leaks().iter().map(labels).collect();
+    leaks.iter_mut().for_each(|group| group.sort());
+    leaks.sort();
+    assert_eq!(leaks, [vec!["a", "b", "c"], vec!["e"]]);
+
+    let ascii = graph.to_ascii();
+    assert!(
+        ascii.contains(
+            "LEAK: a, c and b hold each other through strong pointers, and can never be dropped"
+        ),
+        "{}",
+        ascii
+    );
+    assert!(ascii.contains("LEAK: e holds itself through strong pointers"));
+
+    let dot = graph.to_dot();
+    for (n, node) in graph.nodes.iter().enumerate() {
+        let red = format!("n{} [label=\"{}\\n", n, node.label);
+        let line = dot
+            .lines()
+            .find(|line| line.trim().starts_with(&red))
+            .unwrap();
+        assert_eq!(line.contains("color=red"), node.label != "d", "{}", line);
+    }
+
+    // Break the cycles, or the test itself would leak.
+    a.next.borrow_mut().clear();
+    b.next.borrow_mut().clear();
+    c.next.borrow_mut().clear();
+    e.next.borrow_mut().clear();
+}
+
+#[test]
+fn weak_back_pointers_are_not_a_leak() {
+    let parent = node("parent");
+    let child = node("child");
+    parent.next.borrow_mut().push(Rc::clone(&child));
+    child.back.borrow_mut().push(Rc::downgrade(&parent));
+    let gone = node("gone");
+    child.back.borrow_mut().push(Rc::downgrade(&gone));
+    drop(gone);
+
+    let graph = rc_graph::walk(&[("parent", &parent)]);
+    assert!(graph.leaks().is_empty());
+    assert_eq!(graph.nodes[0].weak_count, 1);
+
+    let ascii = graph.to_ascii();
+    assert!(ascii.contains("back[0] - -> [parent]"), "{}", ascii);
+    assert!(ascii.contains("back[1] - -> (dropped)"));
+    assert!(!ascii.contains("LEAK"));
+
+    let dot = graph.to_dot();
+    assert!(
+        dot.contains("n1 -> n0 [label=\"back[0]\", style=dashed];"),
+        "{}",
+        dot
+    );
+    assert!(dot.contains("[label=\"(dropped)\", shape=plaintext];"));
+    assert!(!dot.contains("color=red"));
+}
@@ -0,0 +1,92 @@
+// The REPL: where inputs go, when to keep reading lines, and what a session remembers.
+
+use rust_tutor::repl::{self, Input, Outcome, Session};
+
+#[test]
+fn inputs_are_classified_by_their_first_word() {
+    assert!(matches!(
+        repl::classify("fn double(x: i32) -> i32 { x * 2 }"),
+        Input::Item(_)
+    ));
+    assert!(matches!(
+        repl::classify("#[derive(Debug)] struct P;"),
+        Input::Item(_)
+    ));
+    assert!(matches!(
+        repl::classify("for i in 0..3 { }"),
+        Input::Statement(_)
+    ));
+    assert_eq!(
+        repl::classify("let x = 5"),
+        Input::Statement(String::from("let x = 5;"))
+    );
+    assert_eq!(
+        repl::classify(" x + 1 "),
+        Input::Expression(String::from("x + 1"))
+    );
+}
+
+#[test]
+fn brackets_in_literals_and_comments_do_not_count() {
+    assert_eq!(repl::open_brackets("fn main() {"), 1);
+    assert_eq!(repl::open_brackets("let s = \"(\\\"[\";"), 0);
+    assert_eq!(repl::open_brackets("let c = '(';"), 0);
+    assert_eq!(repl::open_brackets("let c = '\\'';"), 0);
+    assert_eq!(repl::open_brackets("let c = '\\u{28}';"), 0);
+    assert_eq!(repl::open_brackets("let v = vec![b'['];"), 0);
+    assert_eq!(repl::open_brackets("let x = 1; // {"), 0);
+    assert_eq!(repl::open_brackets("let s = r\"{\";"), 0);
+    assert_eq!(repl::open_brackets("let s = r#\"{ \"( \"#;"), 0);
+    assert_eq!(repl::open_brackets("let s = br##\"[\"#\"##; ("), 1);
+    assert_eq!(repl::open_brackets("let x = 1; /* { /* ( */ [ */"), 0);
+    assert_eq!(repl::open_brackets("/* an unfinished comment {"), 0);
+    assert_eq!(repl::open_brackets("let r#type = [1];"), 0);
+    // A lifetime isn't the start of a char literal.
+    assert_eq!(
+        repl::open_brackets("fn first<'a>(s: &'a str) -> &'a str {"),
+        1
+    );
+}
+
+fn stdout(outcome: Outcome) -> String {
+    match outcome {
+        Outcome::Ran { stdout, .. } => stdout,
+        Outcome::CompileError(errors) => panic!("doesn't compile:\n{}", errors),
+        Outcome::Failed { stderr, .. } => panic!("failed:\n{}", stderr),
+    }
+}
+
+#[test]
+fn a_session_remembers_what_ran() {
+    let mut session = Session::new().unwrap();
+    stdout(session.eval("fn double(x: i32) -> i32 { x * 2 }").unwrap());
+    stdout(session.eval("let x = double(20);").unwrap());
+    stdout(session.eval("let c = '(';").unwrap());
+    assert_eq!(stdout(session.eval("x + 2").unwrap()), "42\n");
+    assert_eq!(stdout(session.eval("c").unwrap()), "'('\n");
+
+    // Inputs that don't compile or panic are dropped, and earlier ones still hold.
+    assert!(matches!(
+        session.eval("let y = undefined;").unwrap(),
+        Outcome::CompileError(_)
+    ));
+    assert!(matches!(
+        session
+            .eval("let z: i32 = \"x\".parse().unwrap();")
+            .unwrap(),
+        Outcome::Failed { .. }
+    ));
+    assert!(!session.source().contains("undefined"));
+    assert!(!session.source().contains("parse"));
+    assert_eq!(stdout(session.eval("x").unwrap()), "40\n");
+
+    // Replayed statements run again, but only the newest input's output is shown.
+    stdout(session.eval("println!(\"once\");").unwrap());
+    assert_eq!(stdout(session.eval("x + 1").unwrap()), "41\n");
+
+    session.reset();
+    assert!(matches!(
+        session.eval("x").unwrap(),
+        Outcome::CompileError(_)
+    ));
+}
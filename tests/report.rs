@@ -0,0 +1,65 @@
+// Error reports from `common/report.rs`, which the tutor uses for its own errors.
+
+use std::fs;
+use std::io;
+use std::num::ParseIntError;
+
+use rust_tutor::{Context, Report};
+
+fn port(text: &str) -> Result<u16, Report> {
+    let port = text.parse::<u16>().context("`port` is not a number")?;
+    Ok(port)
+}
+
+#[test]
+fn context_wraps_the_error_as_its_source() {
+    let report = port("eighty").context("loading server.conf").unwrap_err();
+    let chain: Vec<String> = report.chain().map(|e| e.to_string()).collect();
+    assert_eq!(
+        chain,
+        [
+            "loading server.conf",
+            "`port` is not a number",
+            "invalid digit found in string"
+        ]
+    );
+    assert_eq!(
+        report.root_cause().to_string(),
+        "invalid digit found in string"
+    );
+    assert!(report.downcast_ref::<ParseIntError>().is_some());
+    assert!(report.downcast_ref::<io::Error>().is_none());
+}
+
+#[test]
+fn reports_print_one_line_or_one_cause_per_line() {
+    let report = port("-1").context("loading server.conf").unwrap_err();
+    assert_eq!(report.to_string(), "loading server.conf");
+    assert_eq!(
+        format!("{:#}", report),
+        "loading server.conf: `port` is not a number: invalid digit found in string"
+    );
+    let debug = format!("{:?}", report);
+    assert!(debug.starts_with(
+        "loading server.conf\n\nCaused by:\n    0: `port` is not a number\n    1: invalid digit"
+    ));
+}
+
+#[test]
+fn options_and_messages_become_reports() {
+    let missing: Option<u8> = None;
+    let report = missing.context("no `port` setting").unwrap_err();
+    assert_eq!(format!("{:#}", report), "no `port` setting");
+    assert_eq!(report.chain().count(), 1);
+
+    let lazy = Some(3).with_context(|| -> String { unreachable!() });
+    assert_eq!(lazy.unwrap(), 3);
+
+    let io = fs::read("/nonexistent/rust_tutor").with_context(|| "reading the file");
+    let report = io.unwrap_err();
+    assert_eq!(
+        report.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::NotFound
+    );
+    assert_eq!(Report::msg("plain").to_string(), "plain");
+}
@@ -0,0 +1,89 @@
+// Each of the sandbox's limits, hit by a small program with a small `Limits`.
+
+use std::fs;
+use std::time::{Duration, Instant};
+
+use rust_tutor::compiler;
+use rust_tutor::sandbox::{Limit, Limits, Run, Sandbox};
+
+fn small() -> Limits {
+    Limits {
+        cpu_time: Duration::from_secs(1),
+        wall_time: Duration::from_secs(5),
+        memory: 256 * 1024 * 1024,
+        output: 4096,
+    }
+}
+
+fn run(limits: Limits, main: &str) -> Run {
+    let sandbox = Sandbox::new(limits).unwrap();
+    let source = sandbox.dir().join("main.rs");
+    let binary = sandbox.dir().join("main");
+    fs::write(&source, format!("fn main() {{\n{}\n}}\n", main)).unwrap();
+    let compiled = compiler::compile(&source, &binary, &[]).unwrap();
+    assert!(compiled.success, "{}", compiled.stderr);
+    sandbox.run(&binary, &[]).unwrap()
+}
+
+#[test]
+fn a_program_within_the_limits_just_runs() {
+    let run = run(small(), "println!(\"hello\");");
+    assert!(run.success());
+    assert_eq!(run.stdout, "hello\n");
+}
+
+#[cfg(unix)]
+#[test]
+fn an_endless_loop_hits_the_cpu_limit() {
+    let run = run(
+        small(),
+        "let mut n = 0u64; loop { n = std::hint::black_box(n + 1); }",
+    );
+    assert_eq!(run.limit, Some(Limit::CpuTime));
+}
+
+#[test]
+fn sleeping_hits_the_wall_clock_limit() {
+    let limits = Limits {
+        wall_time: Duration::from_millis(500),
+        ..small()
+    };
+    let started = Instant::now();
+    let run = run(
+        limits,
+        "std::thread::sleep(std::time::Duration::from_secs(60));",
+    );
+    assert_eq!(run.limit, Some(Limit::WallTime));
+    assert!(started.elapsed() < Duration::from_secs(5));
+}
+
+#[cfg(unix)]
+#[test]
+fn a_large_allocation_hits_the_memory_limit() {
+    let run = run(
+        small(),
+        "let v = vec![1u8; 1 << 30]; println!(\"{}\", v[v.len() - 1]);",
+    );
+    assert_eq!(run.limit, Some(Limit::Memory));
+}
+
+#[test]
+fn a_flood_of_output_hits_the_output_limit() {
+    let run = run(small(), "loop { println!(\"all work and no play\"); }");
+    assert_eq!(run.limit, Some(Limit::Output));
+    assert!(run.stdout.len() <= 4096);
+}
+
+// The hard CPU limit kills with SIGKILL, but not every SIGKILL comes from it.
+#[cfg(unix)]
+#[test]
+fn being_killed_early_is_not_blamed_on_the_cpu_limit() {
+    use std::os::unix::process::ExitStatusExt;
+
+    let run = run(
+        small(),
+        "extern \"C\" { fn raise(signal: i32) -> i32; }\nunsafe { raise(9); }",
+    );
+    assert_eq!(run.status.signal(), Some(9));
+    assert_eq!(run.limit, None);
+}
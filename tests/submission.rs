@@ -0,0 +1,180 @@
+// Submission archives: packing, signing, verifying, and catching changes made after submitting.
+
+use rust_tutor::classroom;
+use rust_tutor::compiler::ScratchDir;
+use rust_tutor::exercises;
+use rust_tutor::progress::Store;
+use rust_tutor::sha256;
+use rust_tutor::submission::{self, Expected};
+use rust_tutor::tar::{self, Entry};
+
+#[test]
+fn tar_archives_round_trip_including_long_paths() {
+    let long = format!("{}/{}.rs", "chapter".repeat(12), "exercise".repeat(8));
+    assert!(long.len() > 100);
+    let entries = vec![
+        Entry {
+            path: String::from("MANIFEST.txt"),
+            data: b"learner ada\n".to_vec(),
+        },
+        Entry {
+            path: long.clone(),
+            data: vec![7; 1300],
+        },
+        Entry {
+            path: String::from("empty.txt"),
+            data: Vec::new(),
+        },
+    ];
+    let archive = tar::write(&entries, 1_700_000_000).unwrap();
+    assert_eq!(archive.len() % 512, 0);
+
+    let read = tar::read(&archive).unwrap();
+    assert_eq!(read.len(), 3);
+    for (a, b) in entries.iter().zip(&read) {
+        assert_eq!(a.path, b.path);
+        assert_eq!(a.data, b.data);
+    }
+}
+
+#[test]
+fn hmac_matches_the_standard_test_vectors() {
+    assert_eq!(
+        sha256::hmac_hex(&[0x0b; 20], b"Hi There"),
+        "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7"
+    );
+    // Keys longer than a block are hashed first.
+    assert_eq!(
+        sha256::hmac_hex(
+            &[0xaa; 131],
+            b"Test Using Larger Than Block-Size Key - Hash Key First"
+        ),
+        "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+    );
+}
+
+const SECRET: &str = "correct horse battery staple";
+
+#[test]
+fn a_changed_submission_does_not_verify() {
+    let dir = ScratchDir::new("submission-test").unwrap();
+    let store = Store::at(dir.path().join("ada")).unwrap();
+    let exercise = exercises::find("first_word").unwrap();
+    exercises::start(&store, &exercise).unwrap();
+    let assignment = classroom::Manifest {
+        curriculum: String::from("0123456789abcdef"),
+        exercises: vec![exercise.id.clone()],
+        files: classroom::assigned_files(std::slice::from_ref(&exercise)).unwrap(),
+    };
+
+    let key = submission::learner_key(SECRET, "ada");
+    let (archive, receipt) =
+        submission::pack(&store, &[exercise], "0123456789abcdef", &key).unwrap();
+    let expected = Expected {
+        secret: Some(SECRET),
+        assignment: Some(&assignment),
+        receipt: Some(&receipt),
+    };
+    let verified = submission::verify(&archive, &expected).unwrap();
+    assert!(verified.problems.is_empty(), "{:?}", verified.problems);
+    assert_eq!(verified.manifest.learner, "ada");
+    assert_eq!(verified.manifest.files.len(), 2);
+    assert_eq!(verified.manifest.assigned, assignment.files);
+
+    // The wrong curriculum version.
+    let other = classroom::Manifest {
+        curriculum: String::from("fedcba9876543210"),
+        ..assignment.clone()
+    };
+    let wrong_curriculum = Expected {
+        assignment: Some(&other),
+        ..Expected::default()
+    };
+    let verified = submission::verify(&archive, &wrong_curriculum).unwrap();
+    assert_eq!(verified.problems.len(), 1);
+
+    // A solution edited after submitting, with the manifest left alone.
+    let mut entries = tar::read(&archive).unwrap();
+    let file = entries
+        .iter_mut()
+        .find(|e| e.path.starts_with("exercises/"))
+        .unwrap();
+    file.data.extend_from_slice(b"\n// fixed later\n");
+    let changed = tar::write(&entries, 0).unwrap();
+    let verified = submission::verify(&changed, &Expected::default()).unwrap();
+    assert_eq!(
+        verified.problems,
+        ["exercises/03_ownership/first_word.rs has been changed"]
+    );
+
+    // The same, with the manifest rewritten to match: the signature catches it, and so does the
+    // receipt.
+    let hash = sha256::hex(&entries[2].data);
+    let manifest = &mut entries[0];
+    let mut text = String::from_utf8(manifest.data.clone()).unwrap();
+    let old = text
+        .lines()
+        .find(|l| l.ends_with("first_word.rs"))
+        .unwrap()
+        .to_string();
+    text = text.replace(
+        &old,
+        &format!("file {}  exercises/03_ownership/first_word.rs", hash),
+    );
+    manifest.data = text.into_bytes();
+    let rewritten = tar::write(&entries, 0).unwrap();
+    assert!(submission::verify(&rewritten, &Expected::default())
+        .unwrap()
+        .problems
+        .is_empty());
+    let verified = submission::verify(&rewritten, &expected).unwrap();
+    assert_eq!(verified.problems.len(), 2, "{:?}", verified.problems);
+    assert!(verified.problems[0].starts_with("the signature doesn't match"));
+}
+
+#[test]
+fn a_submission_must_be_signed_by_its_learner_against_the_assigned_files() {
+    let dir = ScratchDir::new("submission-test").unwrap();
+    let store = Store::at(dir.path().join("ada")).unwrap();
+    let exercise = exercises::find("first_word").unwrap();
+    exercises::start(&store, &exercise).unwrap();
+    let mut assignment = classroom::Manifest {
+        curriculum: String::from("0123456789abcdef"),
+        exercises: vec![exercise.id.clone()],
+        files: classroom::assigned_files(std::slice::from_ref(&exercise)).unwrap(),
+    };
+    let exercises = [exercise];
+
+    // Signed with somebody else's key, so anyone with that key could have made it.
+    let bobs_key = submission::learner_key(SECRET, "bob");
+    let (archive, _) = submission::pack(&store, &exercises, "0123456789abcdef", &bobs_key).unwrap();
+    let expected = Expected {
+        secret: Some(SECRET),
+        assignment: Some(&assignment),
+        ..Expected::default()
+    };
+    let verified = submission::verify(&archive, &expected).unwrap();
+    assert_eq!(verified.problems.len(), 1);
+    assert!(verified.problems[0].contains("ada's key"));
+
+    // Correctly signed, but worked against hidden tests that weren't the assigned ones.
+    let key = submission::learner_key(SECRET, "ada");
+    let (archive, _) = submission::pack(&store, &exercises, "0123456789abcdef", &key).unwrap();
+    let hidden = "exercises/03_ownership/first_word/hidden_tests.rs";
+    let (_, hash) = assignment
+        .files
+        .iter_mut()
+        .find(|(path, _)| path == hidden)
+        .unwrap();
+    *hash = sha256::hex(b"different tests");
+    let expected = Expected {
+        secret: Some(SECRET),
+        assignment: Some(&assignment),
+        ..Expected::default()
+    };
+    let verified = submission::verify(&archive, &expected).unwrap();
+    assert_eq!(
+        verified.problems,
+        [format!("{} is not the assigned version", hidden)]
+    );
+}
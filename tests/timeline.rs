@@ -0,0 +1,63 @@
+// The thread timeline drawn after a traced lesson, from a fixed list of events.
+
+use rust_tutor::timeline::{self, Event};
+
+fn events(text: &str) -> Vec<Event> {
+    timeline::parse(text)
+}
+
+// `main` starts two workers one after the other, then a third while the second is running.
+// The first worker is done before the second starts, so the second reuses its column.
+const TRACE: &str = "\
+0\t100000\tmain\tsay\tstarting
+1\t200000\tworker 1\tspawned\tworker 1
+2\t300000\tworker 1\tsent\ttx 1
+3\t400000\tmain\treceived\trx 1
+4\t500000\tmain\tjoined\tworker 1
+5\t600000\tworker 2\tspawned\tworker 2
+6\t700000\tworker 3\tspawned\tworker 3
+7\t800000\tworker 2\tlocked\tcounter
+8\t900000\tworker 2\tunlocked\tcounter
+9\t1000000\tworker 3\tsay\tdone\\twith\\\\tabs
+";
+
+#[test]
+fn lanes_are_drawn_in_columns_that_get_reused() {
+    let rendered = timeline::render(&events(TRACE), 60);
+    let expected = [
+        "     time | main           | worker 1       | worker 3",
+        "------------------------------------------------------",
+        "    0.1ms | starting       |                |",
+        "    0.2ms | |              | + worker 1     |",
+        "    0.3ms | |              | tx.send(1)     |",
+        "    0.4ms | rx got 1       |                |",
+        "    0.5ms | joined worke.. |                |",
+        "    0.6ms | |              | + worker 2     |",
+        "    0.7ms | |              | |              | + worker 3",
+        "    0.8ms | |              | [locked coun.. | |",
+        "    0.9ms | |              | [unlocked co.. | |",
+        "    1.0ms | |              |                | done with\\tabs",
+    ];
+    assert_eq!(
+        rendered.lines().collect::<Vec<_>>(),
+        expected,
+        "\n{}",
+        rendered
+    );
+}
+
+#[test]
+fn main_keeps_the_first_column_while_idle() {
+    // `main` has no event until the end, but its column is still the first, and is drawn as
+    // alive the whole time.
+    let trace = "\
+0\t0\tworker\tspawned\tworker
+1\t1000\tmain\tjoined\tworker
+";
+    let rendered = timeline::render(&events(trace), 40);
+    let lines: Vec<&str> = rendered.lines().collect();
+    assert_eq!(lines[0], "     time | main         | worker");
+    assert_eq!(lines[2], "    0.0ms | |            | + worker");
+    assert_eq!(lines[3], "    0.0ms | joined wor.. |");
+    assert_eq!(timeline::render(&[], 40), "");
+}
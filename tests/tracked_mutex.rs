@@ -0,0 +1,125 @@
+// The lock-order tracking in `common/tracked_mutex.rs`. The tests share one lock-order graph,
+// so each names its mutexes and only looks at the reports that mention them.
+
+use std::sync::Arc;
+use std::thread;
+
+use rust_tutor::common::tracked_mutex::{self, TrackedMutex};
+
+fn reports_about(name: &str) -> Vec<String> {
+    tracked_mutex::potential_deadlocks()
+        .into_iter()
+        .filter(|report| report.contains(&format!("`{}`", name)))
+        .collect()
+}
+
+// Locks `first` then `second` on a thread with the given name, and waits for it to finish.
+fn lock_both(name: &str, first: &Arc<TrackedMutex<i32>>, second: &Arc<TrackedMutex<i32>>) {
+    let (first, second) = (Arc::clone(first), Arc::clone(second));
+    thread::Builder::new()
+        .name(name.to_string())
+        .spawn(move || {
+            let mut a = first.lock().unwrap();
+            let mut b = second.lock().unwrap();
+            *a += 1;
+            *b += 1;
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn opposite_orders_are_reported_without_deadlocking() {
+    let checking = Arc::new(TrackedMutex::named("checking", 0));
+    let savings = Arc::new(TrackedMutex::named("savings", 0));
+    lock_both("alice", &checking, &savings);
+    assert_eq!(reports_about("checking"), Vec::<String>::new());
+    lock_both("bob", &savings, &checking);
+    lock_both("bob", &savings, &checking);
+
+    let reports = reports_about("checking");
+    assert_eq!(reports.len(), 1, "{:#?}", reports);
+    let lines: Vec<&str> = reports[0].lines().collect();
+    assert_eq!(
+        lines[0],
+        "potential deadlock: these threads take the same locks in a circle"
+    );
+    assert!(
+        lines[1].starts_with("  `bob`: tracked_mutex.rs:") && lines[1].contains("locks `savings`"),
+        "{}",
+        lines[1]
+    );
+    assert!(lines[2].starts_with("  `alice`: tracked_mutex.rs:"));
+    assert!(lines[2].ends_with("locks `savings`"));
+    assert_eq!(*checking.lock().unwrap(), 3);
+}
+
+#[test]
+fn a_circle_through_three_threads_is_found() {
+    let locks: Vec<_> = ["ring 0", "ring 1", "ring 2"]
+        .into_iter()
+        .map(|name| Arc::new(TrackedMutex::named(name, 0)))
+        .collect();
+    lock_both("first", &locks[0], &locks[1]);
+    lock_both("second", &locks[1], &locks[2]);
+    assert!(reports_about("ring 0").is_empty());
+    lock_both("third", &locks[2], &locks[0]);
+
+    let reports = reports_about("ring 0");
+    assert_eq!(reports.len(), 1, "{:#?}", reports);
+    assert_eq!(reports[0].lines().count(), 4);
+    for thread in ["first", "second", "third"] {
+        assert!(reports[0].contains(&format!("`{}`", thread)));
+    }
+}
+
+#[test]
+fn a_consistent_order_is_never_reported() {
+    let low = Arc::new(TrackedMutex::named("low", 0));
+    let high = Arc::new(TrackedMutex::named("high", 0));
+    let handles: Vec<_> = (0..4)
+        .map(|_| {
+            let (low, high) = (Arc::clone(&low), Arc::clone(&high));
+            thread::spawn(move || {
+                for _ in 0..100 {
+                    let mut a = low.lock().unwrap();
+                    let mut b = high.lock().unwrap();
+                    *a += 1;
+                    *b += 1;
+                }
+            })
+        })
+        .collect();
+    for handle in handles {
+        handle.join().unwrap();
+    }
+    // Locking one at a time, in either order, holds nothing while waiting.
+    drop(high.lock().unwrap());
+    drop(low.lock().unwrap());
+    assert!(reports_about("low").is_empty());
+    assert_eq!(*high.lock().unwrap(), 400);
+}
+
+#[test]
+fn locking_a_held_mutex_again_panics() {
+    let lock = Arc::new(TrackedMutex::new(0));
+    let again = Arc::clone(&lock);
+    let panic = thread::Builder::new()
+        .name("twice".to_string())
+        .spawn(move || {
+            let _first = again.lock().unwrap();
+            let _second = again.lock().unwrap();
+        })
+        .unwrap()
+        .join()
+        .unwrap_err();
+    let message = panic.downcast_ref::<String>().unwrap();
+    assert!(
+        message.starts_with("deadlock: `twice` locks `mutex from tracked_mutex.rs:"),
+        "{}",
+        message
+    );
+    // The panic poisoned it, as with `std::sync::Mutex`.
+    assert!(lock.lock().is_err());
+}